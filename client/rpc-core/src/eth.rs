@@ -257,9 +257,10 @@ pub trait EthApi {
 	#[method(name = "eth_sendTransaction")]
 	async fn send_transaction(&self, request: TransactionRequest) -> RpcResult<H256>;
 
-	/// Sends signed transaction, returning its hash.
+	/// Sends signed transaction, returning its hash. If `from` is given, the transaction is
+	/// rejected unless it is signed by `from`.
 	#[method(name = "eth_sendRawTransaction")]
-	async fn send_raw_transaction(&self, bytes: Bytes) -> RpcResult<H256>;
+	async fn send_raw_transaction(&self, bytes: Bytes, from: Option<H160>) -> RpcResult<H256>;
 }
 
 /// Eth filters rpc api (polling).
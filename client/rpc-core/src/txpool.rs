@@ -18,7 +18,9 @@
 
 //! tx pool rpc interface
 
-use ethereum_types::U256;
+use std::collections::HashMap;
+
+use ethereum_types::{H160, H256, U256};
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
 
 use crate::types::*;
@@ -65,4 +67,10 @@ pub trait TxPoolApi {
 	/// or [txpool_status (nethermind)](https://docs.nethermind.io/nethermind/ethereum-client/json-rpc/txpool#txpool_status).
 	#[method(name = "txpool_status")]
 	fn status(&self) -> RpcResult<TxPoolResult<U256>>;
+
+	/// Returns the hashes of the transactions sent by `address` that are queued for future
+	/// execution only, keyed by nonce. A queued transaction waits for a nonce gap to be filled,
+	/// so wallets can use this to find stuck transactions and replace them.
+	#[method(name = "txpool_queuedFrom")]
+	fn queued_from(&self, address: H160) -> RpcResult<HashMap<U256, H256>>;
 }
@@ -33,6 +33,7 @@ mod transaction;
 mod transaction_request;
 #[cfg(feature = "txpool")]
 mod txpool;
+mod typed_data;
 mod work;
 
 pub mod pubsub;
@@ -60,12 +61,13 @@ pub use self::{
 		ChainStatus, EthProtocolInfo, PeerCount, PeerInfo, PeerNetworkInfo, PeerProtocolsInfo,
 		Peers, PipProtocolInfo, SyncInfo, SyncStatus, TransactionStats,
 	},
-	transaction::{LocalTransactionStatus, RichRawTransaction, Transaction},
+	transaction::{LocalTransactionStatus, RichRawTransaction, SerializeTransaction, Transaction},
 	transaction_request::{TransactionMessage, TransactionRequest},
+	typed_data::{TypedData, TypedDataField},
 	work::Work,
 };
 
 /// The trait that used to build types from the `from` address and ethereum `transaction`.
-pub trait BuildFrom {
-	fn build_from(from: H160, transaction: &EthereumTransaction) -> Self;
+pub trait BuildFrom<T = EthereumTransaction> {
+	fn build_from(from: H160, transaction: &T) -> Self;
 }
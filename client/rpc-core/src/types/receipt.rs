@@ -60,4 +60,7 @@ pub struct Receipt {
 	/// EIP-2718 type
 	#[serde(rename = "type")]
 	pub transaction_type: U256,
+	/// Revert reason of a failed transaction
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub revert_reason: Option<String>,
 }
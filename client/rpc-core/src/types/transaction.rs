@@ -16,7 +16,10 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
-use ethereum::{AccessListItem, TransactionAction, TransactionV2 as EthereumTransaction};
+use ethereum::{
+	AccessListItem, EIP1559Transaction, EIP2930Transaction, LegacyTransaction, TransactionAction,
+	TransactionV2 as EthereumTransaction,
+};
 use ethereum_types::{H160, H256, U256, U64};
 use serde::{ser::SerializeStruct, Serialize, Serializer};
 
@@ -81,93 +84,144 @@ pub struct Transaction {
 	pub s: U256,
 }
 
-impl BuildFrom for Transaction {
-	fn build_from(from: H160, transaction: &EthereumTransaction) -> Self {
-		let hash = transaction.hash();
-		match transaction {
-			EthereumTransaction::Legacy(t) => Self {
-				transaction_type: U256::from(0),
-				hash,
-				nonce: t.nonce,
-				block_hash: None,
-				block_number: None,
-				transaction_index: None,
-				from,
-				to: match t.action {
-					TransactionAction::Call(to) => Some(to),
-					TransactionAction::Create => None,
-				},
-				value: t.value,
-				gas: t.gas_limit,
-				gas_price: Some(t.gas_price),
-				max_fee_per_gas: None,
-				max_priority_fee_per_gas: None,
-				input: Bytes(t.input.clone()),
-				creates: None,
-				chain_id: t.signature.chain_id().map(U64::from),
-				access_list: None,
-				y_parity: None,
-				v: Some(U256::from(t.signature.v())),
-				r: U256::from(t.signature.r().as_bytes()),
-				s: U256::from(t.signature.s().as_bytes()),
+/// Builds the RPC [`Transaction`] of a typed transaction.
+///
+/// [`Transaction`] can be built from any implementor through [`BuildFrom`], so a downstream
+/// crate with experimental transaction types can wrap `TransactionV2` in its own envelope,
+/// implement this trait for it, and forward the existing variants to the implementations below.
+pub trait SerializeTransaction {
+	/// The hash of the transaction.
+	fn transaction_hash(&self) -> H256;
+	/// Build the RPC representation of the transaction, with the given `hash` and sender.
+	fn to_rpc_transaction(&self, hash: H256, from: H160) -> Transaction;
+}
+
+impl SerializeTransaction for EthereumTransaction {
+	fn transaction_hash(&self) -> H256 {
+		self.hash()
+	}
+
+	fn to_rpc_transaction(&self, hash: H256, from: H160) -> Transaction {
+		match self {
+			EthereumTransaction::Legacy(t) => t.to_rpc_transaction(hash, from),
+			EthereumTransaction::EIP2930(t) => t.to_rpc_transaction(hash, from),
+			EthereumTransaction::EIP1559(t) => t.to_rpc_transaction(hash, from),
+		}
+	}
+}
+
+impl SerializeTransaction for LegacyTransaction {
+	fn transaction_hash(&self) -> H256 {
+		self.hash()
+	}
+
+	fn to_rpc_transaction(&self, hash: H256, from: H160) -> Transaction {
+		Transaction {
+			transaction_type: U256::from(0),
+			hash,
+			nonce: self.nonce,
+			block_hash: None,
+			block_number: None,
+			transaction_index: None,
+			from,
+			to: match self.action {
+				TransactionAction::Call(to) => Some(to),
+				TransactionAction::Create => None,
 			},
-			EthereumTransaction::EIP2930(t) => Self {
-				transaction_type: U256::from(1),
-				hash,
-				nonce: t.nonce,
-				block_hash: None,
-				block_number: None,
-				transaction_index: None,
-				from,
-				to: match t.action {
-					TransactionAction::Call(to) => Some(to),
-					TransactionAction::Create => None,
-				},
-				value: t.value,
-				gas: t.gas_limit,
-				gas_price: Some(t.gas_price),
-				max_fee_per_gas: None,
-				max_priority_fee_per_gas: None,
-				input: Bytes(t.input.clone()),
-				creates: None,
-				chain_id: Some(U64::from(t.chain_id)),
-				access_list: Some(t.access_list.clone()),
-				y_parity: Some(U256::from(t.odd_y_parity as u8)),
-				v: Some(U256::from(t.odd_y_parity as u8)),
-				r: U256::from(t.r.as_bytes()),
-				s: U256::from(t.s.as_bytes()),
+			value: self.value,
+			gas: self.gas_limit,
+			gas_price: Some(self.gas_price),
+			max_fee_per_gas: None,
+			max_priority_fee_per_gas: None,
+			input: Bytes(self.input.clone()),
+			creates: None,
+			chain_id: self.signature.chain_id().map(U64::from),
+			access_list: None,
+			y_parity: None,
+			v: Some(U256::from(self.signature.v())),
+			r: U256::from(self.signature.r().as_bytes()),
+			s: U256::from(self.signature.s().as_bytes()),
+		}
+	}
+}
+
+impl SerializeTransaction for EIP2930Transaction {
+	fn transaction_hash(&self) -> H256 {
+		self.hash()
+	}
+
+	fn to_rpc_transaction(&self, hash: H256, from: H160) -> Transaction {
+		Transaction {
+			transaction_type: U256::from(1),
+			hash,
+			nonce: self.nonce,
+			block_hash: None,
+			block_number: None,
+			transaction_index: None,
+			from,
+			to: match self.action {
+				TransactionAction::Call(to) => Some(to),
+				TransactionAction::Create => None,
 			},
-			EthereumTransaction::EIP1559(t) => Self {
-				transaction_type: U256::from(2),
-				hash,
-				nonce: t.nonce,
-				block_hash: None,
-				block_number: None,
-				transaction_index: None,
-				from,
-				to: match t.action {
-					TransactionAction::Call(to) => Some(to),
-					TransactionAction::Create => None,
-				},
-				value: t.value,
-				gas: t.gas_limit,
-				// If transaction is not mined yet, gas price is considered just max fee per gas.
-				gas_price: Some(t.max_fee_per_gas),
-				max_fee_per_gas: Some(t.max_fee_per_gas),
-				max_priority_fee_per_gas: Some(t.max_priority_fee_per_gas),
-				input: Bytes(t.input.clone()),
-				creates: None,
-				chain_id: Some(U64::from(t.chain_id)),
-				access_list: Some(t.access_list.clone()),
-				y_parity: Some(U256::from(t.odd_y_parity as u8)),
-				v: Some(U256::from(t.odd_y_parity as u8)),
-				r: U256::from(t.r.as_bytes()),
-				s: U256::from(t.s.as_bytes()),
+			value: self.value,
+			gas: self.gas_limit,
+			gas_price: Some(self.gas_price),
+			max_fee_per_gas: None,
+			max_priority_fee_per_gas: None,
+			input: Bytes(self.input.clone()),
+			creates: None,
+			chain_id: Some(U64::from(self.chain_id)),
+			access_list: Some(self.access_list.clone()),
+			y_parity: Some(U256::from(self.odd_y_parity as u8)),
+			v: Some(U256::from(self.odd_y_parity as u8)),
+			r: U256::from(self.r.as_bytes()),
+			s: U256::from(self.s.as_bytes()),
+		}
+	}
+}
+
+impl SerializeTransaction for EIP1559Transaction {
+	fn transaction_hash(&self) -> H256 {
+		self.hash()
+	}
+
+	fn to_rpc_transaction(&self, hash: H256, from: H160) -> Transaction {
+		Transaction {
+			transaction_type: U256::from(2),
+			hash,
+			nonce: self.nonce,
+			block_hash: None,
+			block_number: None,
+			transaction_index: None,
+			from,
+			to: match self.action {
+				TransactionAction::Call(to) => Some(to),
+				TransactionAction::Create => None,
 			},
+			value: self.value,
+			gas: self.gas_limit,
+			// If transaction is not mined yet, gas price is considered just max fee per gas.
+			gas_price: Some(self.max_fee_per_gas),
+			max_fee_per_gas: Some(self.max_fee_per_gas),
+			max_priority_fee_per_gas: Some(self.max_priority_fee_per_gas),
+			input: Bytes(self.input.clone()),
+			creates: None,
+			chain_id: Some(U64::from(self.chain_id)),
+			access_list: Some(self.access_list.clone()),
+			y_parity: Some(U256::from(self.odd_y_parity as u8)),
+			v: Some(U256::from(self.odd_y_parity as u8)),
+			r: U256::from(self.r.as_bytes()),
+			s: U256::from(self.s.as_bytes()),
 		}
 	}
 }
 
+impl<T: SerializeTransaction> BuildFrom<T> for Transaction {
+	fn build_from(from: H160, transaction: &T) -> Self {
+		transaction.to_rpc_transaction(transaction.transaction_hash(), from)
+	}
+}
+
 /// Local Transaction Status
 #[derive(Debug)]
 pub enum LocalTransactionStatus {
@@ -258,3 +312,145 @@ pub struct RichRawTransaction {
 	#[serde(rename = "tx")]
 	pub transaction: Transaction,
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use ethereum::TransactionSignature;
+	use serde_json::Value;
+
+	fn serialize(transaction: &EthereumTransaction) -> Value {
+		serde_json::to_value(Transaction::build_from(
+			H160::repeat_byte(0x11),
+			transaction,
+		))
+		.unwrap()
+	}
+
+	#[test]
+	fn each_transaction_type_serializes_its_type_and_fee_fields() {
+		let legacy = serialize(&EthereumTransaction::Legacy(LegacyTransaction {
+			nonce: U256::zero(),
+			gas_price: U256::from(10),
+			gas_limit: U256::from(21_000),
+			action: TransactionAction::Create,
+			value: U256::zero(),
+			input: vec![],
+			signature: TransactionSignature::new(
+				27,
+				H256::from_low_u64_be(1),
+				H256::from_low_u64_be(1),
+			)
+			.unwrap(),
+		}));
+		assert_eq!(legacy["type"], "0x0");
+		assert_eq!(legacy["gasPrice"], "0xa");
+		assert!(legacy.get("maxFeePerGas").is_none());
+		assert!(legacy.get("maxPriorityFeePerGas").is_none());
+		assert!(legacy.get("accessList").is_none());
+
+		let eip2930 = serialize(&EthereumTransaction::EIP2930(EIP2930Transaction {
+			chain_id: 42,
+			nonce: U256::zero(),
+			gas_price: U256::from(10),
+			gas_limit: U256::from(21_000),
+			action: TransactionAction::Create,
+			value: U256::zero(),
+			input: vec![],
+			access_list: vec![],
+			odd_y_parity: false,
+			r: H256::from_low_u64_be(1),
+			s: H256::from_low_u64_be(1),
+		}));
+		assert_eq!(eip2930["type"], "0x1");
+		assert_eq!(eip2930["gasPrice"], "0xa");
+		assert!(eip2930.get("maxFeePerGas").is_none());
+		assert!(eip2930.get("maxPriorityFeePerGas").is_none());
+		assert_eq!(eip2930["chainId"], "0x2a");
+
+		let eip1559 = serialize(&EthereumTransaction::EIP1559(EIP1559Transaction {
+			chain_id: 42,
+			nonce: U256::zero(),
+			max_priority_fee_per_gas: U256::from(2),
+			max_fee_per_gas: U256::from(10),
+			gas_limit: U256::from(21_000),
+			action: TransactionAction::Create,
+			value: U256::zero(),
+			input: vec![],
+			access_list: vec![],
+			odd_y_parity: true,
+			r: H256::from_low_u64_be(1),
+			s: H256::from_low_u64_be(1),
+		}));
+		assert_eq!(eip1559["type"], "0x2");
+		assert_eq!(eip1559["gasPrice"], "0xa");
+		assert_eq!(eip1559["maxFeePerGas"], "0xa");
+		assert_eq!(eip1559["maxPriorityFeePerGas"], "0x2");
+		assert_eq!(eip1559["yParity"], "0x1");
+	}
+
+	/// A downstream envelope adding an experimental transaction type.
+	enum ExtendedTransaction {
+		Ethereum(EthereumTransaction),
+		Experimental { nonce: U256 },
+	}
+
+	impl SerializeTransaction for ExtendedTransaction {
+		fn transaction_hash(&self) -> H256 {
+			match self {
+				ExtendedTransaction::Ethereum(t) => t.transaction_hash(),
+				ExtendedTransaction::Experimental { .. } => H256::repeat_byte(0x7f),
+			}
+		}
+
+		fn to_rpc_transaction(&self, hash: H256, from: H160) -> Transaction {
+			match self {
+				ExtendedTransaction::Ethereum(t) => t.to_rpc_transaction(hash, from),
+				ExtendedTransaction::Experimental { nonce } => Transaction {
+					transaction_type: U256::from(0x7f),
+					hash,
+					nonce: *nonce,
+					from,
+					..Default::default()
+				},
+			}
+		}
+	}
+
+	#[test]
+	fn downstream_transaction_types_can_be_serialized() {
+		let from = H160::repeat_byte(0x11);
+		let legacy = EthereumTransaction::Legacy(LegacyTransaction {
+			nonce: U256::zero(),
+			gas_price: U256::from(10),
+			gas_limit: U256::from(21_000),
+			action: TransactionAction::Create,
+			value: U256::zero(),
+			input: vec![],
+			signature: TransactionSignature::new(
+				27,
+				H256::from_low_u64_be(1),
+				H256::from_low_u64_be(1),
+			)
+			.unwrap(),
+		});
+		assert_eq!(
+			Transaction::build_from(from, &ExtendedTransaction::Ethereum(legacy.clone())),
+			Transaction::build_from(from, &legacy)
+		);
+
+		let experimental = serde_json::to_value(Transaction::build_from(
+			from,
+			&ExtendedTransaction::Experimental {
+				nonce: U256::from(3),
+			},
+		))
+		.unwrap();
+		assert_eq!(experimental["type"], "0x7f");
+		assert_eq!(experimental["nonce"], "0x3");
+		assert_eq!(
+			experimental["hash"],
+			serde_json::to_value(H256::repeat_byte(0x7f)).unwrap()
+		);
+	}
+}
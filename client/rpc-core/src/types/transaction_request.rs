@@ -20,7 +20,7 @@ use ethereum::{
 	AccessListItem, EIP1559TransactionMessage, EIP2930TransactionMessage, LegacyTransactionMessage,
 	TransactionAction,
 };
-use ethereum_types::{H160, U256, U64};
+use ethereum_types::{H160, H256, U256, U64};
 use serde::{Deserialize, Deserializer};
 
 use crate::types::Bytes;
@@ -60,6 +60,12 @@ pub struct TransactionRequest {
 	/// EIP-2718 type
 	#[serde(rename = "type")]
 	pub transaction_type: Option<U256>,
+
+	/// EIP-4844 max fee per blob gas. Blob transactions are not supported, the blob fields are
+	/// only parsed so that requests from blob-aware wallets are not rejected as a whole.
+	pub max_fee_per_blob_gas: Option<U256>,
+	/// EIP-4844 versioned hashes of the blobs
+	pub blob_versioned_hashes: Option<Vec<H256>>,
 }
 
 impl TransactionRequest {
@@ -73,6 +79,13 @@ impl TransactionRequest {
 			(None, None) => None,
 		}
 	}
+
+	/// Whether the request carries blobs, which can't be turned into a transaction.
+	pub fn has_blobs(&self) -> bool {
+		self.blob_versioned_hashes
+			.as_ref()
+			.is_some_and(|hashes| !hashes.is_empty())
+	}
 }
 
 /// Additional data of the transaction.
@@ -138,6 +151,13 @@ pub enum TransactionMessage {
 
 impl From<TransactionRequest> for Option<TransactionMessage> {
 	fn from(req: TransactionRequest) -> Self {
+		// As in Geth, `gasPrice` can't be mixed with the EIP-1559 fee fields.
+		if req.gas_price.is_some()
+			&& (req.max_fee_per_gas.is_some() || req.max_priority_fee_per_gas.is_some())
+		{
+			return None;
+		}
+
 		match (req.max_fee_per_gas, &req.access_list, req.gas_price) {
 			// EIP1559
 			// Empty fields fall back to the canonical transaction schema.
@@ -308,4 +328,70 @@ mod tests {
 			}
 		);
 	}
+
+	#[test]
+	fn test_deserialize_with_blob_fields() {
+		let data = json!({
+			"from": "0x60be2d1d3665660d22ff9624b7be0551ee1ac91b",
+			"to": "0x13fe2d1d3665660d22ff9624b7be0551ee1ac91b",
+			"maxFeePerGas": "0x20",
+			"maxPriorityFeePerGas": "0x30",
+			"maxFeePerBlobGas": "0x40",
+			"blobVersionedHashes": ["0x01a915e4d060149eb4365960e6a7a45f334393093061116b197e3240065ff2d8"],
+			"input": "0x123abc",
+			"type": "0x3"
+		});
+
+		let args = serde_json::from_value::<TransactionRequest>(data).unwrap();
+		assert_eq!(args.max_fee_per_gas, Some(U256::from(0x20)));
+		assert_eq!(args.max_fee_per_blob_gas, Some(U256::from(0x40)));
+		assert!(args.has_blobs());
+
+		let data = json!({
+			"to": "0x13fe2d1d3665660d22ff9624b7be0551ee1ac91b",
+			"maxFeePerBlobGas": "0x40",
+			"blobVersionedHashes": [],
+			"input": "0x123abc"
+		});
+
+		let args = serde_json::from_value::<TransactionRequest>(data).unwrap();
+		assert!(!args.has_blobs());
+	}
+	#[test]
+	fn test_transaction_message_fee_fields() {
+		let message = |fees: serde_json::Value| {
+			let mut data = json!({
+				"to": "0x13fe2d1d3665660d22ff9624b7be0551ee1ac91b",
+				"input": "0x123abc",
+			});
+			data.as_object_mut()
+				.unwrap()
+				.extend(fees.as_object().unwrap().clone());
+			let request = serde_json::from_value::<TransactionRequest>(data).unwrap();
+			Option::<TransactionMessage>::from(request)
+		};
+
+		assert!(matches!(
+			message(json!({"gasPrice": "0x10"})),
+			Some(TransactionMessage::Legacy(m)) if m.gas_price == U256::from(0x10)
+		));
+		assert!(matches!(
+			message(json!({"gasPrice": "0x10", "accessList": []})),
+			Some(TransactionMessage::EIP2930(m)) if m.gas_price == U256::from(0x10)
+		));
+		assert!(matches!(
+			message(json!({"maxFeePerGas": "0x20", "maxPriorityFeePerGas": "0x10"})),
+			Some(TransactionMessage::EIP1559(m))
+				if m.max_fee_per_gas == U256::from(0x20)
+					&& m.max_priority_fee_per_gas == U256::from(0x10)
+		));
+		assert!(matches!(
+			message(json!({})),
+			Some(TransactionMessage::EIP1559(_))
+		));
+
+		// `gasPrice` mixed with EIP-1559 fee fields is rejected.
+		assert!(message(json!({"gasPrice": "0x10", "maxFeePerGas": "0x20"})).is_none());
+		assert!(message(json!({"gasPrice": "0x10", "maxPriorityFeePerGas": "0x10"})).is_none());
+	}
 }
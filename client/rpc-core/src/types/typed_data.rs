@@ -0,0 +1,329 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! EIP-712 typed structured data.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use ethereum_types::{H160, H256, U256};
+use rustc_hex::FromHex;
+use serde::Deserialize;
+use serde_json::Value;
+use sp_crypto_hashing::keccak_256;
+
+/// A member of an EIP-712 struct type.
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize)]
+pub struct TypedDataField {
+	pub name: String,
+	#[serde(rename = "type")]
+	pub field_type: String,
+}
+
+/// EIP-712 typed data, as accepted by `eth_signTypedData_v4`.
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TypedData {
+	pub types: BTreeMap<String, Vec<TypedDataField>>,
+	pub primary_type: String,
+	pub domain: Value,
+	pub message: Value,
+}
+
+const DOMAIN_TYPE: &str = "EIP712Domain";
+
+impl TypedData {
+	/// The digest to sign: `keccak256("\x19\x01" ‖ domainSeparator ‖ hashStruct(message))`.
+	pub fn signing_hash(&self) -> Result<H256, String> {
+		let mut bytes = vec![0x19, 0x01];
+		bytes.extend_from_slice(self.domain_separator()?.as_bytes());
+		bytes.extend_from_slice(
+			self.hash_struct(&self.primary_type, &self.message)?
+				.as_bytes(),
+		);
+		Ok(H256::from(keccak_256(&bytes)))
+	}
+
+	/// `hashStruct(domain)` using the `EIP712Domain` type.
+	pub fn domain_separator(&self) -> Result<H256, String> {
+		self.hash_struct(DOMAIN_TYPE, &self.domain)
+	}
+
+	/// `keccak256(typeHash ‖ encodeData(data))`.
+	pub fn hash_struct(&self, type_name: &str, data: &Value) -> Result<H256, String> {
+		let mut bytes = self.type_hash(type_name)?.as_bytes().to_vec();
+		bytes.extend(self.encode_data(type_name, data)?);
+		Ok(H256::from(keccak_256(&bytes)))
+	}
+
+	/// `keccak256(encodeType(type))`.
+	pub fn type_hash(&self, type_name: &str) -> Result<H256, String> {
+		Ok(H256::from(keccak_256(
+			self.encode_type(type_name)?.as_bytes(),
+		)))
+	}
+
+	/// The type encoding, with referenced struct types appended in alphabetical order.
+	pub fn encode_type(&self, type_name: &str) -> Result<String, String> {
+		let mut dependencies = BTreeSet::new();
+		self.find_dependencies(type_name, &mut dependencies)?;
+		dependencies.remove(type_name);
+
+		let mut encoded = String::new();
+		for name in std::iter::once(type_name).chain(dependencies.iter().map(String::as_str)) {
+			let fields = self.fields(name)?;
+			let members = fields
+				.iter()
+				.map(|field| format!("{} {}", field.field_type, field.name))
+				.collect::<Vec<_>>()
+				.join(",");
+			encoded.push_str(&format!("{name}({members})"));
+		}
+		Ok(encoded)
+	}
+
+	fn fields(&self, type_name: &str) -> Result<&Vec<TypedDataField>, String> {
+		self.types
+			.get(type_name)
+			.ok_or_else(|| format!("unknown type {type_name}"))
+	}
+
+	fn find_dependencies(
+		&self,
+		type_name: &str,
+		dependencies: &mut BTreeSet<String>,
+	) -> Result<(), String> {
+		let type_name = strip_array(type_name);
+		if dependencies.contains(type_name) || !self.types.contains_key(type_name) {
+			return Ok(());
+		}
+		dependencies.insert(type_name.to_string());
+		for field in self.fields(type_name)? {
+			self.find_dependencies(&field.field_type, dependencies)?;
+		}
+		Ok(())
+	}
+
+	fn encode_data(&self, type_name: &str, data: &Value) -> Result<Vec<u8>, String> {
+		let mut encoded = Vec::new();
+		for field in self.fields(type_name)? {
+			let value = data.get(&field.name).unwrap_or(&Value::Null);
+			encoded.extend_from_slice(self.encode_value(&field.field_type, value)?.as_bytes());
+		}
+		Ok(encoded)
+	}
+
+	fn encode_value(&self, field_type: &str, value: &Value) -> Result<H256, String> {
+		if let Some(item_type) = field_type
+			.strip_suffix(']')
+			.and_then(|t| t.rfind('[').map(|i| &t[..i]))
+		{
+			let items = value
+				.as_array()
+				.ok_or_else(|| format!("expected array for {field_type}"))?;
+			let mut bytes = Vec::new();
+			for item in items {
+				bytes.extend_from_slice(self.encode_value(item_type, item)?.as_bytes());
+			}
+			return Ok(H256::from(keccak_256(&bytes)));
+		}
+
+		if self.types.contains_key(field_type) {
+			return self.hash_struct(field_type, value);
+		}
+
+		match field_type {
+			"string" => {
+				let s = value
+					.as_str()
+					.ok_or_else(|| format!("expected string for {field_type}"))?;
+				Ok(H256::from(keccak_256(s.as_bytes())))
+			}
+			"bytes" => Ok(H256::from(keccak_256(&parse_bytes(value)?))),
+			"bool" => {
+				let b = value
+					.as_bool()
+					.ok_or_else(|| format!("expected bool for {field_type}"))?;
+				Ok(H256::from_low_u64_be(b as u64))
+			}
+			"address" => {
+				let bytes = parse_bytes(value)?;
+				if bytes.len() != 20 {
+					return Err("invalid address".into());
+				}
+				Ok(H256::from(H160::from_slice(&bytes)))
+			}
+			t if t.starts_with("bytes") => {
+				let bytes = parse_bytes(value)?;
+				if bytes.len() > 32 {
+					return Err(format!("value too long for {t}"));
+				}
+				let mut word = H256::zero();
+				word.as_bytes_mut()[..bytes.len()].copy_from_slice(&bytes);
+				Ok(word)
+			}
+			t if t.starts_with("uint") => Ok(u256_to_h256(parse_uint(value)?)),
+			t if t.starts_with("int") => Ok(u256_to_h256(parse_int(value)?)),
+			t => Err(format!("unsupported type {t}")),
+		}
+	}
+}
+
+fn strip_array(type_name: &str) -> &str {
+	type_name.split('[').next().unwrap_or(type_name)
+}
+
+fn u256_to_h256(value: U256) -> H256 {
+	let mut word = [0u8; 32];
+	value.to_big_endian(&mut word);
+	H256::from(word)
+}
+
+fn parse_bytes(value: &Value) -> Result<Vec<u8>, String> {
+	let s = value.as_str().ok_or("expected hex string")?;
+	s.strip_prefix("0x")
+		.unwrap_or(s)
+		.from_hex()
+		.map_err(|_| format!("invalid hex string {s}"))
+}
+
+fn parse_uint(value: &Value) -> Result<U256, String> {
+	match value {
+		Value::Number(n) => n
+			.as_u64()
+			.map(U256::from)
+			.ok_or_else(|| format!("invalid unsigned integer {n}")),
+		Value::String(s) => {
+			let parsed = match s.strip_prefix("0x") {
+				Some(hex) => U256::from_str_radix(hex, 16).ok(),
+				None => U256::from_dec_str(s).ok(),
+			};
+			parsed.ok_or_else(|| format!("invalid unsigned integer {s}"))
+		}
+		_ => Err("expected integer".into()),
+	}
+}
+
+fn parse_int(value: &Value) -> Result<U256, String> {
+	let (negative, magnitude) = match value {
+		Value::Number(n) => {
+			let n = n
+				.as_i64()
+				.ok_or_else(|| format!("invalid signed integer {n}"))?;
+			(n < 0, U256::from(n.unsigned_abs()))
+		}
+		Value::String(s) => match s.strip_prefix('-') {
+			Some(abs) => (true, parse_uint(&Value::String(abs.to_string()))?),
+			None => (false, parse_uint(value)?),
+		},
+		_ => return Err("expected integer".into()),
+	};
+	// Two's complement for negative values.
+	Ok(if negative {
+		(!magnitude).overflowing_add(U256::one()).0
+	} else {
+		magnitude
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	// The `Mail` example from the EIP-712 specification.
+	const MAIL_TYPED_DATA: &str = r#"{
+		"types": {
+			"EIP712Domain": [
+				{ "name": "name", "type": "string" },
+				{ "name": "version", "type": "string" },
+				{ "name": "chainId", "type": "uint256" },
+				{ "name": "verifyingContract", "type": "address" }
+			],
+			"Person": [
+				{ "name": "name", "type": "string" },
+				{ "name": "wallet", "type": "address" }
+			],
+			"Mail": [
+				{ "name": "from", "type": "Person" },
+				{ "name": "to", "type": "Person" },
+				{ "name": "contents", "type": "string" }
+			]
+		},
+		"primaryType": "Mail",
+		"domain": {
+			"name": "Ether Mail",
+			"version": "1",
+			"chainId": 1,
+			"verifyingContract": "0xCcCCccccCCCCcCCCCCCcCcCccCcCCCcCcccccccC"
+		},
+		"message": {
+			"from": { "name": "Cow", "wallet": "0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826" },
+			"to": { "name": "Bob", "wallet": "0xbBbBBBBbbBBBbbbBbbBbbbbBBbBbbbbBbBbbBBbB" },
+			"contents": "Hello, Bob!"
+		}
+	}"#;
+
+	fn h256(hex: &str) -> H256 {
+		H256::from_slice(&hex.from_hex::<Vec<u8>>().unwrap())
+	}
+
+	#[test]
+	fn eip712_mail_example_hashes() {
+		let typed_data: TypedData = serde_json::from_str(MAIL_TYPED_DATA).unwrap();
+
+		assert_eq!(
+			typed_data.encode_type("Mail").unwrap(),
+			"Mail(Person from,Person to,string contents)Person(string name,address wallet)"
+		);
+		assert_eq!(
+			typed_data.type_hash("Mail").unwrap(),
+			h256("a0cedeb2dc280ba39b857546d74f5549c3a1d7bdc2dd96bf881f76108e23dac2")
+		);
+		assert_eq!(
+			typed_data.hash_struct("Mail", &typed_data.message).unwrap(),
+			h256("c52c0ee5d84264471806290a3f2c4cecfc5490626bf912d01f240d7a274b371e")
+		);
+		assert_eq!(
+			typed_data.domain_separator().unwrap(),
+			h256("f2cee375fa42b42143804025fc449deafd50cc031ca257e0b194a650a912090f")
+		);
+		assert_eq!(
+			typed_data.signing_hash().unwrap(),
+			h256("be609aee343fb3c4b28e1df9e632fca64fcfaede20f02e86244efddf30957bd2")
+		);
+	}
+
+	#[test]
+	fn eip712_rejects_unknown_primary_type() {
+		let mut typed_data: TypedData = serde_json::from_str(MAIL_TYPED_DATA).unwrap();
+		typed_data.primary_type = "Letter".into();
+		assert!(typed_data.signing_hash().is_err());
+	}
+
+	#[test]
+	fn eip712_encodes_negative_integers_as_twos_complement() {
+		assert_eq!(parse_int(&Value::from(-1)).unwrap(), U256::max_value());
+		assert_eq!(
+			parse_int(&Value::String("-2".into())).unwrap(),
+			U256::max_value() - 1
+		);
+		assert_eq!(
+			parse_int(&Value::String("0x10".into())).unwrap(),
+			U256::from(16)
+		);
+	}
+}
@@ -34,7 +34,7 @@ use sc_client_api::{
 	client::BlockchainEvents,
 };
 use sc_service::SpawnTaskHandle;
-use sp_api::ProvideRuntimeApi;
+use sp_api::{ApiExt, ProvideRuntimeApi};
 use sp_blockchain::HeaderBackend;
 use sp_runtime::traits::{Block as BlockT, Header as HeaderT, UniqueSaturatedInto};
 // Frontier
@@ -247,6 +247,16 @@ impl<B: BlockT> EthBlockDataCacheTask<B> {
 	}
 }
 
+/// Drop the filters that have not been polled in the last `retain_threshold` blocks. Polling a
+/// removed filter returns a "does not exist" error.
+fn retain_filters(
+	filter_pool: &mut BTreeMap<U256, FilterPoolItem>,
+	imported_number: u64,
+	retain_threshold: u64,
+) {
+	filter_pool.retain(|_, v| v.at_block.saturating_add(retain_threshold) > imported_number);
+}
+
 pub struct EthTask<B, C, BE>(PhantomData<(B, C, BE)>);
 
 impl<B, C, BE> EthTask<B, C, BE>
@@ -271,7 +281,7 @@ where
 					*notification.header.number(),
 				);
 
-				filter_pool.retain(|_, v| v.at_block + retain_threshold > imported_number);
+				retain_filters(filter_pool, imported_number, retain_threshold);
 			}
 		}
 	}
@@ -309,7 +319,17 @@ where
 
 			let block = storage_override.current_block(hash);
 			let mut block_number: Option<u64> = None;
-			let base_fee = client.runtime_api().gas_price(hash).unwrap_or_default();
+			// Read the base fee and gas usage from a single runtime call when supported, so
+			// they are guaranteed to be consistent with each other.
+			let api = client.runtime_api();
+			let base_fee_info = match api.api_version::<dyn EthereumRuntimeRPCApi<B>>(hash) {
+				Ok(Some(api_version)) if api_version >= 6 => api.base_fee_info(hash).ok(),
+				_ => None,
+			};
+			let base_fee = match &base_fee_info {
+				Some(info) => info.base_fee,
+				None => api.gas_price(hash).unwrap_or_default(),
+			};
 			let receipts = storage_override.current_receipts(hash);
 			let mut result = FeeHistoryCacheItem {
 				base_fee: UniqueSaturatedInto::<u64>::unique_saturated_into(base_fee),
@@ -318,8 +338,12 @@ where
 			};
 			if let (Some(block), Some(receipts)) = (block, receipts) {
 				block_number = Some(UniqueSaturatedInto::<u64>::unique_saturated_into(block.header.number));
-				let gas_used = UniqueSaturatedInto::<u64>::unique_saturated_into(block.header.gas_used) as f64;
-				let gas_limit = UniqueSaturatedInto::<u64>::unique_saturated_into(block.header.gas_limit) as f64;
+				let (gas_used, gas_limit) = match &base_fee_info {
+					Some(info) => (info.gas_used, info.gas_limit),
+					None => (block.header.gas_used, block.header.gas_limit),
+				};
+				let gas_used = UniqueSaturatedInto::<u64>::unique_saturated_into(gas_used) as f64;
+				let gas_limit = UniqueSaturatedInto::<u64>::unique_saturated_into(gas_limit) as f64;
 				result.gas_used_ratio = gas_used / gas_limit;
 
 				let mut previous_cumulative_gas = U256::zero();
@@ -425,3 +449,31 @@ where
 		}
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn filter_polled_at(at_block: u64) -> FilterPoolItem {
+		FilterPoolItem {
+			last_poll: BlockNumberOrHash::Num(at_block),
+			filter_type: FilterType::Block,
+			at_block,
+			pending_transaction_hashes: Default::default(),
+		}
+	}
+
+	#[test]
+	fn idle_filters_are_removed_after_the_retain_threshold() {
+		let mut filter_pool = BTreeMap::new();
+		filter_pool.insert(U256::from(1), filter_polled_at(10));
+		filter_pool.insert(U256::from(2), filter_polled_at(50));
+
+		retain_filters(&mut filter_pool, 109, 100);
+		assert_eq!(filter_pool.len(), 2);
+
+		// The first filter was last polled 100 blocks ago.
+		retain_filters(&mut filter_pool, 110, 100);
+		assert_eq!(filter_pool.keys().collect::<Vec<_>>(), vec![&U256::from(2)]);
+	}
+}
@@ -184,11 +184,22 @@ where
 	}
 
 	pub async fn block_transaction_count_by_hash(&self, hash: H256) -> RpcResult<Option<U256>> {
-		let blockinfo = self.block_info_by_eth_block_hash(hash).await?;
-		match blockinfo.block {
-			Some(block) => Ok(Some(U256::from(block.transactions.len()))),
-			None => Ok(None),
-		}
+		let substrate_hash = match frontier_backend_client::load_hash::<B, C>(
+			self.client.as_ref(),
+			self.backend.as_ref(),
+			hash,
+		)
+		.await
+		.map_err(|err| internal_err(format!("{:?}", err)))?
+		{
+			Some(hash) => hash,
+			_ => return Ok(None),
+		};
+
+		Ok(self
+			.storage_override
+			.current_transaction_count(substrate_hash)
+			.map(U256::from))
 	}
 
 	pub async fn block_transaction_count_by_number(
@@ -202,11 +213,25 @@ where
 			)));
 		}
 
-		let block_info = self.block_info_by_number(number_or_hash).await?;
-		match block_info.block {
-			Some(block) => Ok(Some(U256::from(block.transactions.len()))),
-			None => Ok(None),
-		}
+		let id = match frontier_backend_client::native_block_id::<B, C>(
+			self.client.as_ref(),
+			self.backend.as_ref(),
+			Some(number_or_hash),
+		)
+		.await?
+		{
+			Some(id) => id,
+			None => return Ok(None),
+		};
+		let substrate_hash = self
+			.client
+			.expect_block_hash_from_id(&id)
+			.map_err(|_| internal_err(format!("Expect block number from id: {}", id)))?;
+
+		Ok(self
+			.storage_override
+			.current_transaction_count(substrate_hash)
+			.map(U256::from))
 	}
 
 	pub async fn block_transaction_receipts(
@@ -152,7 +152,8 @@ where
 			.gas_limit;
 		let max_gas_limit = block_gas_limit * self.execute_gas_limit_multiplier;
 
-		// use given gas limit or query current block's limit
+		// use given gas limit or query current block's limit, a zero gas limit is treated as unset
+		let gas = gas.filter(|amount| !amount.is_zero());
 		let gas_limit = match gas {
 			Some(amount) => {
 				if amount > max_gas_limit {
@@ -170,6 +171,7 @@ where
 				_ => block_gas_limit,
 			},
 		};
+		let gas_limit = cap_gas_limit(gas_limit, gas.is_some(), EC::RPC_GAS_CAP)?;
 
 		let data = data.into_bytes().map(|d| d.into_vec()).unwrap_or_default();
 		match to {
@@ -213,7 +215,7 @@ where
 
 					error_on_execution_failure(&info.exit_reason, &info.value)?;
 					Ok(Bytes(info.value))
-				} else if api_version == 4 || api_version == 5 {
+				} else if api_version >= 4 {
 					// Post-london + access list support
 					let encoded_params = Encode::encode(&(
 						&from.unwrap_or_default(),
@@ -267,7 +269,7 @@ where
 
 						error_on_execution_failure(&info.exit_reason, &info.value)?;
 						info.value
-					} else if api_version == 5 {
+					} else {
 						let info = self
 							.client
 							.call_api_at(params)
@@ -286,13 +288,9 @@ where
 
 						error_on_execution_failure(&info.exit_reason, &info.value)?;
 						info.value
-					} else {
-						unreachable!("invalid version");
 					};
 
 					Ok(Bytes(value))
-				} else {
-					Err(internal_err("failed to retrieve Runtime Api version"))
 				}
 			}
 			None => {
@@ -371,9 +369,39 @@ where
 						.account_code_at(substrate_hash, info.value)
 						.map_err(|err| internal_err(format!("runtime error: {err}")))?;
 					Ok(Bytes(code))
-				} else if api_version == 5 {
+				} else if api_version < 18 {
 					// Post-london + access list support
 					let access_list = access_list.unwrap_or_default();
+					#[allow(deprecated)]
+					let info = api.create_before_version_18(
+						substrate_hash,
+						from.unwrap_or_default(),
+						data,
+						value.unwrap_or_default(),
+						gas_limit,
+						max_fee_per_gas,
+						max_priority_fee_per_gas,
+						nonce,
+						false,
+						Some(
+							access_list
+								.into_iter()
+								.map(|item| (item.address, item.storage_keys))
+								.collect(),
+						),
+					)
+					.map_err(|err| internal_err(format!("runtime error: {err}")))?
+					.map_err(|err| internal_err(format!("execution fatal: {err:?}")))?;
+
+					error_on_execution_failure(&info.exit_reason, &[])?;
+
+					let code = api
+						.account_code_at(substrate_hash, info.value)
+						.map_err(|err| internal_err(format!("runtime error: {err}")))?;
+					Ok(Bytes(code))
+				} else {
+					// Post-london + access list support + constructor revert data
+					let access_list = access_list.unwrap_or_default();
 					let info = api
 						.create(
 							substrate_hash,
@@ -395,19 +423,22 @@ where
 						.map_err(|err| internal_err(format!("runtime error: {err}")))?
 						.map_err(|err| internal_err(format!("execution fatal: {err:?}")))?;
 
-					error_on_execution_failure(&info.exit_reason, &[])?;
+					error_on_execution_failure(&info.exit_reason, &info.output)?;
 
 					let code = api
 						.account_code_at(substrate_hash, info.value)
 						.map_err(|err| internal_err(format!("runtime error: {err}")))?;
 					Ok(Bytes(code))
-				} else {
-					Err(internal_err("failed to retrieve Runtime Api version"))
 				}
 			}
 		}
 	}
 
+	/// Estimate the gas limit the request needs to succeed.
+	///
+	/// This is an upper bound of the gas a receipt reports for the same execution: receipts
+	/// carry the effective used gas, which has refunds deducted, while the estimate must leave
+	/// room for the gas that is only refunded at the end of the execution.
 	pub async fn estimate_gas(
 		&self,
 		request: TransactionRequest,
@@ -471,8 +502,9 @@ where
 
 		let max_gas_limit = block_gas_limit * self.execute_gas_limit_multiplier;
 
-		// Determine the highest possible gas limits
-		let mut highest = match request.gas {
+		// Determine the highest possible gas limits, a zero gas limit is treated as unset
+		let requested_gas = request.gas.filter(|amount| !amount.is_zero());
+		let highest = match requested_gas {
 			Some(amount) => {
 				if amount > max_gas_limit {
 					return Err(internal_err(format!(
@@ -489,6 +521,7 @@ where
 				_ => block_gas_limit,
 			},
 		};
+		let mut highest = cap_gas_limit(highest, requested_gas.is_some(), EC::RPC_GAS_CAP)?;
 
 		let (gas_price, max_fee_per_gas, max_priority_fee_per_gas, fee_cap) = {
 			let details = fee_details(
@@ -722,10 +755,11 @@ where
 							.map_err(|err| internal_err(format!("execution fatal: {err:?}")))?;
 
 							(info.exit_reason, Vec::new(), info.used_gas)
-						} else {
+						} else if api_version < 18 {
 							// Post-london + access list support
 							let access_list = access_list.unwrap_or_default();
-							let info = api.create(
+							#[allow(deprecated)]
+							let info = api.create_before_version_18(
 								substrate_hash,
 								from.unwrap_or_default(),
 								data,
@@ -746,6 +780,30 @@ where
 							.map_err(|err| internal_err(format!("execution fatal: {err:?}")))?;
 
 							(info.exit_reason, Vec::new(), info.used_gas.effective)
+						} else {
+							// Post-london + access list support + constructor revert data
+							let access_list = access_list.unwrap_or_default();
+							let info = api.create(
+								substrate_hash,
+								from.unwrap_or_default(),
+								data,
+								value.unwrap_or_default(),
+								gas_limit,
+								max_fee_per_gas,
+								max_priority_fee_per_gas,
+								None,
+								estimate_mode,
+								Some(
+									access_list
+										.into_iter()
+										.map(|item| (item.address, item.storage_keys))
+										.collect(),
+								),
+							)
+							.map_err(|err| internal_err(format!("runtime error: {err}")))?
+							.map_err(|err| internal_err(format!("execution fatal: {err:?}")))?;
+
+							(info.exit_reason, info.output, info.used_gas.effective)
 						}
 					}
 				};
@@ -971,23 +1029,9 @@ pub fn error_on_execution_failure(reason: &ExitReason, data: &[u8]) -> RpcResult
 			))
 		}
 		ExitReason::Revert(_) => {
-			const LEN_START: usize = 36;
-			const MESSAGE_START: usize = 68;
-
 			let mut message = "VM Exception while processing transaction: revert".to_string();
-			// A minimum size of error function selector (4) + offset (32) + string length (32)
-			// should contain a utf-8 encoded revert reason.
-			if data.len() > MESSAGE_START {
-				let message_len =
-					U256::from(&data[LEN_START..MESSAGE_START]).saturated_into::<usize>();
-				let message_end = MESSAGE_START.saturating_add(message_len);
-
-				if data.len() >= message_end {
-					let body: &[u8] = &data[MESSAGE_START..message_end];
-					if let Ok(reason) = std::str::from_utf8(body) {
-						message = format!("{message} {reason}");
-					}
-				}
+			if let Some(reason) = decode_revert_reason(data) {
+				message = format!("{message} {reason}");
 			}
 			Err(crate::internal_err_with_data(message, data))
 		}
@@ -998,6 +1042,42 @@ pub fn error_on_execution_failure(reason: &ExitReason, data: &[u8]) -> RpcResult
 	}
 }
 
+/// Decode the utf-8 reason of a revert data, as encoded by `revert("reason")` and
+/// `require(condition, "reason")`.
+pub fn decode_revert_reason(data: &[u8]) -> Option<&str> {
+	const LEN_START: usize = 36;
+	const MESSAGE_START: usize = 68;
+
+	// A minimum size of error function selector (4) + offset (32) + string length (32)
+	// should contain a utf-8 encoded revert reason.
+	if data.len() <= MESSAGE_START {
+		return None;
+	}
+	let message_len = U256::from(&data[LEN_START..MESSAGE_START]).saturated_into::<usize>();
+	let message_end = MESSAGE_START.saturating_add(message_len);
+	if data.len() < message_end {
+		return None;
+	}
+	std::str::from_utf8(&data[MESSAGE_START..message_end]).ok()
+}
+
+/// Bound a gas limit by the `RPC_GAS_CAP` of the node. A gas limit given in the request is rejected
+/// when above the cap, while a default one is lowered to it.
+fn cap_gas_limit(gas_limit: U256, requested: bool, rpc_gas_cap: Option<u64>) -> RpcResult<U256> {
+	match rpc_gas_cap.map(U256::from) {
+		Some(cap) if gas_limit > cap => {
+			if requested {
+				Err(internal_err(format!(
+					"provided gas limit is too high (the RPC gas cap is {cap})"
+				)))
+			} else {
+				Ok(cap)
+			}
+		}
+		_ => Ok(gas_limit),
+	}
+}
+
 struct FeeDetails {
 	gas_price: Option<U256>,
 	max_fee_per_gas: Option<U256>,
@@ -1015,18 +1095,21 @@ fn fee_details(
 		request_max_fee_per_gas,
 		request_priority_fee_per_gas,
 	) {
-		(Some(_), Some(_), Some(_)) => Err(internal_err(
+		(Some(_), Some(_), _) | (Some(_), _, Some(_)) => Err(internal_err(
 			"both gasPrice and (maxFeePerGas or maxPriorityFeePerGas) specified",
 		)),
-		// Legacy or EIP-2930 transaction.
-		(gas_price, None, None) if gas_price.is_some() => Ok(FeeDetails {
-			gas_price,
-			max_fee_per_gas: None,
-			max_priority_fee_per_gas: None,
-			fee_cap: gas_price.unwrap_or_default(),
+		// Legacy or EIP-2930 transaction. As in Geth, the gas price is used as both the max fee
+		// and the tip, so that it is honoured by runtimes executing EIP-1559 calls (API version
+		// 2 and later). Pre-London runtimes (API version 1) are only given the gas price.
+		(Some(gas_price), None, None) => Ok(FeeDetails {
+			gas_price: Some(gas_price),
+			max_fee_per_gas: Some(gas_price),
+			max_priority_fee_per_gas: Some(gas_price),
+			fee_cap: gas_price,
 		}),
-		// EIP-1559 transaction
-		(None, Some(max_fee), Some(max_priority)) => {
+		// EIP-1559 transaction, without a tip if none is given.
+		(None, Some(max_fee), max_priority) => {
+			let max_priority = max_priority.unwrap_or_default();
 			if max_priority > max_fee {
 				return Err(internal_err(
 					"Invalid input: `max_priority_fee_per_gas` greater than `max_fee_per_gas`",
@@ -1040,12 +1123,106 @@ fn fee_details(
 			})
 		}
 		// Default to EIP-1559 transaction
-		_ => Ok(FeeDetails {
+		(None, None, max_priority) => Ok(FeeDetails {
 			gas_price: None,
-			// Old runtimes require max_fee_per_gas to be None for non transactional calls.
+			// Without any fee given, old runtimes require max_fee_per_gas to be None for non
+			// transactional calls.
 			max_fee_per_gas: None,
-			max_priority_fee_per_gas: Some(U256::zero()),
+			max_priority_fee_per_gas: Some(max_priority.unwrap_or_default()),
 			fee_cap: U256::zero(),
 		}),
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn rpc_gas_cap_is_enforced() {
+		let cap = Some(50_000);
+
+		// A requested gas limit above the cap is rejected.
+		assert!(cap_gas_limit(U256::from(50_001), true, cap).is_err());
+		assert_eq!(
+			cap_gas_limit(U256::from(50_000), true, cap).unwrap(),
+			U256::from(50_000)
+		);
+
+		// The default gas limit is lowered to the cap.
+		assert_eq!(
+			cap_gas_limit(U256::from(15_000_000), false, cap).unwrap(),
+			U256::from(50_000)
+		);
+		assert_eq!(
+			cap_gas_limit(U256::from(21_000), false, cap).unwrap(),
+			U256::from(21_000)
+		);
+
+		// Without a cap, the gas limit is left as is.
+		assert_eq!(
+			cap_gas_limit(U256::from(15_000_000), true, None).unwrap(),
+			U256::from(15_000_000)
+		);
+	}
+
+	#[test]
+	fn fee_details_normalizes_legacy_and_eip1559_fields() {
+		let fees = |gas_price: Option<u64>, max_fee: Option<u64>, max_priority: Option<u64>| {
+			fee_details(
+				gas_price.map(U256::from),
+				max_fee.map(U256::from),
+				max_priority.map(U256::from),
+			)
+			.map(|details| {
+				(
+					details.gas_price.map(|fee| fee.as_u64()),
+					details.max_fee_per_gas.map(|fee| fee.as_u64()),
+					details.max_priority_fee_per_gas.map(|fee| fee.as_u64()),
+					details.fee_cap.as_u64(),
+				)
+			})
+		};
+
+		// gasPrice is used as both the max fee and the tip. Pre-London runtimes (API version 1)
+		// only take the gas price, which is kept as is.
+		let (gas_price, max_fee, max_priority, fee_cap) = fees(Some(10), None, None).unwrap();
+		assert_eq!(gas_price, Some(10));
+		assert_eq!((max_fee, max_priority, fee_cap), (Some(10), Some(10), 10));
+
+		// gasPrice can't be mixed with the EIP-1559 fields.
+		assert!(fees(Some(10), Some(10), None).is_err());
+		assert!(fees(Some(10), None, Some(1)).is_err());
+		assert!(fees(Some(10), Some(10), Some(1)).is_err());
+
+		// EIP-1559 fields.
+		assert_eq!(
+			fees(None, Some(10), Some(1)).unwrap(),
+			(None, Some(10), Some(1), 10)
+		);
+		assert_eq!(
+			fees(None, Some(10), None).unwrap(),
+			(None, Some(10), Some(0), 10)
+		);
+		assert!(fees(None, Some(10), Some(11)).is_err());
+		assert_eq!(fees(None, None, Some(1)).unwrap(), (None, None, Some(1), 0));
+
+		// No fee at all.
+		assert_eq!(fees(None, None, None).unwrap(), (None, None, Some(0), 0));
+	}
+
+	#[test]
+	fn revert_reason_is_decoded_from_error_string() {
+		// Error(string) selector, offset, length and the padded "oops" message.
+		let mut data = vec![0x08, 0xc3, 0x79, 0xa0];
+		data.extend_from_slice(H256::from_low_u64_be(32).as_bytes());
+		data.extend_from_slice(H256::from_low_u64_be(4).as_bytes());
+		data.extend_from_slice(&H256::from_slice(&[b"oops".as_slice(), &[0u8; 28]].concat())[..]);
+		assert_eq!(decode_revert_reason(&data), Some("oops"));
+
+		// Truncated or custom error data has no reason.
+		assert_eq!(decode_revert_reason(&data[..68]), None);
+		assert_eq!(decode_revert_reason(&data[..70]), None);
+		assert_eq!(decode_revert_reason(&[0xde, 0xad, 0xbe, 0xef]), None);
+	}
+}
@@ -812,3 +812,72 @@ fn filter_block_logs<'a>(
 	}
 	ret
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use ethereum::PartialHeader;
+	use ethereum_types::{Bloom, H160, H64};
+
+	#[test]
+	fn filtered_block_logs_have_every_field_set() {
+		let block = EthereumBlock::new(
+			PartialHeader {
+				parent_hash: H256::zero(),
+				beneficiary: H160::zero(),
+				state_root: H256::zero(),
+				receipts_root: H256::zero(),
+				logs_bloom: Bloom::zero(),
+				difficulty: U256::zero(),
+				number: U256::from(7),
+				gas_limit: U256::zero(),
+				gas_used: U256::zero(),
+				timestamp: 0,
+				extra_data: vec![],
+				mix_hash: H256::zero(),
+				nonce: H64::zero(),
+			},
+			vec![],
+			vec![],
+		);
+		let log = |byte| ethereum::Log {
+			address: H160::repeat_byte(byte),
+			topics: vec![],
+			data: vec![],
+		};
+		let statuses = vec![
+			TransactionStatus {
+				transaction_hash: H256::repeat_byte(1),
+				transaction_index: 0,
+				logs: vec![log(1)],
+				..Default::default()
+			},
+			TransactionStatus {
+				transaction_hash: H256::repeat_byte(2),
+				transaction_index: 1,
+				logs: vec![log(2), log(3)],
+				..Default::default()
+			},
+		];
+		let filter = Filter {
+			from_block: None,
+			to_block: None,
+			block_hash: None,
+			address: None,
+			topics: None,
+		};
+
+		let mut logs = Vec::new();
+		filter_block_logs(&mut logs, &filter, block.clone(), statuses);
+
+		assert_eq!(logs.len(), 3);
+		let log = &logs[2];
+		assert_eq!(log.block_hash, Some(block.header.hash()));
+		assert_eq!(log.block_number, Some(U256::from(7)));
+		assert_eq!(log.transaction_hash, Some(H256::repeat_byte(2)));
+		assert_eq!(log.transaction_index, Some(U256::one()));
+		assert_eq!(log.log_index, Some(U256::from(2)));
+		assert_eq!(log.transaction_log_index, Some(U256::one()));
+		assert!(!log.removed);
+	}
+}
@@ -37,22 +37,35 @@ impl Geth {
 				InvalidTransaction::Stale => "nonce too low".into(),
 				InvalidTransaction::Payment => "insufficient funds for gas * price + value".into(),
 				InvalidTransaction::ExhaustsResources => "exceeds block gas limit".into(),
-				InvalidTransaction::Custom(inner) => match inner.into() {
-					VError::UnknownError => "unknown error".into(),
-					VError::InvalidChainId => "invalid chain id".into(),
-					VError::InvalidSignature => "invalid sender".into(),
-					VError::GasLimitTooLow => "intrinsic gas too low".into(),
-					VError::GasLimitTooHigh => "exceeds block gas limit".into(),
-					VError::GasPriceTooLow => "gas price less than block base fee".into(),
-					VError::PriorityFeeTooHigh => {
-						"max priority fee per gas higher than max fee per gas".into()
-					}
-					VError::InvalidFeeInput => "invalid fee input".into(),
-					_ => "transaction validation error".into(),
-				},
+				InvalidTransaction::Custom(inner) => Self::validation_error(inner.into()),
 				_ => "unknown error".into(),
 			},
 			err => format!("submit transaction to pool failed: {:?}", err),
 		}
 	}
+
+	pub fn validation_error(err: VError) -> String {
+		match err {
+			VError::UnknownError => "unknown error".into(),
+			VError::InvalidChainId => "invalid chain id".into(),
+			VError::InvalidSignature => "invalid sender".into(),
+			VError::GasLimitTooLow => "intrinsic gas too low".into(),
+			VError::GasLimitTooHigh => "exceeds block gas limit".into(),
+			VError::GasPriceTooLow => "gas price less than block base fee".into(),
+			VError::PriorityFeeTooHigh => {
+				"max priority fee per gas higher than max fee per gas".into()
+			}
+			VError::BalanceTooLow => "insufficient funds for gas * price + value".into(),
+			VError::TxNonceTooLow => "nonce too low".into(),
+			VError::TxNonceTooHigh => "nonce too high".into(),
+			VError::InvalidFeeInput => "invalid fee input".into(),
+			VError::CreateContractSizeLimit => "max initcode size exceeded".into(),
+			VError::BlockedAddress => "address is blocked".into(),
+			VError::AccessListTooLarge => "access list too large".into(),
+			VError::BalanceTooLowForFee => "insufficient funds for gas * price".into(),
+			VError::BalanceTooLowForValue => "insufficient funds for transfer".into(),
+			VError::Paused => "evm execution is paused".into(),
+			VError::SenderNotEoa => "sender not an eoa".into(),
+		}
+	}
 }
@@ -63,6 +63,21 @@ pub use self::{execute::EstimateGasAdapter, filter::EthFilter};
 pub trait EthConfig<B: BlockT, C>: Send + Sync + 'static {
 	type EstimateGasAdapter: EstimateGasAdapter + Send + Sync;
 	type RuntimeStorageOverride: RuntimeStorageOverride<B, C>;
+	/// Serve `eth_getBalance` and `eth_getTransactionCount` for non-pending blocks by reading
+	/// `system.account` through `RuntimeStorageOverride::account_basic`, instead of calling
+	/// the runtime. Falls back to the runtime API when the override returns nothing.
+	///
+	/// The direct read computes the balance `pallet_balances` lets the account spend, as the
+	/// runtime does, which requires `EXISTENTIAL_DEPOSIT` to be set. It reads the `frame_system`
+	/// nonce, so it must not be enabled when `pallet_evm` uses another `AccountProvider`.
+	const DIRECT_ACCOUNT_READ: bool = false;
+	/// The existential deposit of the runtime, used by `DIRECT_ACCOUNT_READ`.
+	const EXISTENTIAL_DEPOSIT: u128 = 0;
+
+	/// Upper bound on the gas `eth_call` and `eth_estimateGas` may use, regardless of the block
+	/// gas limit. Requests asking for more are rejected, and requests without a gas limit are
+	/// executed with this one. `None` disables the cap.
+	const RPC_GAS_CAP: Option<u64> = None;
 }
 
 impl<B: BlockT, C> EthConfig<B, C> for () {
@@ -538,8 +553,8 @@ where
 		self.send_transaction(request).await
 	}
 
-	async fn send_raw_transaction(&self, bytes: Bytes) -> RpcResult<H256> {
-		self.send_raw_transaction(bytes).await
+	async fn send_raw_transaction(&self, bytes: Bytes, from: Option<H160>) -> RpcResult<H256> {
+		self.send_raw_transaction(bytes, from).await
 	}
 }
 
@@ -30,9 +30,13 @@ use sp_inherents::CreateInherentDataProviders;
 use sp_runtime::traits::Block as BlockT;
 // Frontier
 use fc_rpc_core::types::*;
-use fp_rpc::EthereumRuntimeRPCApi;
+use fp_evm::Account;
+use fp_rpc::{EthereumRuntimeRPCApi, RuntimeStorageOverride};
 
-use crate::{eth::Eth, frontier_backend_client, internal_err};
+use crate::{
+	eth::{Eth, EthConfig},
+	frontier_backend_client, internal_err,
+};
 
 impl<B, C, P, CT, BE, A, CIDP, EC> Eth<B, C, P, CT, BE, A, CIDP, EC>
 where
@@ -44,6 +48,7 @@ where
 	P: TransactionPool<Block = B> + 'static,
 	A: ChainApi<Block = B>,
 	CIDP: CreateInherentDataProviders<B, ()> + Send + 'static,
+	EC: EthConfig<B, C>,
 {
 	pub async fn balance(
 		&self,
@@ -73,9 +78,7 @@ where
 				.map_err(|_| internal_err(format!("Expect block number from id: {id}")))?;
 
 			Ok(self
-				.client
-				.runtime_api()
-				.account_basic(substrate_hash, address)
+				.account_basic_at(substrate_hash, address)
 				.map_err(|err| internal_err(format!("Fetch account balances failed: {:?}", err)))?
 				.balance)
 		} else {
@@ -125,24 +128,16 @@ where
 			let substrate_hash = self.client.info().best_hash;
 
 			let nonce = self
-				.client
-				.runtime_api()
-				.account_basic(substrate_hash, address)
+				.account_basic_at(substrate_hash, address)
 				.map_err(|err| internal_err(format!("Fetch account nonce failed: {err}")))?
 				.nonce;
 
-			let mut current_nonce = nonce;
-			let mut current_tag = (address, nonce).encode();
-			for tx in self.pool.ready() {
-				// since transactions in `ready()` need to be ordered by nonce
-				// it's fine to continue with current iterator.
-				if tx.provides().first() == Some(&current_tag) {
-					current_nonce = current_nonce.saturating_add(1.into());
-					current_tag = (address, current_nonce).encode();
-				}
-			}
-
-			return Ok(current_nonce);
+			let ready = self.pool.ready().collect::<Vec<_>>();
+			return Ok(pending_nonce(
+				address,
+				nonce,
+				ready.iter().map(|tx| tx.provides()),
+			));
 		}
 
 		let id = match frontier_backend_client::native_block_id::<B, C>(
@@ -162,9 +157,7 @@ where
 			.map_err(|_| internal_err(format!("Expect block number from id: {id}")))?;
 
 		Ok(self
-			.client
-			.runtime_api()
-			.account_basic(substrate_hash, address)
+			.account_basic_at(substrate_hash, address)
 			.map_err(|err| internal_err(format!("Fetch account nonce failed: {err}")))?
 			.nonce)
 	}
@@ -204,4 +197,82 @@ where
 			Ok(Bytes(vec![]))
 		}
 	}
+
+	/// Fetch the account basic at the given block, reading `system.account` directly when
+	/// `EthConfig::DIRECT_ACCOUNT_READ` is set and the storage override supports it.
+	fn account_basic_at(
+		&self,
+		substrate_hash: B::Hash,
+		address: H160,
+	) -> Result<Account, sp_api::ApiError> {
+		if EC::DIRECT_ACCOUNT_READ {
+			if let Some(account) = EC::RuntimeStorageOverride::account_basic(
+				self.client.as_ref(),
+				substrate_hash,
+				address,
+				EC::EXISTENTIAL_DEPOSIT,
+			) {
+				return Ok(account);
+			}
+		}
+		self.client
+			.runtime_api()
+			.account_basic(substrate_hash, address)
+	}
+}
+
+/// Extend the on-chain `nonce` of `address` with the nonces provided by the ready pool
+/// transactions of the same sender, so the result is the next nonce the sender should use.
+fn pending_nonce<'a>(
+	address: H160,
+	nonce: U256,
+	ready_provides: impl Iterator<Item = &'a [Vec<u8>]>,
+) -> U256 {
+	let mut current_nonce = nonce;
+	let mut current_tag = (address, nonce).encode();
+	for provides in ready_provides {
+		// since transactions in `ready()` need to be ordered by nonce
+		// it's fine to continue with current iterator.
+		if provides.first() == Some(&current_tag) {
+			current_nonce = current_nonce.saturating_add(1.into());
+			current_tag = (address, current_nonce).encode();
+		}
+	}
+	current_nonce
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn tag(address: H160, nonce: u64) -> Vec<Vec<u8>> {
+		vec![(address, U256::from(nonce)).encode()]
+	}
+
+	#[test]
+	fn pending_nonce_counts_ready_transactions_of_sender() {
+		let alice = H160::repeat_byte(0xaa);
+		let bob = H160::repeat_byte(0xbb);
+		let ready = [tag(alice, 5), tag(bob, 0), tag(alice, 6)];
+
+		assert_eq!(
+			pending_nonce(alice, U256::from(5), ready.iter().map(Vec::as_slice)),
+			U256::from(7)
+		);
+		assert_eq!(
+			pending_nonce(bob, U256::zero(), ready.iter().map(Vec::as_slice)),
+			U256::from(1)
+		);
+	}
+
+	#[test]
+	fn pending_nonce_stops_at_nonce_gap() {
+		let alice = H160::repeat_byte(0xaa);
+		let ready = [tag(alice, 6), tag(alice, 7)];
+
+		assert_eq!(
+			pending_nonce(alice, U256::from(5), ready.iter().map(Vec::as_slice)),
+			U256::from(5)
+		);
+	}
 }
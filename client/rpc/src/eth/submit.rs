@@ -16,7 +16,7 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
-use ethereum_types::H256;
+use ethereum_types::{H160, H256};
 use futures::future::TryFutureExt;
 use jsonrpsee::core::RpcResult;
 // Substrate
@@ -33,8 +33,8 @@ use fc_rpc_core::types::*;
 use fp_rpc::{ConvertTransaction, ConvertTransactionRuntimeApi, EthereumRuntimeRPCApi};
 
 use crate::{
-	eth::{format, Eth},
-	internal_err,
+	eth::{format, Eth, EthConfig},
+	internal_err, verify_sender,
 };
 
 impl<B, C, P, CT, BE, A, CIDP, EC> Eth<B, C, P, CT, BE, A, CIDP, EC>
@@ -48,8 +48,13 @@ where
 	CT: ConvertTransaction<<B as BlockT>::Extrinsic> + 'static,
 	A: ChainApi<Block = B>,
 	CIDP: CreateInherentDataProviders<B, ()> + Send + 'static,
+	EC: EthConfig<B, C>,
 {
 	pub async fn send_transaction(&self, request: TransactionRequest) -> RpcResult<H256> {
+		if request.has_blobs() {
+			return Err(internal_err("blob transactions are not supported"));
+		}
+
 		let from = match request.from {
 			Some(from) => from,
 			None => {
@@ -155,7 +160,7 @@ where
 			.await
 	}
 
-	pub async fn send_raw_transaction(&self, bytes: Bytes) -> RpcResult<H256> {
+	pub async fn send_raw_transaction(&self, bytes: Bytes, from: Option<H160>) -> RpcResult<H256> {
 		let bytes = bytes.into_vec();
 		if bytes.is_empty() {
 			return Err(internal_err("transaction data is empty"));
@@ -166,9 +171,13 @@ where
 				Ok(transaction) => transaction,
 				Err(_) => return Err(internal_err("decode transaction failed")),
 			};
+		if let Some(from) = from {
+			verify_sender(&transaction, from)?;
+		}
 		let transaction_hash = transaction.hash();
 
 		let block_hash = self.client.info().best_hash;
+		self.validate_transaction(block_hash, &transaction)?;
 		let extrinsic = self.convert_transaction(block_hash, transaction)?;
 
 		self.pool
@@ -178,6 +187,29 @@ where
 			.await
 	}
 
+	/// Fail fast with a precise reason if the runtime already knows the transaction is invalid.
+	fn validate_transaction(
+		&self,
+		block_hash: B::Hash,
+		transaction: &ethereum::TransactionV2,
+	) -> RpcResult<()> {
+		let api_version = self
+			.client
+			.runtime_api()
+			.api_version::<dyn EthereumRuntimeRPCApi<B>>(block_hash)
+			.map_err(|_| internal_err("failed to retrieve Runtime Api version"))?;
+		if api_version < Some(10) {
+			// Older runtimes only report the reason through the pool.
+			return Ok(());
+		}
+
+		self.client
+			.runtime_api()
+			.validate_transaction(block_hash, transaction.clone())
+			.map_err(|err| internal_err(format!("runtime error: {err}")))?
+			.map_err(|err| internal_err(format::Geth::validation_error(err)))
+	}
+
 	fn convert_transaction(
 		&self,
 		block_hash: B::Hash,
@@ -34,7 +34,7 @@ use fc_rpc_core::types::*;
 use fp_rpc::EthereumRuntimeRPCApi;
 
 use crate::{
-	eth::{transaction_build, BlockInfo, Eth},
+	eth::{execute::decode_revert_reason, transaction_build, BlockInfo, Eth},
 	frontier_backend_client, internal_err,
 };
 
@@ -281,9 +281,15 @@ where
 					};
 
 				let status = statuses[index].clone();
+				let revert_reason = if status_code == 0 {
+					self.revert_reason(substrate_hash, status.transaction_index)
+				} else {
+					None
+				};
 				let mut cumulative_receipts = receipts;
 				cumulative_receipts.truncate((status.transaction_index + 1) as usize);
 				let transaction = block.transactions[index].clone();
+				let transaction_type = transaction_type(&transaction);
 				let effective_gas_price = match transaction {
 					EthereumTransaction::Legacy(t) => t.gas_price,
 					EthereumTransaction::EIP2930(t) => t.gas_price,
@@ -361,14 +367,112 @@ where
 					logs_bloom,
 					state_root: None,
 					effective_gas_price,
-					transaction_type: match receipt {
-						ethereum::ReceiptV3::Legacy(_) => U256::from(0),
-						ethereum::ReceiptV3::EIP2930(_) => U256::from(1),
-						ethereum::ReceiptV3::EIP1559(_) => U256::from(2),
-					},
+					transaction_type,
+					revert_reason,
 				}));
 			}
 			_ => Ok(None),
 		}
 	}
+
+	/// The revert reason of a failed transaction of the block, as `execution reverted: <reason>`.
+	///
+	/// `None` when the runtime does not keep the revert data of its transactions.
+	fn revert_reason(&self, substrate_hash: B::Hash, transaction_index: u32) -> Option<String> {
+		let api = self.client.runtime_api();
+		let data = match api.api_version::<dyn EthereumRuntimeRPCApi<B>>(substrate_hash) {
+			Ok(Some(api_version)) if api_version >= 17 => api
+				.revert_reason(substrate_hash, transaction_index)
+				.ok()
+				.flatten()?,
+			_ => return None,
+		};
+		Some(match decode_revert_reason(&data) {
+			Some(reason) => format!("execution reverted: {reason}"),
+			None => "execution reverted".to_string(),
+		})
+	}
+}
+
+/// The EIP-2718 type of a transaction envelope.
+///
+/// Receipts stored under older schemas are all converted to legacy receipts, so the receipt
+/// type must be derived from the transaction rather than from the receipt variant.
+fn transaction_type(transaction: &EthereumTransaction) -> U256 {
+	match transaction {
+		EthereumTransaction::Legacy(_) => U256::from(0),
+		EthereumTransaction::EIP2930(_) => U256::from(1),
+		EthereumTransaction::EIP1559(_) => U256::from(2),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use ethereum::{
+		EIP1559Transaction, EIP2930Transaction, LegacyTransaction, TransactionAction,
+		TransactionSignature,
+	};
+
+	fn legacy() -> EthereumTransaction {
+		EthereumTransaction::Legacy(LegacyTransaction {
+			nonce: U256::zero(),
+			gas_price: U256::from(1),
+			gas_limit: U256::from(21_000),
+			action: TransactionAction::Create,
+			value: U256::zero(),
+			input: vec![],
+			signature: TransactionSignature::new(
+				27,
+				H256::from_low_u64_be(1),
+				H256::from_low_u64_be(1),
+			)
+			.unwrap(),
+		})
+	}
+
+	fn eip2930() -> EthereumTransaction {
+		EthereumTransaction::EIP2930(EIP2930Transaction {
+			chain_id: 42,
+			nonce: U256::zero(),
+			gas_price: U256::from(1),
+			gas_limit: U256::from(21_000),
+			action: TransactionAction::Create,
+			value: U256::zero(),
+			input: vec![],
+			access_list: vec![],
+			odd_y_parity: false,
+			r: H256::from_low_u64_be(1),
+			s: H256::from_low_u64_be(1),
+		})
+	}
+
+	fn eip1559() -> EthereumTransaction {
+		EthereumTransaction::EIP1559(EIP1559Transaction {
+			chain_id: 42,
+			nonce: U256::zero(),
+			max_priority_fee_per_gas: U256::from(1),
+			max_fee_per_gas: U256::from(1),
+			gas_limit: U256::from(21_000),
+			action: TransactionAction::Create,
+			value: U256::zero(),
+			input: vec![],
+			access_list: vec![],
+			odd_y_parity: false,
+			r: H256::from_low_u64_be(1),
+			s: H256::from_low_u64_be(1),
+		})
+	}
+
+	#[test]
+	fn receipt_type_matches_transaction_envelope() {
+		assert_eq!(transaction_type(&legacy()), U256::from(0));
+		assert_eq!(transaction_type(&eip2930()), U256::from(1));
+		assert_eq!(transaction_type(&eip1559()), U256::from(2));
+		// The receipt type matches the EIP-2718 type byte of the encoded envelope.
+		for transaction in [eip2930(), eip1559()] {
+			let encoded = ethereum::EnvelopedEncodable::encode(&transaction);
+			assert_eq!(U256::from(encoded[0]), transaction_type(&transaction));
+		}
+	}
 }
@@ -16,7 +16,7 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
-use std::{marker::PhantomData, sync::Arc};
+use std::{collections::VecDeque, marker::PhantomData, sync::Arc};
 
 use ethereum::TransactionV2 as EthereumTransaction;
 use futures::{future, FutureExt as _, StreamExt as _};
@@ -36,7 +36,7 @@ use sc_transaction_pool_api::{InPoolTransaction, TransactionPool, TxHash};
 use sp_api::{ApiExt, ProvideRuntimeApi};
 use sp_blockchain::HeaderBackend;
 use sp_consensus::SyncOracle;
-use sp_runtime::traits::{Block as BlockT, UniqueSaturatedInto};
+use sp_runtime::traits::{Block as BlockT, Header as HeaderT, UniqueSaturatedInto};
 // Frontier
 use fc_mapping_sync::{EthereumBlockNotification, EthereumBlockNotificationSinks};
 use fc_rpc_core::{
@@ -49,6 +49,17 @@ use fc_rpc_core::{
 use fc_storage::StorageOverride;
 use fp_rpc::EthereumRuntimeRPCApi;
 
+/// Number of blocks a logs subscription remembers the delivered logs of, to flag them as
+/// `removed` if a reorganization retracts their block.
+const MAX_REORG_DEPTH: usize = 64;
+
+/// Logs of a block delivered to a logs subscription.
+struct DeliveredLogs<B: BlockT> {
+	hash: B::Hash,
+	number: u64,
+	logs: Vec<PubSubResult>,
+}
+
 #[derive(Clone, Debug)]
 pub struct EthereumSubIdProvider;
 impl IdProvider for EthereumSubIdProvider {
@@ -134,21 +145,64 @@ where
 		&self,
 		notification: EthereumBlockNotification<B>,
 		params: &FilteredParams,
-	) -> future::Ready<Option<impl Iterator<Item = PubSubResult>>> {
-		let res = if notification.is_new_best {
-			let substrate_hash = notification.hash;
-
-			let block = self.storage_override.current_block(substrate_hash);
-			let receipts = self.storage_override.current_receipts(substrate_hash);
+		delivered: &mut VecDeque<DeliveredLogs<B>>,
+	) -> future::Ready<Option<Vec<PubSubResult>>> {
+		if !notification.is_new_best {
+			return future::ready(None);
+		}
 
-			match (block, receipts) {
-				(Some(block), Some(receipts)) => Some((block, receipts)),
-				_ => None,
+		// Walk back from the new best block to the last block whose logs were delivered. Blocks
+		// delivered after that one were retracted by a reorganization.
+		let mut enacted = Vec::new();
+		let mut hash = notification.hash;
+		let fork_point = loop {
+			if let Some(position) = delivered.iter().position(|block| block.hash == hash) {
+				break Some(position);
+			}
+			let Ok(Some(header)) = self.client.header(hash) else {
+				return future::ready(None);
+			};
+			let number = UniqueSaturatedInto::<u64>::unique_saturated_into(*header.number());
+			enacted.push((hash, number));
+			match delivered.front() {
+				Some(oldest) if number > oldest.number && enacted.len() < MAX_REORG_DEPTH => {
+					hash = *header.parent_hash();
+				}
+				_ => break None,
 			}
-		} else {
-			None
 		};
-		future::ready(res.map(|(block, receipts)| PubSubResult::logs(block, receipts, params)))
+
+		// Only logs which were delivered are sent again as removed, before the logs of the new
+		// canonical blocks.
+		let retracted = delivered.split_off(fork_point.map_or(0, |position| position + 1));
+		let mut results = retracted
+			.into_iter()
+			.rev()
+			.flat_map(|block| block.logs)
+			.map(|result| match result {
+				PubSubResult::Log(mut log) => {
+					log.removed = true;
+					PubSubResult::Log(log)
+				}
+				result => result,
+			})
+			.collect::<Vec<_>>();
+
+		for (hash, number) in enacted.into_iter().rev() {
+			let block = self.storage_override.current_block(hash);
+			let receipts = self.storage_override.current_receipts(hash);
+			let (Some(block), Some(receipts)) = (block, receipts) else {
+				continue;
+			};
+			let logs = PubSubResult::logs(block, receipts, params).collect::<Vec<_>>();
+			results.extend(logs.iter().cloned());
+			delivered.push_back(DeliveredLogs { hash, number, logs });
+			if delivered.len() > MAX_REORG_DEPTH {
+				delivered.pop_front();
+			}
+		}
+
+		future::ready(Some(results))
 	}
 
 	fn pending_transaction(&self, hash: &TxHash<P>) -> future::Ready<Option<PubSubResult>> {
@@ -250,9 +304,10 @@ where
 						.await
 				}
 				Kind::Logs => {
+					let mut delivered = VecDeque::new();
 					let stream = block_notification_stream
 						.filter_map(move |notification| {
-							pubsub.notify_logs(notification, &filtered_params)
+							pubsub.notify_logs(notification, &filtered_params, &mut delivered)
 						})
 						.flat_map(futures::stream::iter);
 					PendingSubscription::from(pending)
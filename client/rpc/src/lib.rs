@@ -61,7 +61,7 @@ pub mod frontier_backend_client {
 
 	use ethereum_types::{H160, H256, U256};
 	use jsonrpsee::core::RpcResult;
-	use scale_codec::Encode;
+	use scale_codec::{Decode, Encode};
 	// Substrate
 	use sc_client_api::{
 		backend::{Backend, StorageProvider},
@@ -77,6 +77,55 @@ pub mod frontier_backend_client {
 	// Frontier
 	use fc_rpc_core::types::BlockNumberOrHash;
 
+	/// Storage key of the `system.account` entry for the given raw account id.
+	fn system_account_key(account_id: &[u8]) -> Vec<u8> {
+		let mut key = [twox_128(b"System"), twox_128(b"Account")]
+			.concat()
+			.to_vec();
+		key.extend(blake2_128(account_id));
+		key.extend(account_id);
+		key
+	}
+
+	/// Decode the nonce and balance out of an encoded `system.account` entry, using the same
+	/// layout assumptions as `set_overlayed_changes`.
+	///
+	/// The balance is the one `pallet_evm` reports, the balance `pallet_balances` lets the
+	/// account spend while keeping it alive: the free balance minus the largest of the frozen
+	/// balance not covered by reserves and the existential deposit.
+	pub(crate) fn decode_system_account(
+		item: &[u8],
+		existential_deposit: u128,
+	) -> Option<fp_evm::Account> {
+		let nonce = u32::decode(&mut item.get(0..4)?).ok()?;
+		let free = u128::decode(&mut item.get(16..32)?).ok()?;
+		let reserved = u128::decode(&mut item.get(32..48)?).ok()?;
+		let frozen = u128::decode(&mut item.get(48..64)?).ok()?;
+		let untouchable = frozen.saturating_sub(reserved).max(existential_deposit);
+		Some(fp_evm::Account {
+			nonce: U256::from(nonce),
+			balance: U256::from(free.saturating_sub(untouchable)),
+		})
+	}
+
+	fn read_system_account<B, C, BE>(
+		client: &C,
+		block: B::Hash,
+		account_id: &[u8],
+		existential_deposit: u128,
+	) -> Option<fp_evm::Account>
+	where
+		B: BlockT,
+		C: StorageProvider<B, BE>,
+		BE: Backend<B>,
+	{
+		let key = system_account_key(account_id);
+		match client.storage(block, &StorageKey(key)) {
+			Ok(Some(item)) => decode_system_account(&item.0, existential_deposit),
+			_ => None,
+		}
+	}
+
 	/// Implements a default runtime storage override.
 	/// It assumes that the balances and nonces are stored in pallet `system.account`, and
 	/// have `nonce: Index` = `u32` for  and `free: Balance` = `u128`.
@@ -101,12 +150,7 @@ pub mod frontier_backend_client {
 			balance: Option<U256>,
 			nonce: Option<U256>,
 		) {
-			let mut key = [twox_128(b"System"), twox_128(b"Account")]
-				.concat()
-				.to_vec();
-			let account_id = Self::into_account_id_bytes(address);
-			key.extend(blake2_128(&account_id));
-			key.extend(&account_id);
+			let key = system_account_key(&Self::into_account_id_bytes(address));
 
 			if let Ok(Some(item)) = client.storage(block, &StorageKey(key.clone())) {
 				let mut new_item = item.0;
@@ -128,6 +172,20 @@ pub mod frontier_backend_client {
 			let address: H160 = pallet_evm::IdentityAddressMapping::into_account_id(address);
 			address.as_ref().to_owned()
 		}
+
+		fn account_basic(
+			client: &C,
+			block: B::Hash,
+			address: H160,
+			existential_deposit: u128,
+		) -> Option<fp_evm::Account> {
+			read_system_account::<B, C, BE>(
+				client,
+				block,
+				&Self::into_account_id_bytes(address),
+				existential_deposit,
+			)
+		}
 	}
 
 	/// Implements a runtime storage override.
@@ -154,12 +212,7 @@ pub mod frontier_backend_client {
 			balance: Option<U256>,
 			nonce: Option<U256>,
 		) {
-			let mut key = [twox_128(b"System"), twox_128(b"Account")]
-				.concat()
-				.to_vec();
-			let account_id = Self::into_account_id_bytes(address);
-			key.extend(blake2_128(&account_id));
-			key.extend(&account_id);
+			let key = system_account_key(&Self::into_account_id_bytes(address));
 
 			if let Ok(Some(item)) = client.storage(block, &StorageKey(key.clone())) {
 				let mut new_item = item.0;
@@ -185,6 +238,20 @@ pub mod frontier_backend_client {
 				.as_slice()
 				.to_owned()
 		}
+
+		fn account_basic(
+			client: &C,
+			block: B::Hash,
+			address: H160,
+			existential_deposit: u128,
+		) -> Option<fp_evm::Account> {
+			read_system_account::<B, C, BE>(
+				client,
+				block,
+				&Self::into_account_id_bytes(address),
+				existential_deposit,
+			)
+		}
 	}
 
 	pub async fn native_block_id<B, C>(
@@ -346,14 +413,34 @@ pub fn public_key(transaction: &EthereumTransaction) -> Result<[u8; 64], sp_io::
 	sp_io::crypto::secp256k1_ecdsa_recover(&sig, &msg)
 }
 
+/// Checks that the signature of `transaction` recovers to `from`, the sender a client provided
+/// alongside the signed transaction.
+pub fn verify_sender(
+	transaction: &EthereumTransaction,
+	from: ethereum_types::H160,
+) -> Result<(), jsonrpsee::types::error::ErrorObjectOwned> {
+	let sender = public_key(transaction)
+		.map(|pk| ethereum_types::H160::from_slice(&sp_io::hashing::keccak_256(&pk)[12..]))
+		.map_err(|_| internal_err("invalid transaction signature"))?;
+	if sender != from {
+		return Err(internal_err(format!(
+			"transaction is signed by {sender:?}, not by `from` {from:?}"
+		)));
+	}
+	Ok(())
+}
+
 #[cfg(test)]
 mod tests {
 	use std::{path::PathBuf, sync::Arc};
 
 	use futures::executor;
 	use sc_block_builder::BlockBuilderBuilder;
+	use scale_codec::Encode;
 	use sp_blockchain::HeaderBackend;
 	use sp_consensus::BlockOrigin;
+	use sp_core::{H160, U256};
+	use sp_io::hashing::{blake2_128, twox_128};
 	use sp_runtime::{
 		generic::{Block, Header},
 		traits::{BlakeTwo256, Block as BlockT},
@@ -362,6 +449,8 @@ mod tests {
 		prelude::*, DefaultTestClientBuilderExt, TestClientBuilder,
 	};
 	use tempfile::tempdir;
+	// Frontier
+	use fp_rpc::RuntimeStorageOverride;
 
 	type OpaqueBlock =
 		Block<Header<u64, BlakeTwo256>, substrate_test_runtime_client::runtime::Extrinsic>;
@@ -497,4 +586,176 @@ mod tests {
 			b2_hash,
 		);
 	}
+
+	type Override<C> = super::frontier_backend_client::SystemAccountId20StorageOverride<
+		OpaqueBlock,
+		C,
+		substrate_test_runtime_client::Backend,
+	>;
+
+	#[test]
+	fn system_account_storage_override_reads_account_basic() {
+		let (client, _) = TestClientBuilder::new()
+			.build_with_native_executor::<substrate_test_runtime_client::runtime::RuntimeApi, _>(
+			None,
+		);
+		let client = Arc::new(client);
+
+		let address = H160::repeat_byte(0x11);
+		let nonce: u32 = 7;
+		let free: u128 = 1_000_000_000_000_000_000;
+
+		// `frame_system::AccountInfo<u32, pallet_balances::AccountData<u128>>` layout:
+		// (nonce, consumers, providers, sufficients, free, reserved, frozen, flags).
+		let account_info = (nonce, 0u32, 1u32, 0u32, free, 0u128, 0u128, 0u128).encode();
+
+		// `IdentityAddressMapping`, the account id is the address itself.
+		let account_id = address.as_bytes().to_vec();
+		let mut key = [twox_128(b"System"), twox_128(b"Account")]
+			.concat()
+			.to_vec();
+		key.extend(blake2_128(&account_id));
+		key.extend(&account_id);
+
+		let chain = client.chain_info();
+		let mut builder = BlockBuilderBuilder::new(&*client)
+			.on_parent_block(chain.best_hash)
+			.with_parent_block_number(chain.best_number)
+			.build()
+			.unwrap();
+		builder
+			.push_storage_change(key, Some(account_info))
+			.unwrap();
+		let a1 = builder.build().unwrap().block;
+		let a1_hash = a1.header.hash();
+		executor::block_on(client.import(BlockOrigin::Own, a1)).unwrap();
+
+		// The existential deposit is kept out of the reported balance.
+		let account =
+			Override::<_>::account_basic(client.as_ref(), a1_hash, address, 1_000).unwrap();
+		assert_eq!(account.nonce, U256::from(nonce));
+		assert_eq!(account.balance, U256::from(free - 1_000));
+
+		// Unknown accounts are left to the runtime API fallback.
+		assert!(Override::<_>::account_basic(
+			client.as_ref(),
+			a1_hash,
+			H160::repeat_byte(0x22),
+			1_000
+		)
+		.is_none());
+		assert!(
+			<() as RuntimeStorageOverride<OpaqueBlock, _>>::account_basic(
+				client.as_ref(),
+				a1_hash,
+				address,
+				1_000
+			)
+			.is_none()
+		);
+	}
+
+	mod balances_runtime {
+		use frame_support::{derive_impl, traits::ConstU128};
+
+		pub const EXISTENTIAL_DEPOSIT: u128 = 10;
+
+		frame_support::construct_runtime! {
+			pub enum Runtime {
+				System: frame_system,
+				Balances: pallet_balances,
+			}
+		}
+
+		#[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
+		impl frame_system::Config for Runtime {
+			type Block = frame_system::mocking::MockBlock<Runtime>;
+			type Nonce = u32;
+			type AccountData = pallet_balances::AccountData<u128>;
+		}
+
+		#[derive_impl(pallet_balances::config_preludes::TestDefaultConfig)]
+		impl pallet_balances::Config for Runtime {
+			type Balance = u128;
+			type ExistentialDeposit = ConstU128<EXISTENTIAL_DEPOSIT>;
+			type AccountStore = System;
+		}
+	}
+
+	#[test]
+	fn system_account_balance_matches_the_runtime_reducible_balance() {
+		use frame_support::traits::{
+			fungible::Inspect,
+			tokens::{Fortitude, Preservation},
+			Currency, LockableCurrency, ReservableCurrency, WithdrawReasons,
+		};
+
+		use balances_runtime::{Balances, Runtime, System, EXISTENTIAL_DEPOSIT};
+
+		// (free, reserved, locked)
+		let cases = [
+			(1_000, 0, 0),
+			(1_000, 0, 300),
+			(900, 100, 300),
+			(900, 400, 300),
+			(1_000, 0, 5),
+			(1_000, 0, 1_000),
+		];
+		for (free, reserved, locked) in cases {
+			sp_io::TestExternalities::default().execute_with(|| {
+				let who = 1;
+				let _ = Balances::make_free_balance_be(&who, free + reserved);
+				Balances::reserve(&who, reserved).unwrap();
+				if locked > 0 {
+					Balances::set_lock(*b"testlock", &who, locked, WithdrawReasons::all());
+				}
+				System::inc_account_nonce(who);
+
+				let item =
+					sp_io::storage::get(&frame_system::Account::<Runtime>::hashed_key_for(who))
+						.unwrap();
+				let account = super::frontier_backend_client::decode_system_account(
+					&item,
+					EXISTENTIAL_DEPOSIT,
+				)
+				.unwrap();
+
+				// `pallet_evm::Pallet::account_basic` reports this balance.
+				let reducible =
+					Balances::reducible_balance(&who, Preservation::Preserve, Fortitude::Polite);
+				assert_eq!(
+					account.balance,
+					U256::from(reducible),
+					"free {free}, reserved {reserved}, locked {locked}"
+				);
+				assert_eq!(account.nonce, U256::from(System::account_nonce(who)));
+			});
+		}
+	}
+
+	#[test]
+	fn verify_sender_rejects_a_mismatched_from() {
+		use super::EthSigner;
+
+		let signer = super::EthDevSigner::new();
+		let sender = signer.accounts()[0];
+		let message = ethereum::LegacyTransactionMessage {
+			nonce: U256::zero(),
+			gas_price: U256::from(1_000_000_000),
+			gas_limit: U256::from(21_000),
+			action: ethereum::TransactionAction::Call(H160::repeat_byte(0x42)),
+			value: U256::zero(),
+			input: Vec::new(),
+			chain_id: Some(42),
+		};
+		let transaction = signer
+			.sign(
+				fc_rpc_core::types::TransactionMessage::Legacy(message),
+				&sender,
+			)
+			.unwrap();
+
+		assert!(super::verify_sender(&transaction, sender).is_ok());
+		assert!(super::verify_sender(&transaction, H160::repeat_byte(0x42)).is_err());
+	}
 }
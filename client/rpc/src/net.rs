@@ -66,14 +66,47 @@ where
 	}
 
 	fn peer_count(&self) -> RpcResult<PeerCount> {
-		let peer_count = self.network.sync_num_connected();
-		Ok(match self.peer_count_as_hex {
-			true => PeerCount::String(format!("0x{:x}", peer_count)),
-			false => PeerCount::U32(peer_count as u32),
-		})
+		Ok(format_peer_count(
+			self.network.sync_num_connected(),
+			self.peer_count_as_hex,
+		))
 	}
 
 	fn is_listening(&self) -> RpcResult<bool> {
 		Ok(true)
 	}
 }
+
+/// Format the number of peers connected to the sync protocol, either as a hex
+/// quantity or as a plain number.
+fn format_peer_count(peer_count: usize, as_hex: bool) -> PeerCount {
+	match as_hex {
+		true => PeerCount::String(format!("0x{:x}", peer_count)),
+		false => PeerCount::U32(peer_count as u32),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn peer_count_is_formatted_as_hex_quantity() {
+		assert_eq!(
+			serde_json::to_value(format_peer_count(0, true)).unwrap(),
+			serde_json::json!("0x0")
+		);
+		assert_eq!(
+			serde_json::to_value(format_peer_count(26, true)).unwrap(),
+			serde_json::json!("0x1a")
+		);
+	}
+
+	#[test]
+	fn peer_count_is_formatted_as_number() {
+		assert_eq!(
+			serde_json::to_value(format_peer_count(26, false)).unwrap(),
+			serde_json::json!(26)
+		);
+	}
+}
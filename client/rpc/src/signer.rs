@@ -22,7 +22,7 @@ use jsonrpsee::types::ErrorObjectOwned;
 // Substrate
 use sp_core::hashing::keccak_256;
 // Frontier
-use fc_rpc_core::types::TransactionMessage;
+use fc_rpc_core::types::{TransactionMessage, TypedData};
 
 use crate::internal_err;
 
@@ -36,6 +36,21 @@ pub trait EthSigner: Send + Sync {
 		message: TransactionMessage,
 		address: &H160,
 	) -> Result<EthereumTransaction, ErrorObjectOwned>;
+	/// Sign an arbitrary message, prefixed as defined by `personal_sign`, returning the
+	/// 65-byte `r || s || v` signature.
+	fn sign_message(&self, _address: &H160, _message: &[u8]) -> Result<[u8; 65], ErrorObjectOwned> {
+		Err(internal_err("message signing not supported by this signer"))
+	}
+	/// Sign EIP-712 typed data, returning the 65-byte `r || s || v` signature.
+	fn sign_typed_data(
+		&self,
+		_address: &H160,
+		_typed_data: &TypedData,
+	) -> Result<[u8; 65], ErrorObjectOwned> {
+		Err(internal_err(
+			"typed data signing not supported by this signer",
+		))
+	}
 }
 
 pub struct EthDevSigner {
@@ -66,6 +81,33 @@ fn public_key_address(public: &libsecp256k1::PublicKey) -> H160 {
 	H160::from(H256::from(keccak_256(&res)))
 }
 
+/// Hash of a message as signed by `personal_sign`.
+fn personal_message_hash(message: &[u8]) -> [u8; 32] {
+	let mut prefixed = format!("\x19Ethereum Signed Message:\n{}", message.len()).into_bytes();
+	prefixed.extend_from_slice(message);
+	keccak_256(&prefixed)
+}
+
+impl EthDevSigner {
+	fn secret_for(&self, address: &H160) -> Option<&libsecp256k1::SecretKey> {
+		self.keys
+			.iter()
+			.find(|secret| &secret_key_address(secret) == address)
+	}
+
+	fn sign_hash(&self, address: &H160, hash: &[u8; 32]) -> Result<[u8; 65], ErrorObjectOwned> {
+		let secret = self
+			.secret_for(address)
+			.ok_or_else(|| internal_err("signer not available"))?;
+		let signing_message = libsecp256k1::Message::parse(hash);
+		let (signature, recid) = libsecp256k1::sign(&signing_message, secret);
+		let mut res = [0u8; 65];
+		res[0..64].copy_from_slice(&signature.serialize());
+		res[64] = 27 + recid.serialize();
+		Ok(res)
+	}
+}
+
 impl EthSigner for EthDevSigner {
 	fn accounts(&self) -> Vec<H160> {
 		self.keys.iter().map(secret_key_address).collect()
@@ -160,4 +202,109 @@ impl EthSigner for EthDevSigner {
 
 		transaction.ok_or_else(|| internal_err("signer not available"))
 	}
+
+	fn sign_message(&self, address: &H160, message: &[u8]) -> Result<[u8; 65], ErrorObjectOwned> {
+		self.sign_hash(address, &personal_message_hash(message))
+	}
+
+	fn sign_typed_data(
+		&self,
+		address: &H160,
+		typed_data: &TypedData,
+	) -> Result<[u8; 65], ErrorObjectOwned> {
+		let hash = typed_data.signing_hash().map_err(internal_err)?;
+		self.sign_hash(address, &hash.0)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn recover(hash: &[u8; 32], signature: &[u8; 65]) -> H160 {
+		let message = libsecp256k1::Message::parse(hash);
+		let sig = libsecp256k1::Signature::parse_standard_slice(&signature[0..64]).unwrap();
+		let recid = libsecp256k1::RecoveryId::parse_rpc(signature[64]).unwrap();
+		public_key_address(&libsecp256k1::recover(&message, &sig, &recid).unwrap())
+	}
+
+	#[test]
+	fn sign_message_uses_personal_sign_prefix() {
+		let signer = EthDevSigner::new();
+		let address = signer.accounts()[0];
+		let signature = signer.sign_message(&address, b"hello").unwrap();
+
+		assert_eq!(
+			H256::from(personal_message_hash(b"hello")),
+			H256::from(keccak_256(b"\x19Ethereum Signed Message:\n5hello"))
+		);
+		assert_eq!(
+			recover(&personal_message_hash(b"hello"), &signature),
+			address
+		);
+		assert!(signer
+			.sign_message(&H160::repeat_byte(0x42), b"hello")
+			.is_err());
+	}
+
+	#[test]
+	fn sign_typed_data_matches_eip712_example() {
+		// Example from https://eips.ethereum.org/EIPS/eip-712, signed with `keccak256("cow")`.
+		let typed_data: TypedData = serde_json::from_str(
+			r#"{
+				"types": {
+					"EIP712Domain": [
+						{ "name": "name", "type": "string" },
+						{ "name": "version", "type": "string" },
+						{ "name": "chainId", "type": "uint256" },
+						{ "name": "verifyingContract", "type": "address" }
+					],
+					"Person": [
+						{ "name": "name", "type": "string" },
+						{ "name": "wallet", "type": "address" }
+					],
+					"Mail": [
+						{ "name": "from", "type": "Person" },
+						{ "name": "to", "type": "Person" },
+						{ "name": "contents", "type": "string" }
+					]
+				},
+				"primaryType": "Mail",
+				"domain": {
+					"name": "Ether Mail",
+					"version": "1",
+					"chainId": 1,
+					"verifyingContract": "0xCcCCccccCCCCcCCCCCCcCcCccCcCCCcCcccccccC"
+				},
+				"message": {
+					"from": { "name": "Cow", "wallet": "0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826" },
+					"to": { "name": "Bob", "wallet": "0xbBbBBBBbbBBBbbbBbbBbbbbBBbBbbbbBbBbbBBbB" },
+					"contents": "Hello, Bob!"
+				}
+			}"#,
+		)
+		.unwrap();
+		let signer = EthDevSigner {
+			keys: vec![libsecp256k1::SecretKey::parse(&keccak_256(b"cow")).unwrap()],
+		};
+		let address: H160 = "0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826"
+			.parse()
+			.unwrap();
+		assert_eq!(signer.accounts(), vec![address]);
+
+		let signature = signer.sign_typed_data(&address, &typed_data).unwrap();
+		assert_eq!(
+			H256::from_slice(&signature[0..32]),
+			"0x4355c47d63924e8a72e509b65029052eb6c299d53a04e167c5775fd466751c9d"
+				.parse::<H256>()
+				.unwrap()
+		);
+		assert_eq!(
+			H256::from_slice(&signature[32..64]),
+			"0x07299936d304c153f6443dfa05f40ff007d72911b6f72307f996231605b91562"
+				.parse::<H256>()
+				.unwrap()
+		);
+		assert_eq!(signature[64], 28);
+	}
 }
@@ -16,7 +16,7 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
-use std::{marker::PhantomData, sync::Arc};
+use std::{collections::HashMap, marker::PhantomData, sync::Arc};
 
 use ethereum::TransactionV2 as EthereumTransaction;
 use ethereum_types::{H160, H256, U256};
@@ -85,15 +85,8 @@ where
 	{
 		let mut result = TransactionMap::<T>::new();
 		for txn in txns {
-			let nonce = match txn {
-				EthereumTransaction::Legacy(t) => t.nonce,
-				EthereumTransaction::EIP2930(t) => t.nonce,
-				EthereumTransaction::EIP1559(t) => t.nonce,
-			};
-			let from = match public_key(txn) {
-				Ok(pk) => H160::from(H256::from(keccak_256(&pk))),
-				Err(_) => H160::default(),
-			};
+			let nonce = Self::nonce(txn);
+			let from = Self::sender(txn);
 			result
 				.entry(from)
 				.or_default()
@@ -102,6 +95,21 @@ where
 		result
 	}
 
+	fn nonce(txn: &EthereumTransaction) -> U256 {
+		match txn {
+			EthereumTransaction::Legacy(t) => t.nonce,
+			EthereumTransaction::EIP2930(t) => t.nonce,
+			EthereumTransaction::EIP1559(t) => t.nonce,
+		}
+	}
+
+	fn sender(txn: &EthereumTransaction) -> H160 {
+		match public_key(txn) {
+			Ok(pk) => H160::from(H256::from(keccak_256(&pk))),
+			Err(_) => H160::default(),
+		}
+	}
+
 	/// Collect the extrinsics currently in the ready and future queues.
 	fn collect_txpool_transactions(&self) -> RpcResult<TxPoolTransactions> {
 		// Collect extrinsics in the ready validated pool.
@@ -168,4 +176,14 @@ where
 			queued: U256::from(status.future),
 		})
 	}
+
+	fn queued_from(&self, address: H160) -> RpcResult<HashMap<U256, H256>> {
+		let txns = self.collect_txpool_transactions()?;
+		Ok(txns
+			.future
+			.iter()
+			.filter(|txn| Self::sender(txn) == address)
+			.map(|txn| (Self::nonce(txn), txn.hash()))
+			.collect())
+	}
 }
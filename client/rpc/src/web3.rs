@@ -21,7 +21,7 @@ use std::{marker::PhantomData, sync::Arc};
 use ethereum_types::H256;
 use jsonrpsee::core::RpcResult;
 // Substrate
-use sp_api::{Core, ProvideRuntimeApi};
+use sp_api::{Core, ProvideRuntimeApi, RuntimeVersion};
 use sp_blockchain::HeaderBackend;
 use sp_core::keccak_256;
 use sp_runtime::traits::Block as BlockT;
@@ -34,6 +34,7 @@ use crate::internal_err;
 /// Web3 API implementation.
 pub struct Web3<B, C> {
 	client: Arc<C>,
+	client_name: Option<String>,
 	_marker: PhantomData<B>,
 }
 
@@ -41,9 +42,17 @@ impl<B, C> Web3<B, C> {
 	pub fn new(client: Arc<C>) -> Self {
 		Self {
 			client,
+			client_name: None,
 			_marker: PhantomData,
 		}
 	}
+
+	/// Set the client name reported by `web3_clientVersion`, instead of this crate's name and
+	/// version.
+	pub fn with_client_name(mut self, client_name: impl Into<String>) -> Self {
+		self.client_name = Some(client_name.into());
+		self
+	}
 }
 
 impl<B, C> Web3ApiServer for Web3<B, C>
@@ -60,17 +69,75 @@ where
 			.runtime_api()
 			.version(hash)
 			.map_err(|err| internal_err(format!("fetch runtime version failed: {:?}", err)))?;
-		Ok(format!(
-			"{spec_name}/v{spec_version}.{impl_version}/{pkg_name}-{pkg_version}",
-			spec_name = version.spec_name,
-			spec_version = version.spec_version,
-			impl_version = version.impl_version,
+		Ok(format_client_version(&version, self.client_name.as_deref()))
+	}
+
+	fn sha3(&self, input: Bytes) -> RpcResult<H256> {
+		Ok(sha3(&input))
+	}
+}
+
+/// Format the client version as `{spec_name}/v{spec_version}.{impl_version}/{client_name}`.
+fn format_client_version(version: &RuntimeVersion, client_name: Option<&str>) -> String {
+	let client_name = client_name.map(str::to_string).unwrap_or_else(|| {
+		format!(
+			"{pkg_name}-{pkg_version}",
 			pkg_name = env!("CARGO_PKG_NAME"),
 			pkg_version = env!("CARGO_PKG_VERSION")
-		))
+		)
+	});
+	format!(
+		"{spec_name}/v{spec_version}.{impl_version}/{client_name}",
+		spec_name = version.spec_name,
+		spec_version = version.spec_version,
+		impl_version = version.impl_version,
+	)
+}
+
+/// Keccak-256, as used by Ethereum, of the raw input bytes.
+fn sha3(input: &[u8]) -> H256 {
+	H256::from(keccak_256(input))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::str::FromStr;
+
+	#[test]
+	fn sha3_is_keccak_256() {
+		assert_eq!(
+			sha3(b"hello world"),
+			H256::from_str("47173285a8d7341e5e972fc677286384f802f8ef42a5ec5f03bbfa254cb01fad")
+				.unwrap()
+		);
+		assert_eq!(
+			sha3(&[]),
+			H256::from_str("c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470")
+				.unwrap()
+		);
 	}
 
-	fn sha3(&self, input: Bytes) -> RpcResult<H256> {
-		Ok(H256::from(keccak_256(&input.into_vec())))
+	#[test]
+	fn client_version_format() {
+		let version = RuntimeVersion {
+			spec_name: "frontier-template".into(),
+			spec_version: 7,
+			impl_version: 2,
+			..Default::default()
+		};
+
+		assert_eq!(
+			format_client_version(&version, Some("frontier-node-1.0.0")),
+			"frontier-template/v7.2/frontier-node-1.0.0"
+		);
+		assert_eq!(
+			format_client_version(&version, None),
+			format!(
+				"frontier-template/v7.2/{}-{}",
+				env!("CARGO_PKG_NAME"),
+				env!("CARGO_PKG_VERSION")
+			)
+		);
 	}
 }
@@ -136,6 +136,13 @@ where
 		}
 	}
 
+	fn current_transaction_count(&self, at: B::Hash) -> Option<u32> {
+		match self.querier.storage_schema(at) {
+			Some(_) => self.querier.current_transaction_count(at),
+			None => self.fallback.current_transaction_count(at),
+		}
+	}
+
 	fn elasticity(&self, at: B::Hash) -> Option<Permill> {
 		match self.querier.storage_schema(at) {
 			Some(EthereumStorageSchema::V1) => {
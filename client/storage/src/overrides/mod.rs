@@ -19,7 +19,7 @@
 use std::{marker::PhantomData, sync::Arc};
 
 use ethereum_types::{Address, H256, U256};
-use scale_codec::Decode;
+use scale_codec::{Compact, Decode};
 // Substrate
 use sc_client_api::{Backend, StorageProvider};
 use sp_io::hashing::{blake2_128, twox_128};
@@ -63,6 +63,11 @@ pub trait StorageOverride<Block: BlockT>: Send + Sync {
 	fn current_receipts(&self, at: Block::Hash) -> Option<Vec<ethereum::ReceiptV3>>;
 	/// Return the current ethereum transaction status.
 	fn current_transaction_statuses(&self, at: Block::Hash) -> Option<Vec<TransactionStatus>>;
+	/// Return the number of transactions in the current ethereum block.
+	fn current_transaction_count(&self, at: Block::Hash) -> Option<u32> {
+		self.current_block(at)
+			.map(|block| block.transactions.len() as u32)
+	}
 
 	/// Return the elasticity multiplier at the given post-eip1559 block.
 	fn elasticity(&self, at: Block::Hash) -> Option<Permill>;
@@ -74,6 +79,14 @@ fn storage_prefix_build(module: &[u8], storage: &[u8]) -> Vec<u8> {
 	[twox_128(module), twox_128(storage)].concat().to_vec()
 }
 
+/// Read the transaction count of a SCALE encoded ethereum block, without decoding the
+/// transactions. All block versions share the header as their first field, followed by the
+/// transactions vector.
+fn decode_transaction_count(mut data: &[u8]) -> Option<u32> {
+	ethereum::Header::decode(&mut data).ok()?;
+	Compact::<u32>::decode(&mut data).ok().map(|count| count.0)
+}
+
 fn blake2_128_extend(bytes: &[u8]) -> Vec<u8> {
 	let mut ext: Vec<u8> = blake2_128(bytes).to_vec();
 	ext.extend_from_slice(bytes);
@@ -138,6 +151,14 @@ where
 		self.query::<Block>(at, &StorageKey(key))
 	}
 
+	pub fn current_transaction_count(&self, at: B::Hash) -> Option<u32> {
+		let key = storage_prefix_build(PALLET_ETHEREUM, ETHEREUM_CURRENT_BLOCK);
+		match self.client.storage(at, &StorageKey(key)) {
+			Ok(Some(data)) => decode_transaction_count(&data.0),
+			_ => None,
+		}
+	}
+
 	pub fn current_receipts<Receipt: Decode>(&self, at: B::Hash) -> Option<Vec<Receipt>> {
 		let key = storage_prefix_build(PALLET_ETHEREUM, ETHEREUM_CURRENT_RECEIPTS);
 		self.query::<Vec<Receipt>>(at, &StorageKey(key))
@@ -153,3 +174,67 @@ where
 		self.query::<Permill>(at, &StorageKey(key))
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use ethereum::{BlockV2, EIP1559Transaction, PartialHeader, TransactionAction, TransactionV2};
+	use ethereum_types::{Bloom, H64};
+	use scale_codec::Encode;
+
+	fn transaction(nonce: u64) -> TransactionV2 {
+		TransactionV2::EIP1559(EIP1559Transaction {
+			chain_id: 42,
+			nonce: nonce.into(),
+			max_priority_fee_per_gas: U256::zero(),
+			max_fee_per_gas: U256::one(),
+			gas_limit: 21_000.into(),
+			action: TransactionAction::Call(Address::repeat_byte(0x11)),
+			value: U256::zero(),
+			input: vec![0xaa; 64],
+			access_list: vec![],
+			odd_y_parity: false,
+			r: H256::repeat_byte(0x01),
+			s: H256::repeat_byte(0x02),
+		})
+	}
+
+	fn block(transactions: Vec<TransactionV2>) -> BlockV2 {
+		let partial_header = PartialHeader {
+			parent_hash: H256::zero(),
+			beneficiary: Address::zero(),
+			state_root: H256::zero(),
+			receipts_root: H256::zero(),
+			logs_bloom: Bloom::default(),
+			difficulty: U256::zero(),
+			number: U256::one(),
+			gas_limit: U256::zero(),
+			gas_used: U256::zero(),
+			timestamp: 0,
+			extra_data: vec![0xde, 0xad],
+			mix_hash: H256::zero(),
+			nonce: H64::zero(),
+		};
+		BlockV2::new(partial_header, transactions, vec![])
+	}
+
+	#[test]
+	fn transaction_count_matches_decoded_block() {
+		for count in [0, 1, 3, 70] {
+			let block = block((0..count).map(transaction).collect());
+			let encoded = block.encode();
+
+			let decoded = BlockV2::decode(&mut &encoded[..]).unwrap();
+			assert_eq!(
+				decode_transaction_count(&encoded),
+				Some(decoded.transactions.len() as u32)
+			);
+		}
+	}
+
+	#[test]
+	fn transaction_count_of_malformed_block_is_none() {
+		assert_eq!(decode_transaction_count(&[]), None);
+		assert_eq!(decode_transaction_count(&[0u8; 16]), None);
+	}
+}
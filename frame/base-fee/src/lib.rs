@@ -231,6 +231,27 @@ impl<T: Config> fp_evm::FeeCalculator for Pallet<T> {
 	}
 }
 
+/// A [`fp_evm::FeeCalculator`] composing this pallet's base fee with another fee calculator
+/// used as a floor, typically `pallet_dynamic_fee`.
+///
+/// Precedence: the elasticity-driven `BaseFeePerGas` is the price the chain charges, and the
+/// floor's minimum gas price (e.g. the target price noted by block authors) is a lower bound
+/// it never drops below, so `min_gas_price = max(base fee, floor)`. Use this adapter as
+/// `pallet_evm::Config::FeeCalculator` instead of configuring both pallets independently, so
+/// only one price is ever reported to the EVM and the RPC.
+pub struct BaseFeeWithFloor<T, Floor>(core::marker::PhantomData<(T, Floor)>);
+
+impl<T: Config, Floor: fp_evm::FeeCalculator> fp_evm::FeeCalculator for BaseFeeWithFloor<T, Floor> {
+	fn min_gas_price() -> (U256, Weight) {
+		let (base_fee, base_fee_weight) = <Pallet<T> as fp_evm::FeeCalculator>::min_gas_price();
+		let (floor, floor_weight) = Floor::min_gas_price();
+		(
+			base_fee.max(floor),
+			base_fee_weight.saturating_add(floor_weight),
+		)
+	}
+}
+
 impl<T: Config> Pallet<T> {
 	pub fn set_base_fee_per_gas_inner(value: U256) -> Weight {
 		<BaseFeePerGas<T>>::put(value);
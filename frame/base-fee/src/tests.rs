@@ -303,3 +303,42 @@ fn set_elasticity_dispatchable() {
 		assert_eq!(Elasticity::<Test>::get(), Permill::from_parts(1_000));
 	});
 }
+
+parameter_types! {
+	pub static FloorGasPrice: U256 = U256::zero();
+}
+
+pub struct MockFloor;
+impl fp_evm::FeeCalculator for MockFloor {
+	fn min_gas_price() -> (U256, Weight) {
+		(FloorGasPrice::get(), Weight::zero())
+	}
+}
+
+#[test]
+fn base_fee_with_floor_reports_the_highest_price() {
+	use fp_evm::FeeCalculator;
+	type Composed = BaseFeeWithFloor<Test, MockFloor>;
+
+	let base_fee = U256::from(1_000_000_000u128);
+	new_test_ext(Some(base_fee), None).execute_with(|| {
+		// Floor below the base fee: the base fee wins.
+		FloorGasPrice::set(U256::from(500_000_000u128));
+		assert_eq!(Composed::min_gas_price().0, base_fee);
+
+		// Floor above the base fee: the floor wins.
+		FloorGasPrice::set(U256::from(2_000_000_000u128));
+		assert_eq!(Composed::min_gas_price().0, U256::from(2_000_000_000u128));
+
+		// Once elasticity raises the base fee above the floor, the base fee wins again.
+		System::register_extra_weight_unchecked(
+			Weight::from_parts(1_000_000_000_000, 0),
+			DispatchClass::Normal,
+		);
+		BaseFee::on_finalize(System::block_number());
+		let raised = BaseFeePerGas::<Test>::get();
+		FloorGasPrice::set(U256::from(1_000_000_000u128));
+		assert!(raised > base_fee);
+		assert_eq!(Composed::min_gas_price().0, raised);
+	});
+}
@@ -0,0 +1,36 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use super::*;
+use frame_benchmarking::{benchmarks, impl_benchmark_test_suite};
+
+benchmarks! {
+	logs_bloom {
+		let l in 0 .. 1000;
+		// Worst case: every log carries the maximum of four topics.
+		let logs: Vec<Log> = (0..l)
+			.map(|i| Log {
+				address: H160::from_low_u64_be(i.into()),
+				topics: (0..4u64).map(|t| H256::from_low_u64_be(t + u64::from(i) * 4)).collect(),
+				data: Vec::new(),
+			})
+			.collect();
+	}: {
+		compute_logs_bloom(&logs);
+	}
+}
+
+impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(0).1, crate::mock::Test);
@@ -27,12 +27,16 @@
 
 extern crate alloc;
 
+#[cfg(feature = "runtime-benchmarks")]
+pub mod benchmarking;
+
 #[cfg(all(feature = "std", test))]
 mod mock;
 #[cfg(all(feature = "std", test))]
 mod tests;
+pub mod weights;
 
-use alloc::{vec, vec::Vec};
+use alloc::{collections::BTreeSet, vec, vec::Vec};
 use core::marker::PhantomData;
 pub use ethereum::{
 	AccessListItem, BlockV2 as Block, LegacyTransactionMessage, Log, ReceiptV3 as Receipt,
@@ -47,17 +51,17 @@ use frame_support::{
 	dispatch::{
 		DispatchErrorWithPostInfo, DispatchInfo, DispatchResultWithPostInfo, Pays, PostDispatchInfo,
 	},
-	traits::{EnsureOrigin, Get, PalletInfoAccess, Time},
+	traits::{fungible::Inspect, Contains, EnsureOrigin, Get, PalletInfoAccess, Time},
 	weights::Weight,
 };
-use frame_system::{pallet_prelude::OriginFor, CheckWeight, WeightInfo};
+use frame_system::{pallet_prelude::OriginFor, CheckWeight, WeightInfo as _};
 use sp_runtime::{
 	generic::DigestItem,
 	traits::{DispatchInfoOf, Dispatchable, One, Saturating, UniqueSaturatedInto, Zero},
 	transaction_validity::{
 		InvalidTransaction, TransactionValidity, TransactionValidityError, ValidTransactionBuilder,
 	},
-	RuntimeDebug, SaturatedConversion,
+	BoundedVec, RuntimeDebug, SaturatedConversion,
 };
 use sp_version::RuntimeVersion;
 // Frontier
@@ -69,7 +73,11 @@ use fp_evm::{
 };
 pub use fp_rpc::TransactionStatus;
 use fp_storage::{EthereumStorageSchema, PALLET_ETHEREUM_SCHEMA};
-use pallet_evm::{BlockHashMapping, FeeCalculator, GasWeightMapping, Runner};
+use pallet_evm::{
+	AccountProvider, AddressMapping, BlockHashMapping, FeeCalculator, GasWeightMapping, Runner,
+};
+
+pub use self::weights::WeightInfo;
 
 #[derive(Clone, Eq, PartialEq, RuntimeDebug)]
 #[derive(Encode, Decode, MaxEncodedLen, TypeInfo)]
@@ -137,6 +145,10 @@ where
 		len: usize,
 	) -> Option<Result<(), TransactionValidityError>> {
 		if let Call::transact { transaction } = self {
+			if Pallet::<T>::is_block_full() {
+				return Some(Err(InvalidTransaction::ExhaustsResources.into()));
+			}
+
 			if let Err(e) = CheckWeight::<T>::do_pre_dispatch(dispatch_info, len) {
 				return Some(Err(e));
 			}
@@ -171,6 +183,23 @@ where
 	}
 }
 
+/// Gas charged per calldata token by the EIP-7623 calldata floor.
+pub const TOTAL_COST_FLOOR_PER_TOKEN: u64 = 10;
+
+/// Maximum number of bytes of revert data kept in [`RevertReasons`], longer data is truncated.
+pub const MAX_REVERT_REASON_LEN: u32 = 1024;
+
+/// Gas cost of the cheapest log, a `LOG0` without data.
+const LOG_GAS: u64 = 375;
+
+/// Proof size reserved for each state entry read by [`EthereumStateRoot`].
+const STATE_ROOT_ENTRY_PROOF_SIZE: u64 = fp_evm::ACCOUNT_STORAGE_PROOF_SIZE;
+
+/// Maximum number of logs an execution with the given gas limit can emit.
+fn max_logs(gas_limit: u64) -> u32 {
+	(gas_limit / LOG_GAS).saturated_into()
+}
+
 #[derive(Copy, Clone, Eq, PartialEq, Default)]
 pub enum PostLogContent {
 	#[default]
@@ -202,13 +231,54 @@ pub mod pallet {
 		type StateRoot: Get<H256>;
 		/// What's included in the PostLog.
 		type PostLogContent: Get<PostLogContent>;
-		/// The maximum length of the extra data in the Executed event.
+		/// The maximum length of the extra data in the Executed event and in the block header.
 		type ExtraDataLength: Get<u32>;
+		/// The extra data written into each ethereum block header, e.g. a client identifier.
+		/// Must not be longer than `ExtraDataLength`.
+		type ExtraData: Get<Vec<u8>>;
+		/// Whether transactions with a zero gas price are accepted regardless of the base fee.
+		/// No fee is withdrawn for such transactions, so the chain is expected to charge the
+		/// sender, or a sponsor, by other means.
+		type AllowZeroGasPrice: Get<bool>;
+		/// The maximum number of ethereum transactions in a block, if any.
+		type MaxTransactionsPerBlock: Get<Option<u32>>;
+		/// Addresses which may not send ethereum transactions, nor be called by them if
+		/// `BlockTransactionRecipients` is enabled.
+		type TransactionBlocklist: Contains<H160>;
+		/// Whether ethereum transactions calling an address in `TransactionBlocklist` are
+		/// rejected as well.
+		type BlockTransactionRecipients: Get<bool>;
+		/// Whether an `Executed` event is deposited for each applied ethereum transaction.
+		/// Chains that index transactions from receipts may disable it to save event storage.
+		type EmitExecutedEvent: Get<bool>;
+		/// Gas added to the intrinsic cost of every ethereum transaction, charged to the sender
+		/// for the extrinsic overhead the EVM doesn't meter, such as signature recovery.
+		type ExtrinsicBaseGas: Get<u64>;
+		/// Whether ethereum transactions are charged at least the EIP-7623 calldata floor, i.e.
+		/// the base cost plus `TOTAL_COST_FLOOR_PER_TOKEN` gas per calldata token.
+		type CalldataFloorPricing: Get<bool>;
+		/// Maximum number of entries in the access list of an ethereum transaction, counting
+		/// each address and each storage key.
+		type MaxAccessListSize: Get<u32>;
+		/// Maximum number of stale `BlockHash` entries removed per block. Entries older than
+		/// the `BlockHashCount` window are left behind when that window is reduced, and are
+		/// then removed by batches of at most this size.
+		type BlockHashPruningLimit: Get<u32>;
+		/// Maximum number of state entries read by [`EthereumStateRoot`] in a block, counting
+		/// each account key, basic account, code and storage entry. Their cost is reserved in
+		/// every block, and a larger state falls back to the intermediate state root. Set to 0
+		/// when `EthereumStateRoot` is not used.
+		type MaxStateRootEntries: Get<u32>;
+		/// Weight information for the work done on behalf of ethereum transactions.
+		type WeightInfo: WeightInfo;
 	}
 
 	pub mod config_preludes {
 		use super::*;
-		use frame_support::{derive_impl, parameter_types};
+		use frame_support::{
+			derive_impl, parameter_types,
+			traits::{ConstBool, ConstU32},
+		};
 
 		pub struct TestDefaultConfig;
 
@@ -229,6 +299,18 @@ pub mod pallet {
 			type StateRoot = IntermediateStateRoot<Self::Version>;
 			type PostLogContent = PostBlockAndTxnHashes;
 			type ExtraDataLength = ConstU32<30>;
+			type ExtraData = ();
+			type AllowZeroGasPrice = ConstBool<false>;
+			type MaxTransactionsPerBlock = ();
+			type TransactionBlocklist = ();
+			type BlockTransactionRecipients = ConstBool<true>;
+			type EmitExecutedEvent = ConstBool<true>;
+			type ExtrinsicBaseGas = ();
+			type CalldataFloorPricing = ConstBool<false>;
+			type MaxAccessListSize = ConstU32<{ u32::MAX }>;
+			type BlockHashPruningLimit = ConstU32<32>;
+			type MaxStateRootEntries = ConstU32<0>;
+			type WeightInfo = ();
 		}
 	}
 
@@ -258,8 +340,14 @@ pub mod pallet {
 			Pending::<T>::kill();
 		}
 
-		fn on_initialize(_: BlockNumberFor<T>) -> Weight {
+		fn on_initialize(n: BlockNumberFor<T>) -> Weight {
 			let mut weight = T::SystemWeightInfo::kill_storage(1);
+			weight.saturating_accrue(Self::prune_stale_block_hashes(n));
+
+			// The revert reasons are kept until the end of the block they were recorded in, there
+			// is at most one per ethereum transaction of the previous block.
+			let removed = RevertReasons::<T>::clear(u32::MAX, None).unique;
+			weight.saturating_accrue(T::DbWeight::get().writes(removed.into()));
 
 			// If the digest contain an existing ethereum block(encoded as PreLog), If contains,
 			// execute the imported block firstly and disable transact dispatch function.
@@ -285,7 +373,10 @@ pub mod pallet {
 			//	- read: frame_system::Pallet::<T>::block_number()
 			//	- write: <Pallet<T>>::store_block()
 			//	- write: <BlockHash<T>>::remove()
-			weight.saturating_add(T::DbWeight::get().reads_writes(2, 2))
+			//	- reads: the `EthereumStateRoot` computation, at most `MaxStateRootEntries`
+			weight
+				.saturating_add(T::DbWeight::get().reads_writes(2, 2))
+				.saturating_add(Self::state_root_weight())
 		}
 
 		fn on_runtime_upgrade() -> Weight {
@@ -296,6 +387,17 @@ pub mod pallet {
 
 			T::DbWeight::get().writes(1)
 		}
+
+		fn integrity_test() {
+			assert!(
+				T::ExtraData::get().len() <= T::ExtraDataLength::get() as usize,
+				"ExtraData must not be longer than ExtraDataLength"
+			);
+			assert!(
+				Self::state_root_weight().all_lte(T::BlockWeights::get().max_block),
+				"The MaxStateRootEntries reads must fit in a block"
+			);
+		}
 	}
 
 	#[pallet::call]
@@ -307,10 +409,18 @@ pub mod pallet {
 		#[pallet::call_index(0)]
 		#[pallet::weight({
 			let without_base_extrinsic_weight = true;
-			<T as pallet_evm::Config>::GasWeightMapping::gas_to_weight({
+			let gas_limit: u64 = {
 				let transaction_data: TransactionData = transaction.into();
 				transaction_data.gas_limit.unique_saturated_into()
-			}, without_base_extrinsic_weight)
+			};
+			<T as pallet_evm::Config>::GasWeightMapping::gas_to_weight(
+				gas_limit,
+				without_base_extrinsic_weight,
+			)
+			// The revert data of the transaction may be written to `RevertReasons`.
+			.saturating_add(T::DbWeight::get().writes(1))
+			// The logs are folded into the block bloom, as many as the gas limit can pay for.
+			.saturating_add(<T as Config>::WeightInfo::logs_bloom(max_logs(gas_limit)))
 		})]
 		pub fn transact(
 			origin: OriginFor<T>,
@@ -365,6 +475,17 @@ pub mod pallet {
 	#[pallet::storage]
 	pub type CurrentTransactionStatuses<T: Config> = StorageValue<_, Vec<TransactionStatus>>;
 
+	/// Revert data of the ethereum transactions of the current block that reverted, by
+	/// transaction index, truncated to [`MAX_REVERT_REASON_LEN`] bytes.
+	#[pallet::storage]
+	pub type RevertReasons<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		u32,
+		BoundedVec<u8, ConstU32<MAX_REVERT_REASON_LEN>>,
+		OptionQuery,
+	>;
+
 	// Mapping for block number and hashes.
 	#[pallet::storage]
 	pub type BlockHash<T: Config> = StorageMap<_, Twox64Concat, U256, H256, ValueQuery>;
@@ -388,6 +509,22 @@ pub mod pallet {
 	}
 }
 
+/// Compute the bloom filter of the given logs, as found in receipts and block headers.
+pub fn compute_logs_bloom(logs: &[Log]) -> Bloom {
+	let mut bloom = Bloom::default();
+	accrue_logs_bloom(logs, &mut bloom);
+	bloom
+}
+
+fn accrue_logs_bloom(logs: &[Log], bloom: &mut Bloom) {
+	for log in logs {
+		bloom.accrue(BloomInput::Raw(&log.address[..]));
+		for topic in &log.topics {
+			bloom.accrue(BloomInput::Raw(&topic[..]));
+		}
+	}
+}
+
 impl<T: Config> Pallet<T> {
 	pub fn transaction_weight(transaction_data: &TransactionData) -> (Option<Weight>, Option<u64>) {
 		match <T as pallet_evm::Config>::GasWeightMapping::gas_to_weight(
@@ -402,6 +539,129 @@ impl<T: Config> Pallet<T> {
 		}
 	}
 
+	/// Whether the block holds `MaxTransactionsPerBlock` ethereum transactions already.
+	fn is_block_full() -> bool {
+		T::MaxTransactionsPerBlock::get()
+			.is_some_and(|max| Pending::<T>::decode_len().unwrap_or_default() >= max as usize)
+	}
+
+	/// Whether the transaction sender, or its call target if `BlockTransactionRecipients` is
+	/// enabled, is in `TransactionBlocklist`.
+	fn is_blocked(origin: &H160, transaction_data: &TransactionData) -> bool {
+		let blocked_target = match transaction_data.action {
+			TransactionAction::Call(target) => {
+				T::BlockTransactionRecipients::get() && T::TransactionBlocklist::contains(&target)
+			}
+			TransactionAction::Create => false,
+		};
+		blocked_target || T::TransactionBlocklist::contains(origin)
+	}
+
+	/// Remove up to `BlockHashPruningLimit` of the `BlockHash` entries that are older than the
+	/// one `on_finalize` prunes at block `n`, oldest first, and return the weight consumed.
+	///
+	/// `BlockHash` entries always form a contiguous range besides the genesis hash, so the
+	/// oldest one is found with a binary search.
+	fn prune_stale_block_hashes(n: BlockNumberFor<T>) -> Weight {
+		let limit = T::BlockHashPruningLimit::get();
+		let pruned_on_finalize: u32 = n
+			.saturating_sub(T::BlockHashCount::get())
+			.saturating_sub(One::one())
+			.unique_saturated_into();
+		// Keep the genesis hash.
+		if limit == 0 || pruned_on_finalize <= 1 {
+			return Weight::zero();
+		}
+
+		let mut reads = 1;
+		let newest_stale = pruned_on_finalize - 1;
+		if !<BlockHash<T>>::contains_key(U256::from(newest_stale)) {
+			return T::DbWeight::get().reads(reads);
+		}
+
+		let (mut oldest_stale, mut high) = (1, newest_stale);
+		while oldest_stale < high {
+			let middle = oldest_stale + (high - oldest_stale) / 2;
+			reads += 1;
+			if <BlockHash<T>>::contains_key(U256::from(middle)) {
+				high = middle;
+			} else {
+				oldest_stale = middle + 1;
+			}
+		}
+
+		let last = newest_stale.min(oldest_stale.saturating_add(limit - 1));
+		for number in oldest_stale..=last {
+			<BlockHash<T>>::remove(U256::from(number));
+		}
+
+		T::DbWeight::get().reads_writes(reads, u64::from(last - oldest_stale + 1))
+	}
+
+	/// The EIP-7623 calldata floor of the transaction, or zero if `CalldataFloorPricing` is
+	/// disabled. Zero calldata bytes count as one token, and other bytes as four.
+	fn calldata_floor(transaction_data: &TransactionData) -> u64 {
+		if !T::CalldataFloorPricing::get() {
+			return 0;
+		}
+
+		let tokens = transaction_data
+			.input
+			.iter()
+			.map(|byte| if *byte == 0 { 1u64 } else { 4u64 })
+			.sum::<u64>();
+		Self::evm_config()
+			.gas_transaction_call
+			.saturating_add(tokens.saturating_mul(TOTAL_COST_FLOOR_PER_TOKEN))
+	}
+
+	/// Check that the gas limit of the transaction covers its EIP-7623 calldata floor.
+	fn check_calldata_floor(
+		transaction_data: &TransactionData,
+	) -> Result<(), TransactionValidationError> {
+		if transaction_data.gas_limit < U256::from(Self::calldata_floor(transaction_data)) {
+			return Err(TransactionValidationError::GasLimitTooLow);
+		}
+		Ok(())
+	}
+
+	/// Check that the access list of the transaction doesn't exceed `MaxAccessListSize` entries.
+	fn check_access_list_size(
+		transaction_data: &TransactionData,
+	) -> Result<(), TransactionValidationError> {
+		let size = transaction_data
+			.access_list
+			.iter()
+			.map(|(_, storage_keys)| 1u64.saturating_add(storage_keys.len() as u64))
+			.fold(0u64, u64::saturating_add);
+		if size > u64::from(T::MaxAccessListSize::get()) {
+			return Err(TransactionValidationError::AccessListTooLarge);
+		}
+		Ok(())
+	}
+
+	/// Whether the transaction has a zero gas price and `AllowZeroGasPrice` is enabled, in which
+	/// case the base fee check is skipped. The runner doesn't withdraw any fee for it.
+	fn is_zero_gas_price_allowed(transaction_data: &TransactionData) -> bool {
+		T::AllowZeroGasPrice::get()
+			&& transaction_data
+				.gas_price
+				.or(transaction_data.max_fee_per_gas)
+				.unwrap_or_default()
+				.is_zero()
+	}
+
+	/// The EVM configuration ethereum transactions are validated and executed with: the
+	/// runtime's configuration with `ExtrinsicBaseGas` added to the intrinsic cost. Runtime APIs
+	/// simulating or estimating transactions should use it too.
+	pub fn evm_config() -> evm::Config {
+		let mut config = T::config().clone();
+		let base_gas = T::ExtrinsicBaseGas::get();
+		config.gas_transaction_call = config.gas_transaction_call.saturating_add(base_gas);
+		config.gas_transaction_create = config.gas_transaction_create.saturating_add(base_gas);
+		config
+	}
+
 	fn recover_signer(transaction: &Transaction) -> Option<H160> {
 		let mut sig = [0u8; 65];
 		let mut msg = [0u8; 32];
@@ -445,13 +705,13 @@ impl<T: Config> Pallet<T> {
 			transactions.push(transaction);
 			statuses.push(status);
 			receipts.push(receipt.clone());
-			let (logs, used_gas) = match receipt {
+			let (logs, used_gas) = match &receipt {
 				Receipt::Legacy(d) | Receipt::EIP2930(d) | Receipt::EIP1559(d) => {
-					(d.logs.clone(), d.used_gas)
+					(&d.logs, d.used_gas)
 				}
 			};
 			cumulative_gas_used = used_gas;
-			Self::logs_bloom(logs, &mut logs_bloom);
+			accrue_logs_bloom(logs, &mut logs_bloom);
 		}
 
 		let ommers = Vec::<ethereum::Header>::new();
@@ -473,7 +733,7 @@ impl<T: Config> Pallet<T> {
 			gas_limit: T::BlockGasLimit::get(),
 			gas_used: cumulative_gas_used,
 			timestamp: T::Timestamp::now().unique_saturated_into(),
-			extra_data: Vec::new(),
+			extra_data: T::ExtraData::get(),
 			mix_hash: H256::default(),
 			nonce: H64::default(),
 		};
@@ -503,15 +763,6 @@ impl<T: Config> Pallet<T> {
 		}
 	}
 
-	fn logs_bloom(logs: Vec<Log>, bloom: &mut Bloom) {
-		for log in logs {
-			bloom.accrue(BloomInput::Raw(&log.address[..]));
-			for topic in log.topics {
-				bloom.accrue(BloomInput::Raw(&topic[..]));
-			}
-		}
-	}
-
 	// Controls that must be performed by the pool.
 	// The controls common with the State Transition Function (STF) are in
 	// the function `validate_transaction_common`.
@@ -521,27 +772,8 @@ impl<T: Config> Pallet<T> {
 	) -> TransactionValidity {
 		let transaction_data: TransactionData = transaction.into();
 		let transaction_nonce = transaction_data.nonce;
-		let (weight_limit, proof_size_base_cost) = Self::transaction_weight(&transaction_data);
-		let (base_fee, _) = T::FeeCalculator::min_gas_price();
-		let (who, _) = pallet_evm::Pallet::<T>::account_basic(&origin);
-
-		let _ = CheckEvmTransaction::<InvalidTransactionWrapper>::new(
-			CheckEvmTransactionConfig {
-				evm_config: T::config(),
-				block_gas_limit: T::BlockGasLimit::get(),
-				base_fee,
-				chain_id: T::ChainId::get(),
-				is_transactional: true,
-			},
-			transaction_data.clone().into(),
-			weight_limit,
-			proof_size_base_cost,
-		)
-		.validate_in_pool_for(&who)
-		.and_then(|v| v.with_chain_id())
-		.and_then(|v| v.with_base_fee())
-		.and_then(|v| v.with_balance_for(&who))
-		.map_err(|e| e.0)?;
+		let (who, base_fee) = Self::validate_transaction_common(origin, &transaction_data, true)
+			.map_err(|e| InvalidTransactionWrapper::from(e).0)?;
 
 		// EIP-3607: https://eips.ethereum.org/EIPS/eip-3607
 		// Do not allow transactions for which `tx.sender` has any code deployed.
@@ -550,7 +782,9 @@ impl<T: Config> Pallet<T> {
 		// on transaction execution, otherwise a contract tx will be included in
 		// the mempool and pollute the mempool forever.
 		if !pallet_evm::AccountCodes::<T>::get(origin).is_empty() {
-			return Err(InvalidTransaction::BadSigner.into());
+			return Err(
+				InvalidTransaction::Custom(TransactionValidationError::SenderNotEoa as u8).into(),
+			);
 		}
 
 		let priority = match (
@@ -611,11 +845,7 @@ impl<T: Config> Pallet<T> {
 					to,
 					contract_address: None,
 					logs: info.logs.clone(),
-					logs_bloom: {
-						let mut bloom: Bloom = Bloom::default();
-						Self::logs_bloom(info.logs, &mut bloom);
-						bloom
-					},
+					logs_bloom: compute_logs_bloom(&info.logs),
 				},
 				info.weight_info,
 				info.used_gas,
@@ -655,11 +885,7 @@ impl<T: Config> Pallet<T> {
 					to,
 					contract_address: Some(info.value),
 					logs: info.logs.clone(),
-					logs_bloom: {
-						let mut bloom: Bloom = Bloom::default();
-						Self::logs_bloom(info.logs, &mut bloom);
-						bloom
-					},
+					logs_bloom: compute_logs_bloom(&info.logs),
 				},
 				info.weight_info,
 				info.used_gas,
@@ -706,15 +932,35 @@ impl<T: Config> Pallet<T> {
 			}
 		};
 
+		// Keep the revert data, so that the reason of the failure can be queried after the block.
+		let mut revert_reason_weight = Weight::zero();
+		if let ExitReason::Revert(_) = reason {
+			let data = match &info {
+				CallOrCreateInfo::Call(info) => &info.value,
+				CallOrCreateInfo::Create(info) => &info.output,
+			};
+			let len = data.len().min(MAX_REVERT_REASON_LEN as usize);
+			RevertReasons::<T>::insert(
+				transaction_index,
+				BoundedVec::truncate_from(data[..len].to_vec()),
+			);
+			revert_reason_weight = T::DbWeight::get().writes(1);
+		}
+
+		// The logs are folded into the block bloom in `on_finalize`, the transaction weight
+		// reserves it for the most logs its gas limit allows.
+		let logs_bloom_weight = <T as Config>::WeightInfo::logs_bloom(status.logs.len() as u32);
 		Pending::<T>::append((transaction, status, receipt));
 
-		Self::deposit_event(Event::Executed {
-			from: source,
-			to: dest.unwrap_or_default(),
-			transaction_hash,
-			exit_reason: reason,
-			extra_data,
-		});
+		if T::EmitExecutedEvent::get() {
+			Self::deposit_event(Event::Executed {
+				from: source,
+				to: dest.unwrap_or_default(),
+				transaction_hash,
+				exit_reason: reason,
+				extra_data,
+			});
+		}
 
 		Ok((
 			PostDispatchInfo {
@@ -731,7 +977,11 @@ impl<T: Config> Pallet<T> {
 							*gas_to_weight.proof_size_mut() = proof_size_usage;
 						}
 					}
-					Some(gas_to_weight)
+					Some(
+						gas_to_weight
+							.saturating_add(revert_reason_weight)
+							.saturating_add(logs_bloom_weight),
+					)
 				},
 				pays_fee: Pays::No,
 			},
@@ -744,6 +994,127 @@ impl<T: Config> Pallet<T> {
 		<CurrentBlock<T>>::get().map(|block| block.header.hash())
 	}
 
+	/// Get the block with the given number along with its receipts and transaction statuses.
+	///
+	/// Only the current block is retained in storage, so any other number yields `None`.
+	pub fn block_by_number(number: U256) -> Option<(Block, Vec<Receipt>, Vec<TransactionStatus>)> {
+		let block = <CurrentBlock<T>>::get().filter(|block| block.header.number == number)?;
+		let receipts = <CurrentReceipts<T>>::get()?;
+		let statuses = <CurrentTransactionStatuses<T>>::get()?;
+		Some((block, receipts, statuses))
+	}
+
+	/// Get the canonical encoding of a transaction of the current block, as given by
+	/// `EnvelopedEncodable::encode`: the RLP of a legacy transaction, or the transaction type
+	/// followed by the RLP payload of a typed one.
+	pub fn raw_transaction(hash: H256) -> Option<Vec<u8>> {
+		<CurrentBlock<T>>::get()?
+			.transactions
+			.into_iter()
+			.find(|transaction| transaction.hash() == hash)
+			.map(|transaction| ethereum::EnvelopedEncodable::encode(&transaction).to_vec())
+	}
+
+	/// Cumulative gas used by the transactions already applied in the current block.
+	pub fn pending_gas_used() -> U256 {
+		Pending::<T>::get()
+			.last()
+			.map(|(_, _, receipt)| match receipt {
+				Receipt::Legacy(d) | Receipt::EIP2930(d) | Receipt::EIP1559(d) => d.used_gas,
+			})
+			.unwrap_or_default()
+	}
+
+	/// Weight reserved for [`EthereumStateRoot`], reading at most `MaxStateRootEntries`.
+	fn state_root_weight() -> Weight {
+		let entries = u64::from(T::MaxStateRootEntries::get());
+		T::DbWeight::get()
+			.reads(entries)
+			.saturating_add(Weight::from_parts(
+				0,
+				entries.saturating_mul(STATE_ROOT_ENTRY_PROOF_SIZE),
+			))
+	}
+
+	/// Ethereum-compatible state root of the given accounts, or `None` if it takes reading more
+	/// than `limit` entries. See [`EthereumStateRoot`].
+	fn state_root_of(addresses: BTreeSet<H160>, limit: u64) -> Option<H256> {
+		// Reading the keys of the accounts.
+		let mut entries = addresses.len() as u64;
+		if entries > limit {
+			return None;
+		}
+		let mut accounts = Vec::with_capacity(addresses.len());
+		for address in addresses {
+			let account_id = <T as pallet_evm::Config>::AddressMapping::into_account_id(address);
+			let nonce = <T as pallet_evm::Config>::AccountProvider::account_nonce(&account_id);
+			let balance =
+				<<T as pallet_evm::Config>::Currency as Inspect<_>>::total_balance(&account_id);
+			let account = pallet_evm::Account {
+				nonce: U256::from(UniqueSaturatedInto::<u128>::unique_saturated_into(nonce)),
+				balance: U256::from(UniqueSaturatedInto::<u128>::unique_saturated_into(balance)),
+			};
+			let code = pallet_evm::AccountCodes::<T>::get(address);
+			// The basic account, and the code counted by chunks of the entry proof size.
+			entries = entries.saturating_add(1).saturating_add(
+				(code.len() as u64)
+					.div_ceil(STATE_ROOT_ENTRY_PROOF_SIZE)
+					.max(1),
+			);
+			if entries > limit {
+				return None;
+			}
+
+			let mut storage = Vec::new();
+			for entry in pallet_evm::AccountStorages::<T>::iter_prefix(address) {
+				entries = entries.saturating_add(1);
+				if entries > limit {
+					return None;
+				}
+				storage.push(entry);
+			}
+			accounts.push((address, account, code, storage));
+		}
+		Some(ethereum_state_root(accounts))
+	}
+
+	/// The hardfork and gas schedule ethereum transactions are validated and executed with.
+	pub fn evm_config_info() -> fp_rpc::EvmConfigInfo {
+		(&Self::evm_config()).into()
+	}
+
+	/// Whether the signature of the transaction recovers to `expected`, e.g. to cross-check a
+	/// sender provided alongside a signed transaction.
+	pub fn verify_sender(transaction: &Transaction, expected: H160) -> bool {
+		Self::recover_signer(transaction) == Some(expected)
+	}
+
+	/// The intrinsic gas of the transaction, i.e. the minimum gas limit validation accepts for it,
+	/// including the EIP-7623 calldata floor when `CalldataFloorPricing` is enabled.
+	pub fn intrinsic_gas(transaction: &Transaction) -> u64 {
+		let transaction_data: TransactionData = transaction.into();
+		let floor = Self::calldata_floor(&transaction_data);
+		let transaction: fp_evm::CheckEvmTransactionInput = transaction_data.into();
+		transaction.intrinsic_gas(&Self::evm_config()).max(floor)
+	}
+
+	/// Validate a signed transaction against the current state, the way the transaction pool
+	/// would, and return the specific reason it would be rejected.
+	pub fn validate_transaction(
+		transaction: &Transaction,
+	) -> Result<(), TransactionValidationError> {
+		let origin = Self::recover_signer(transaction)
+			.ok_or(TransactionValidationError::InvalidSignature)?;
+		Self::validate_transaction_common(origin, &transaction.into(), true)?;
+
+		// EIP-3607: the pool rejects senders with deployed code.
+		if !pallet_evm::AccountCodes::<T>::get(origin).is_empty() {
+			return Err(TransactionValidationError::SenderNotEoa);
+		}
+
+		Ok(())
+	}
+
 	/// Execute an Ethereum transaction.
 	pub fn execute(
 		from: H160,
@@ -752,6 +1123,8 @@ impl<T: Config> Pallet<T> {
 	) -> Result<(Option<H160>, Option<H160>, CallOrCreateInfo), DispatchErrorWithPostInfo> {
 		let transaction_data: TransactionData = transaction.into();
 		let (weight_limit, proof_size_base_cost) = Self::transaction_weight(&transaction_data);
+		let gas_floor = Self::calldata_floor(&transaction_data);
+		let config = config.unwrap_or_else(Self::evm_config);
 		let is_transactional = true;
 		let validate = false;
 
@@ -825,13 +1198,15 @@ impl<T: Config> Pallet<T> {
 					gas_limit.unique_saturated_into(),
 					max_fee_per_gas,
 					max_priority_fee_per_gas,
+					None,
 					nonce,
 					access_list,
 					is_transactional,
 					validate,
 					weight_limit,
 					proof_size_base_cost,
-					config.as_ref().unwrap_or_else(|| T::config()),
+					Some(gas_floor),
+					&config,
 				) {
 					Ok(res) => res,
 					Err(e) => {
@@ -855,13 +1230,15 @@ impl<T: Config> Pallet<T> {
 					gas_limit.unique_saturated_into(),
 					max_fee_per_gas,
 					max_priority_fee_per_gas,
+					None,
 					nonce,
 					access_list,
 					is_transactional,
 					validate,
 					weight_limit,
 					proof_size_base_cost,
-					config.as_ref().unwrap_or_else(|| T::config()),
+					Some(gas_floor),
+					&config,
 				) {
 					Ok(res) => res,
 					Err(e) => {
@@ -888,30 +1265,64 @@ impl<T: Config> Pallet<T> {
 		origin: H160,
 		transaction: &Transaction,
 	) -> Result<(), TransactionValidityError> {
-		let transaction_data: TransactionData = transaction.into();
-		let (weight_limit, proof_size_base_cost) = Self::transaction_weight(&transaction_data);
+		Self::validate_transaction_common(origin, &transaction.into(), false)
+			.map_err(|e| TransactionValidityError::Invalid(InvalidTransactionWrapper::from(e).0))?;
+
+		Ok(())
+	}
+
+	/// Controls common to the pool and to the State Transition Function (STF). `in_pool` accepts
+	/// nonces above the one of the sender, which the pool orders. Returns the sender account and
+	/// the base fee.
+	fn validate_transaction_common(
+		origin: H160,
+		transaction_data: &TransactionData,
+		in_pool: bool,
+	) -> Result<(pallet_evm::Account, U256), TransactionValidationError> {
+		if pallet_evm::Paused::<T>::get() {
+			return Err(TransactionValidationError::Paused);
+		}
+		if Self::is_blocked(&origin, transaction_data) {
+			return Err(TransactionValidationError::BlockedAddress);
+		}
+
+		Self::check_calldata_floor(transaction_data)?;
+		Self::check_access_list_size(transaction_data)?;
+
+		let (weight_limit, proof_size_base_cost) = Self::transaction_weight(transaction_data);
 		let (base_fee, _) = T::FeeCalculator::min_gas_price();
-		let (who, _) = pallet_evm::Pallet::<T>::account_basic(&origin);
+		let (who, _) = pallet_evm::Pallet::<T>::account_basic_for_fees(&origin);
+		let zero_gas_price_allowed = Self::is_zero_gas_price_allowed(transaction_data);
 
-		let _ = CheckEvmTransaction::<InvalidTransactionWrapper>::new(
+		let evm_config = Self::evm_config();
+		let check = CheckEvmTransaction::<TransactionValidationError>::new(
 			CheckEvmTransactionConfig {
-				evm_config: T::config(),
+				evm_config: &evm_config,
 				block_gas_limit: T::BlockGasLimit::get(),
 				base_fee,
 				chain_id: T::ChainId::get(),
 				is_transactional: true,
 			},
-			transaction_data.into(),
+			transaction_data.clone().into(),
 			weight_limit,
 			proof_size_base_cost,
-		)
-		.validate_in_block_for(&who)
+		);
+		let _ = if in_pool {
+			check.validate_in_pool_for(&who)
+		} else {
+			check.validate_in_block_for(&who)
+		}
 		.and_then(|v| v.with_chain_id())
-		.and_then(|v| v.with_base_fee())
-		.and_then(|v| v.with_balance_for(&who))
-		.map_err(|e| TransactionValidityError::Invalid(e.0))?;
+		.and_then(|v| {
+			if zero_gas_price_allowed {
+				Ok(v)
+			} else {
+				v.with_base_fee()
+			}
+		})
+		.and_then(|v| v.with_balance_for(&who))?;
 
-		Ok(())
+		Ok((who, base_fee))
 	}
 
 	pub fn migrate_block_v0_to_v2() -> Weight {
@@ -1004,6 +1415,66 @@ impl<T: Get<RuntimeVersion>> Get<H256> for IntermediateStateRoot<T> {
 	}
 }
 
+/// Computes an Ethereum-compatible state root: the root of the secure Merkle Patricia trie of
+/// all EVM accounts and their storage, as an Ethereum client would for the same state.
+///
+/// Every account and storage entry is read and hashed in `on_finalize` of each block, at most
+/// `MaxStateRootEntries` of them, whose cost is reserved in `on_initialize`. Once the state
+/// outgrows that bound, the intermediate state root is used instead. It is only practical for
+/// chains with a small state; most chains should use `IntermediateStateRoot`. Account ids must
+/// be ethereum addresses, e.g. `AccountId20` with an identity address mapping.
+pub struct EthereumStateRoot<T>(PhantomData<T>);
+impl<T: Config> Get<H256> for EthereumStateRoot<T>
+where
+	T::AccountId: Into<H160>,
+{
+	fn get() -> H256 {
+		let limit = u64::from(T::MaxStateRootEntries::get());
+		// One more key than the limit is enough to know it is exceeded.
+		let take = limit.saturating_add(1).try_into().unwrap_or(usize::MAX);
+		let mut addresses: BTreeSet<H160> = frame_system::Account::<T>::iter_keys()
+			.take(take)
+			.map(Into::into)
+			.collect();
+		addresses.extend(pallet_evm::AccountCodes::<T>::iter_keys().take(take));
+
+		Pallet::<T>::state_root_of(addresses, limit).unwrap_or_else(|| {
+			log::error!(
+				target: "ethereum",
+				"EVM state exceeds MaxStateRootEntries, using the intermediate state root",
+			);
+			IntermediateStateRoot::<T::Version>::get()
+		})
+	}
+}
+
+/// Compute the root of the secure Merkle Patricia trie of the given accounts, each given as its
+/// address, basic account, code and storage.
+pub fn ethereum_state_root<I>(accounts: I) -> H256
+where
+	I: IntoIterator<Item = (H160, pallet_evm::Account, Vec<u8>, Vec<(H256, H256)>)>,
+{
+	ethereum::util::sec_trie_root(
+		accounts
+			.into_iter()
+			.map(|(address, account, code, storage)| {
+				let storage_root = ethereum::util::sec_trie_root(
+					storage
+						.into_iter()
+						.filter(|(_, value)| !value.is_zero())
+						.map(|(key, value)| (key, rlp::encode(&U256::from_big_endian(&value[..])))),
+				);
+
+				let mut stream = rlp::RlpStream::new_list(4);
+				stream.append(&account.nonce);
+				stream.append(&account.balance);
+				stream.append(&storage_root);
+				stream.append(&H256::from(sp_io::hashing::keccak_256(&code)));
+				(address, stream.out())
+			}),
+	)
+}
+
 /// Returns the Ethereum block hash by number.
 pub struct EthereumBlockHashMapping<T>(PhantomData<T>);
 impl<T: Config> BlockHashMapping for EthereumBlockHashMapping<T> {
@@ -1026,7 +1497,9 @@ impl From<TransactionValidationError> for InvalidTransactionWrapper {
 			TransactionValidationError::PriorityFeeTooHigh => InvalidTransactionWrapper(
 				InvalidTransaction::Custom(TransactionValidationError::PriorityFeeTooHigh as u8),
 			),
-			TransactionValidationError::BalanceTooLow => {
+			TransactionValidationError::BalanceTooLow
+			| TransactionValidationError::BalanceTooLowForFee
+			| TransactionValidationError::BalanceTooLowForValue => {
 				InvalidTransactionWrapper(InvalidTransaction::Payment)
 			}
 			TransactionValidationError::TxNonceTooLow => {
@@ -1047,6 +1520,23 @@ impl From<TransactionValidationError> for InvalidTransactionWrapper {
 			TransactionValidationError::GasPriceTooLow => InvalidTransactionWrapper(
 				InvalidTransaction::Custom(TransactionValidationError::GasPriceTooLow as u8),
 			),
+			TransactionValidationError::CreateContractSizeLimit => {
+				InvalidTransactionWrapper(InvalidTransaction::Custom(
+					TransactionValidationError::CreateContractSizeLimit as u8,
+				))
+			}
+			TransactionValidationError::BlockedAddress => InvalidTransactionWrapper(
+				InvalidTransaction::Custom(TransactionValidationError::BlockedAddress as u8),
+			),
+			TransactionValidationError::AccessListTooLarge => InvalidTransactionWrapper(
+				InvalidTransaction::Custom(TransactionValidationError::AccessListTooLarge as u8),
+			),
+			TransactionValidationError::Paused => InvalidTransactionWrapper(
+				InvalidTransaction::Custom(TransactionValidationError::Paused as u8),
+			),
+			TransactionValidationError::SenderNotEoa => InvalidTransactionWrapper(
+				InvalidTransaction::Custom(TransactionValidationError::SenderNotEoa as u8),
+			),
 			TransactionValidationError::UnknownError => InvalidTransactionWrapper(
 				InvalidTransaction::Custom(TransactionValidationError::UnknownError as u8),
 			),
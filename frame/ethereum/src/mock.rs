@@ -20,7 +20,11 @@
 use ethereum::{TransactionAction, TransactionSignature};
 use rlp::RlpStream;
 // Substrate
-use frame_support::{derive_impl, parameter_types, traits::FindAuthor, ConsensusEngineId};
+use frame_support::{
+	derive_impl, parameter_types,
+	traits::{Contains, FindAuthor},
+	ConsensusEngineId,
+};
 use sp_core::{hashing::keccak_256, H160, H256, U256};
 use sp_runtime::{
 	traits::{Dispatchable, IdentityLookup},
@@ -39,12 +43,13 @@ frame_support::construct_runtime! {
 		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
 		Timestamp: pallet_timestamp::{Pallet, Call, Storage},
 		EVM: pallet_evm::{Pallet, Call, Storage, Config<T>, Event<T>},
+		EVMChainId: pallet_evm_chain_id::{Pallet, Call, Storage, Config<T>},
 		Ethereum: crate::{Pallet, Call, Storage, Event, Origin},
 	}
 }
 
 parameter_types! {
-	pub const BlockHashCount: u64 = 250;
+	pub static BlockHashCount: u64 = 250;
 }
 
 #[derive_impl(frame_system::config_preludes::TestDefaultConfig as frame_system::DefaultConfig)]
@@ -86,23 +91,62 @@ impl FindAuthor<H160> for FindAuthorTruncated {
 parameter_types! {
 	pub const TransactionByteFee: u64 = 1;
 	pub const GasLimitStorageGrowthRatio: u64 = 0;
+	// Low enough for the default `BlockGasLimit` to fit the default block weight.
+	pub WeightPerGas: Weight = Weight::from_parts(6_000, 0);
 }
 
 #[derive_impl(pallet_evm::config_preludes::TestDefaultConfig)]
 impl pallet_evm::Config for Test {
 	type AccountProvider = pallet_evm::FrameSystemAccountProvider<Self>;
 	type BlockHashMapping = crate::EthereumBlockHashMapping<Self>;
+	type ChainId = EVMChainId;
 	type Currency = Balances;
 	type PrecompilesType = ();
 	type PrecompilesValue = ();
 	type Runner = pallet_evm::runner::stack::Runner<Self>;
 	type FindAuthor = FindAuthorTruncated;
 	type GasLimitStorageGrowthRatio = GasLimitStorageGrowthRatio;
+	type WeightPerGas = WeightPerGas;
 	type Timestamp = Timestamp;
 }
 
+impl pallet_evm_chain_id::Config for Test {}
+
+parameter_types! {
+	pub static ExtraData: Vec<u8> = Vec::new();
+	pub static AllowZeroGasPrice: bool = false;
+	pub static MaxTransactionsPerBlock: Option<u32> = None;
+	pub static BlockedAddresses: Vec<H160> = Vec::new();
+	pub static BlockTransactionRecipients: bool = true;
+	pub static EmitExecutedEvent: bool = true;
+	pub static ExtrinsicBaseGas: u64 = 0;
+	pub static CalldataFloorPricing: bool = false;
+	pub static MaxAccessListSize: u32 = u32::MAX;
+	pub static BlockHashPruningLimit: u32 = 32;
+	pub static MaxStateRootEntries: u32 = 1_000;
+}
+
+pub struct TransactionBlocklist;
+impl Contains<H160> for TransactionBlocklist {
+	fn contains(address: &H160) -> bool {
+		BlockedAddresses::get().contains(address)
+	}
+}
+
 #[derive_impl(crate::config_preludes::TestDefaultConfig)]
-impl Config for Test {}
+impl Config for Test {
+	type ExtraData = ExtraData;
+	type AllowZeroGasPrice = AllowZeroGasPrice;
+	type MaxTransactionsPerBlock = MaxTransactionsPerBlock;
+	type TransactionBlocklist = TransactionBlocklist;
+	type BlockTransactionRecipients = BlockTransactionRecipients;
+	type EmitExecutedEvent = EmitExecutedEvent;
+	type ExtrinsicBaseGas = ExtrinsicBaseGas;
+	type CalldataFloorPricing = CalldataFloorPricing;
+	type MaxAccessListSize = MaxAccessListSize;
+	type BlockHashPruningLimit = BlockHashPruningLimit;
+	type MaxStateRootEntries = MaxStateRootEntries;
+}
 
 impl fp_self_contained::SelfContainedCall for RuntimeCall {
 	type SignedInfo = H160;
@@ -198,6 +242,12 @@ pub fn new_test_ext(accounts_len: usize) -> (Vec<AccountInfo>, sp_io::TestExtern
 	pallet_balances::GenesisConfig::<Test> { balances }
 		.assimilate_storage(&mut ext)
 		.unwrap();
+	pallet_evm_chain_id::GenesisConfig::<Test> {
+		chain_id: ChainId::get(),
+		..Default::default()
+	}
+	.assimilate_storage(&mut ext)
+	.unwrap();
 
 	(pairs, ext.into())
 }
@@ -223,6 +273,12 @@ pub fn new_test_ext_with_initial_balance(
 	pallet_balances::GenesisConfig::<Test> { balances }
 		.assimilate_storage(&mut ext)
 		.unwrap();
+	pallet_evm_chain_id::GenesisConfig::<Test> {
+		chain_id: ChainId::get(),
+		..Default::default()
+	}
+	.assimilate_storage(&mut ext)
+	.unwrap();
 
 	(pairs, ext.into())
 }
@@ -313,6 +369,15 @@ pub struct EIP2930UnsignedTransaction {
 
 impl EIP2930UnsignedTransaction {
 	pub fn sign(&self, secret: &H256, chain_id: Option<u64>) -> Transaction {
+		self.sign_with_access_list(secret, chain_id, vec![])
+	}
+
+	pub fn sign_with_access_list(
+		&self,
+		secret: &H256,
+		chain_id: Option<u64>,
+		access_list: Vec<ethereum::AccessListItem>,
+	) -> Transaction {
 		let secret = {
 			let mut sk: [u8; 32] = [0u8; 32];
 			sk.copy_from_slice(&secret[0..]);
@@ -327,7 +392,7 @@ impl EIP2930UnsignedTransaction {
 			action: self.action,
 			value: self.value,
 			input: self.input.clone(),
-			access_list: vec![],
+			access_list,
 		};
 		let signing_message = libsecp256k1::Message::parse_slice(&msg.hash()[..]).unwrap();
 
@@ -363,6 +428,15 @@ pub struct EIP1559UnsignedTransaction {
 
 impl EIP1559UnsignedTransaction {
 	pub fn sign(&self, secret: &H256, chain_id: Option<u64>) -> Transaction {
+		self.sign_with_access_list(secret, chain_id, vec![])
+	}
+
+	pub fn sign_with_access_list(
+		&self,
+		secret: &H256,
+		chain_id: Option<u64>,
+		access_list: Vec<ethereum::AccessListItem>,
+	) -> Transaction {
 		let secret = {
 			let mut sk: [u8; 32] = [0u8; 32];
 			sk.copy_from_slice(&secret[0..]);
@@ -378,7 +452,7 @@ impl EIP1559UnsignedTransaction {
 			action: self.action,
 			value: self.value,
 			input: self.input.clone(),
-			access_list: vec![],
+			access_list,
 		};
 		let signing_message = libsecp256k1::Message::parse_slice(&msg.hash()[..]).unwrap();
 
@@ -261,6 +261,36 @@ fn transaction_with_invalid_chain_id_should_fail_in_block() {
 	});
 }
 
+#[test]
+fn transaction_with_priority_fee_above_max_fee_should_fail() {
+	let (pairs, mut ext) = new_test_ext(1);
+	let alice = &pairs[0];
+
+	ext.execute_with(|| {
+		let mut transaction = eip1559_erc20_creation_unsigned_transaction();
+		transaction.max_fee_per_gas = U256::from(1);
+		transaction.max_priority_fee_per_gas = U256::from(2);
+		let transaction = transaction.sign(&alice.private_key, None);
+
+		let call = crate::Call::<Test>::transact { transaction };
+		let source = call.check_self_contained().unwrap().unwrap();
+		let dispatch_info = call.get_dispatch_info();
+		let priority_fee_too_high = InvalidTransaction::Custom(
+			fp_evm::TransactionValidationError::PriorityFeeTooHigh as u8,
+		);
+		assert_err!(
+			call.validate_self_contained(&source, &dispatch_info, 0)
+				.unwrap(),
+			priority_fee_too_high
+		);
+		assert_err!(
+			call.pre_dispatch_self_contained(&source, &dispatch_info, 0)
+				.unwrap(),
+			priority_fee_too_high
+		);
+	});
+}
+
 #[test]
 fn contract_constructor_should_get_executed() {
 	let (pairs, mut ext) = new_test_ext(1);
@@ -621,3 +651,45 @@ fn proof_size_base_cost_should_keep_the_same_in_execution_and_estimate() {
 		);
 	});
 }
+
+#[test]
+fn transaction_with_access_list_above_limit_should_not_work() {
+	let (pairs, mut ext) = new_test_ext(1);
+	let alice = &pairs[0];
+
+	ext.execute_with(|| {
+		MaxAccessListSize::set(2);
+
+		let access_list = |storage_keys: u64| {
+			vec![ethereum::AccessListItem {
+				address: H160::repeat_byte(0x11),
+				storage_keys: (0..storage_keys).map(H256::from_low_u64_be).collect(),
+			}]
+		};
+		let transaction = eip1559_erc20_creation_unsigned_transaction();
+
+		// The address and its storage key fill the access list up to the limit.
+		let t = transaction.sign_with_access_list(&alice.private_key, None, access_list(1));
+		assert_ok!(Ethereum::validate_transaction(&t));
+
+		let t = transaction.sign_with_access_list(&alice.private_key, None, access_list(2));
+		assert_err!(
+			Ethereum::validate_transaction(&t),
+			fp_evm::TransactionValidationError::AccessListTooLarge
+		);
+
+		let call = crate::Call::<Test>::transact { transaction: t };
+		let source = call.check_self_contained().unwrap().unwrap();
+		let extrinsic = CheckedExtrinsic::<_, _, SignedExtra, _> {
+			signed: fp_self_contained::CheckedSignature::SelfContained(source),
+			function: RuntimeCall::Ethereum(call),
+		};
+		let dispatch_info = extrinsic.get_dispatch_info();
+		assert_err!(
+			extrinsic.apply::<Test>(&dispatch_info, 0),
+			TransactionValidityError::Invalid(InvalidTransaction::Custom(
+				fp_evm::TransactionValidationError::AccessListTooLarge as u8,
+			))
+		);
+	});
+}
@@ -546,3 +546,95 @@ fn proof_size_base_cost_should_keep_the_same_in_execution_and_estimate() {
 		);
 	});
 }
+
+#[test]
+fn transaction_with_access_list_above_limit_should_not_work() {
+	let (pairs, mut ext) = new_test_ext(1);
+	let alice = &pairs[0];
+
+	ext.execute_with(|| {
+		MaxAccessListSize::set(2);
+
+		let access_list = |storage_keys: u64| {
+			vec![ethereum::AccessListItem {
+				address: H160::repeat_byte(0x11),
+				storage_keys: (0..storage_keys).map(H256::from_low_u64_be).collect(),
+			}]
+		};
+		let transaction = eip2930_erc20_creation_unsigned_transaction();
+
+		// The address and its storage key fill the access list up to the limit.
+		let t = transaction.sign_with_access_list(&alice.private_key, None, access_list(1));
+		assert_ok!(Ethereum::validate_transaction(&t));
+
+		let t = transaction.sign_with_access_list(&alice.private_key, None, access_list(2));
+		assert_err!(
+			Ethereum::validate_transaction(&t),
+			fp_evm::TransactionValidationError::AccessListTooLarge
+		);
+
+		let call = crate::Call::<Test>::transact { transaction: t };
+		let source = call.check_self_contained().unwrap().unwrap();
+		let extrinsic = CheckedExtrinsic::<_, _, SignedExtra, _> {
+			signed: fp_self_contained::CheckedSignature::SelfContained(source),
+			function: RuntimeCall::Ethereum(call),
+		};
+		let dispatch_info = extrinsic.get_dispatch_info();
+		assert_err!(
+			extrinsic.apply::<Test>(&dispatch_info, 0),
+			TransactionValidityError::Invalid(InvalidTransaction::Custom(
+				fp_evm::TransactionValidationError::AccessListTooLarge as u8,
+			))
+		);
+	});
+}
+
+#[test]
+fn intrinsic_gas_is_the_minimum_gas_limit() {
+	let (pairs, mut ext) = new_test_ext(1);
+	let alice = &pairs[0];
+
+	ext.execute_with(|| {
+		let access_list = vec![ethereum::AccessListItem {
+			address: H160::repeat_byte(0x11),
+			storage_keys: vec![H256::zero(), H256::repeat_byte(0x22)],
+		}];
+		let transaction = |action: TransactionAction, gas_limit: u64| {
+			EIP2930UnsignedTransaction {
+				nonce: U256::zero(),
+				gas_price: U256::from(1),
+				gas_limit: U256::from(gas_limit),
+				action,
+				value: U256::zero(),
+				input: vec![0, 1],
+			}
+			.sign_with_access_list(&alice.private_key, None, access_list.clone())
+		};
+
+		// 4 gas per zero byte, 16 per nonzero byte, 2_400 per address and 1_900 per storage key.
+		let call = TransactionAction::Call(H160::repeat_byte(0x33));
+		let intrinsic_gas = Ethereum::intrinsic_gas(&transaction(call, 0));
+		assert_eq!(intrinsic_gas, 21_000 + 4 + 16 + 2_400 + 2 * 1_900);
+		assert_ok!(Ethereum::validate_transaction(&transaction(
+			call,
+			intrinsic_gas
+		)));
+		assert_err!(
+			Ethereum::validate_transaction(&transaction(call, intrinsic_gas - 1)),
+			fp_evm::TransactionValidationError::GasLimitTooLow
+		);
+
+		// Creations pay 53_000 and 2 gas per init code word.
+		let create = TransactionAction::Create;
+		let intrinsic_gas = Ethereum::intrinsic_gas(&transaction(create, 0));
+		assert_eq!(intrinsic_gas, 53_000 + 4 + 16 + 2_400 + 2 * 1_900 + 2);
+		assert_ok!(Ethereum::validate_transaction(&transaction(
+			create,
+			intrinsic_gas
+		)));
+		assert_err!(
+			Ethereum::validate_transaction(&transaction(create, intrinsic_gas - 1)),
+			fp_evm::TransactionValidationError::GasLimitTooLow
+		);
+	});
+}
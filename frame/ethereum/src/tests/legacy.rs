@@ -58,6 +58,34 @@ fn transaction_should_increment_nonce() {
 	});
 }
 
+#[test]
+fn reverted_creation_should_increment_nonce() {
+	let (pairs, mut ext) = new_test_ext(1);
+	let alice = &pairs[0];
+
+	ext.execute_with(|| {
+		// PUSH1 0, PUSH1 0, REVERT
+		let t = LegacyUnsignedTransaction {
+			input: vec![0x60, 0x00, 0x60, 0x00, 0xfd],
+			..legacy_erc20_creation_unsigned_transaction()
+		}
+		.sign(&alice.private_key);
+		let (_, _, info) = Ethereum::execute(alice.address, &t, None).unwrap();
+		match info {
+			CallOrCreateInfo::Create(info) => {
+				assert_eq!(info.exit_reason, ExitReason::Revert(ExitRevert::Reverted));
+			}
+			CallOrCreateInfo::Call(_) => panic!("expected a create"),
+		}
+		assert_eq!(
+			pallet_evm::Pallet::<Test>::account_basic(&alice.address)
+				.0
+				.nonce,
+			U256::from(1)
+		);
+	});
+}
+
 #[test]
 fn transaction_without_enough_gas_should_not_work() {
 	let (pairs, mut ext) = new_test_ext(1);
@@ -193,6 +221,41 @@ fn transaction_with_invalid_chain_id_should_fail_in_block() {
 	});
 }
 
+#[test]
+fn transaction_with_previous_chain_id_should_fail_after_chain_id_change() {
+	let (pairs, mut ext) = new_test_ext(1);
+	let alice = &pairs[0];
+
+	ext.execute_with(|| {
+		let previous_chain_id = pallet_evm_chain_id::ChainId::<Test>::get();
+		assert_ok!(EVMChainId::set_chain_id(
+			RuntimeOrigin::root(),
+			previous_chain_id + 1
+		));
+
+		let apply = |chain_id: u64| {
+			let transaction = legacy_erc20_creation_unsigned_transaction()
+				.sign_with_chain_id(&alice.private_key, chain_id);
+			let call = crate::Call::<Test>::transact { transaction };
+			let source = call.check_self_contained().unwrap().unwrap();
+			let extrinsic = CheckedExtrinsic::<_, _, SignedExtra, _> {
+				signed: fp_self_contained::CheckedSignature::SelfContained(source),
+				function: RuntimeCall::Ethereum(call),
+			};
+			let dispatch_info = extrinsic.get_dispatch_info();
+			extrinsic.apply::<Test>(&dispatch_info, 0)
+		};
+
+		assert_err!(
+			apply(previous_chain_id),
+			TransactionValidityError::Invalid(InvalidTransaction::Custom(
+				fp_evm::TransactionValidationError::InvalidChainId as u8,
+			))
+		);
+		assert_ok!(apply(previous_chain_id + 1));
+	});
+}
+
 #[test]
 fn contract_constructor_should_get_executed() {
 	let (pairs, mut ext) = new_test_ext(1);
@@ -212,6 +275,19 @@ fn contract_constructor_should_get_executed() {
 	});
 }
 
+#[test]
+fn verify_sender_should_reject_mismatched_sender() {
+	let (pairs, mut ext) = new_test_ext(2);
+	let alice = &pairs[0];
+	let bob = &pairs[1];
+
+	ext.execute_with(|| {
+		let transaction = legacy_erc20_creation_transaction(alice);
+		assert!(Ethereum::verify_sender(&transaction, alice.address));
+		assert!(!Ethereum::verify_sender(&transaction, bob.address));
+	});
+}
+
 #[test]
 fn source_should_be_derived_from_signature() {
 	let (pairs, mut ext) = new_test_ext(1);
@@ -403,6 +479,38 @@ fn event_extra_data_should_be_handle_properly() {
 	});
 }
 
+#[test]
+fn executed_event_can_be_disabled() {
+	let (pairs, mut ext) = new_test_ext(1);
+	let alice = &pairs[0];
+
+	ext.execute_with(|| {
+		System::set_block_number(1);
+		let executed_events = || {
+			System::events()
+				.iter()
+				.filter(|record| {
+					matches!(record.event, RuntimeEvent::Ethereum(Event::Executed { .. }))
+				})
+				.count()
+		};
+
+		let t = legacy_erc20_creation_transaction(alice);
+		assert_ok!(Ethereum::apply_validated_transaction(alice.address, t));
+		assert_eq!(executed_events(), 1);
+
+		EmitExecutedEvent::set(false);
+		let mut unsigned = legacy_erc20_creation_unsigned_transaction();
+		unsigned.nonce = U256::from(1);
+		let t = unsigned.sign(&alice.private_key);
+		assert_ok!(Ethereum::apply_validated_transaction(alice.address, t));
+
+		// The transaction is still recorded, only the event is skipped.
+		assert_eq!(crate::Pending::<Test>::decode_len(), Some(2));
+		assert_eq!(executed_events(), 1);
+	});
+}
+
 #[test]
 fn self_contained_transaction_with_extra_gas_should_adjust_weight_with_post_dispatch() {
 	let (pairs, mut ext) = new_test_ext(1);
@@ -478,6 +586,364 @@ fn validated_transaction_apply_zero_gas_price_works() {
 	});
 }
 
+#[test]
+fn zero_gas_price_transaction_is_valid_only_when_allowed() {
+	let (pairs, mut ext) = new_test_ext_with_initial_balance(2, 1_000);
+	let alice = &pairs[0];
+	let bob = &pairs[1];
+	let substrate_alice =
+		<Test as pallet_evm::Config>::AddressMapping::into_account_id(alice.address);
+
+	ext.execute_with(|| {
+		let transaction = LegacyUnsignedTransaction {
+			nonce: U256::zero(),
+			gas_price: U256::zero(),
+			gas_limit: U256::from(21_000),
+			action: ethereum::TransactionAction::Call(bob.address),
+			value: U256::from(100),
+			input: Default::default(),
+		}
+		.sign(&alice.private_key);
+
+		let call = crate::Call::<Test>::transact { transaction };
+		let source = call.check_self_contained().unwrap().unwrap();
+		let extrinsic = CheckedExtrinsic::<u64, _, SignedExtra, H160> {
+			signed: fp_self_contained::CheckedSignature::SelfContained(source),
+			function: RuntimeCall::Ethereum(call.clone()),
+		};
+		let dispatch_info = extrinsic.get_dispatch_info();
+
+		// The base fee is not covered.
+		assert_err!(
+			call.validate_self_contained(&source, &dispatch_info, 0)
+				.unwrap(),
+			InvalidTransaction::Custom(fp_evm::TransactionValidationError::GasPriceTooLow as u8)
+		);
+
+		AllowZeroGasPrice::set(true);
+		assert_ok!(call
+			.validate_self_contained(&source, &dispatch_info, 0)
+			.unwrap());
+		assert_ok!(call
+			.pre_dispatch_self_contained(&source, &dispatch_info, 0)
+			.unwrap());
+		assert_ok!(extrinsic.apply::<Test>(&dispatch_info, 0).unwrap());
+
+		// No fee was withdrawn, only the value was transferred.
+		assert_eq!(Balances::free_balance(&substrate_alice), 900);
+	});
+}
+
+#[test]
+fn extrinsic_base_gas_is_charged_to_the_sender() {
+	let (pairs, mut ext) = new_test_ext(2);
+	let alice = &pairs[0];
+	let bob = &pairs[1];
+	let substrate_alice =
+		<Test as pallet_evm::Config>::AddressMapping::into_account_id(alice.address);
+
+	ext.execute_with(|| {
+		let transfer = |nonce: u64, gas_limit: u64| {
+			LegacyUnsignedTransaction {
+				nonce: U256::from(nonce),
+				gas_price: U256::from(1),
+				gas_limit: U256::from(gas_limit),
+				action: ethereum::TransactionAction::Call(bob.address),
+				value: U256::zero(),
+				input: Default::default(),
+			}
+			.sign(&alice.private_key)
+		};
+		let balance = || Balances::free_balance(&substrate_alice);
+
+		let before = balance();
+		assert_ok!(Ethereum::apply_validated_transaction(
+			alice.address,
+			transfer(0, 21_000)
+		));
+		assert_eq!(before - balance(), 21_000);
+
+		ExtrinsicBaseGas::set(1_000);
+
+		// The intrinsic cost now includes the base gas.
+		assert_eq!(
+			Ethereum::validate_transaction(&transfer(1, 21_000)),
+			Err(fp_evm::TransactionValidationError::GasLimitTooLow)
+		);
+
+		let before = balance();
+		let (_, info) =
+			Ethereum::apply_validated_transaction(alice.address, transfer(1, 22_000)).unwrap();
+		match info {
+			CallOrCreateInfo::Call(info) => {
+				assert_eq!(info.used_gas.standard, U256::from(22_000))
+			}
+			CallOrCreateInfo::Create(_) => panic!("expected a call"),
+		}
+		assert_eq!(before - balance(), 22_000);
+	});
+}
+
+#[test]
+fn gas_estimated_with_the_ethereum_config_is_accepted() {
+	use pallet_evm::Runner;
+
+	let (pairs, mut ext) = new_test_ext(2);
+	let alice = &pairs[0];
+	let bob = &pairs[1];
+
+	ext.execute_with(|| {
+		ExtrinsicBaseGas::set(1_000);
+
+		// Estimate the way the runtime API does.
+		let mut config = Ethereum::evm_config();
+		config.estimate = true;
+		let estimate = <Test as pallet_evm::Config>::Runner::call(
+			alice.address,
+			bob.address,
+			Vec::new(),
+			U256::zero(),
+			1_000_000,
+			None,
+			None,
+			None,
+			None,
+			Vec::new(),
+			false,
+			true,
+			None,
+			None,
+			None,
+			&config,
+		)
+		.unwrap()
+		.used_gas
+		.standard;
+		assert_eq!(estimate, U256::from(22_000));
+
+		let transaction = LegacyUnsignedTransaction {
+			nonce: U256::zero(),
+			gas_price: U256::from(1),
+			gas_limit: estimate,
+			action: ethereum::TransactionAction::Call(bob.address),
+			value: U256::zero(),
+			input: Default::default(),
+		}
+		.sign(&alice.private_key);
+		assert_ok!(Ethereum::validate_transaction(&transaction));
+		assert_ok!(Ethereum::transact(
+			RawOrigin::EthereumTransaction(alice.address).into(),
+			transaction
+		));
+	});
+}
+
+#[test]
+fn transactions_over_max_transactions_per_block_are_rejected() {
+	let (pairs, mut ext) = new_test_ext(2);
+	let alice = &pairs[0];
+	let bob = &pairs[1];
+
+	ext.execute_with(|| {
+		MaxTransactionsPerBlock::set(Some(2));
+
+		let apply = |nonce: u64| {
+			let transaction = LegacyUnsignedTransaction {
+				nonce: U256::from(nonce),
+				gas_price: U256::from(1),
+				gas_limit: U256::from(21_000),
+				action: ethereum::TransactionAction::Call(bob.address),
+				value: U256::from(1),
+				input: Default::default(),
+			}
+			.sign(&alice.private_key);
+			let call = crate::Call::<Test>::transact { transaction };
+			let source = call.check_self_contained().unwrap().unwrap();
+			let extrinsic = CheckedExtrinsic::<u64, _, SignedExtra, H160> {
+				signed: fp_self_contained::CheckedSignature::SelfContained(source),
+				function: RuntimeCall::Ethereum(call),
+			};
+			let dispatch_info = extrinsic.get_dispatch_info();
+			extrinsic.apply::<Test>(&dispatch_info, 0)
+		};
+
+		assert_ok!(apply(0).unwrap());
+		assert_ok!(apply(1).unwrap());
+		assert_eq!(crate::Pending::<Test>::decode_len(), Some(2));
+		assert_err!(apply(2), InvalidTransaction::ExhaustsResources);
+
+		// The next block accepts transactions again.
+		<Ethereum as Hooks<u64>>::on_finalize(1);
+		assert_ok!(apply(2).unwrap());
+	});
+}
+
+#[test]
+fn transactions_involving_blocked_addresses_are_rejected() {
+	let (pairs, mut ext) = new_test_ext(2);
+	let alice = &pairs[0];
+	let bob = &pairs[1];
+
+	ext.execute_with(|| {
+		let transaction = LegacyUnsignedTransaction {
+			nonce: U256::zero(),
+			gas_price: U256::from(1),
+			gas_limit: U256::from(21_000),
+			action: ethereum::TransactionAction::Call(bob.address),
+			value: U256::from(1),
+			input: Default::default(),
+		}
+		.sign(&alice.private_key);
+		let call = crate::Call::<Test>::transact { transaction };
+		let source = call.check_self_contained().unwrap().unwrap();
+		let dispatch_info = call.get_dispatch_info();
+		let blocked =
+			InvalidTransaction::Custom(fp_evm::TransactionValidationError::BlockedAddress as u8);
+
+		assert_ok!(call
+			.validate_self_contained(&source, &dispatch_info, 0)
+			.unwrap());
+
+		// Blocked sender.
+		BlockedAddresses::set(vec![alice.address]);
+		assert_err!(
+			call.validate_self_contained(&source, &dispatch_info, 0)
+				.unwrap(),
+			blocked
+		);
+		assert_err!(
+			call.pre_dispatch_self_contained(&source, &dispatch_info, 0)
+				.unwrap(),
+			blocked
+		);
+
+		// Blocked recipient.
+		BlockedAddresses::set(vec![bob.address]);
+		assert_err!(
+			call.validate_self_contained(&source, &dispatch_info, 0)
+				.unwrap(),
+			blocked
+		);
+		assert_err!(
+			call.pre_dispatch_self_contained(&source, &dispatch_info, 0)
+				.unwrap(),
+			blocked
+		);
+
+		// Recipients may be left out of the blocklist check.
+		BlockTransactionRecipients::set(false);
+		assert_ok!(call
+			.validate_self_contained(&source, &dispatch_info, 0)
+			.unwrap());
+	});
+}
+
+#[test]
+fn transactions_are_rejected_while_evm_is_paused() {
+	let (pairs, mut ext) = new_test_ext(1);
+	let alice = &pairs[0];
+
+	ext.execute_with(|| {
+		let transaction = legacy_erc20_creation_transaction(alice);
+		let call = crate::Call::<Test>::transact {
+			transaction: transaction.clone(),
+		};
+		let source = call.check_self_contained().unwrap().unwrap();
+		let dispatch_info = call.get_dispatch_info();
+		let paused = InvalidTransaction::Custom(fp_evm::TransactionValidationError::Paused as u8);
+
+		assert_ok!(EVM::set_paused(RuntimeOrigin::root(), true));
+		assert_err!(
+			call.validate_self_contained(&source, &dispatch_info, 0)
+				.unwrap(),
+			paused
+		);
+		assert_err!(
+			call.pre_dispatch_self_contained(&source, &dispatch_info, 0)
+				.unwrap(),
+			paused
+		);
+		assert_eq!(
+			Ethereum::validate_transaction(&transaction),
+			Err(fp_evm::TransactionValidationError::Paused)
+		);
+
+		assert_ok!(EVM::set_paused(RuntimeOrigin::root(), false));
+		assert_ok!(call
+			.validate_self_contained(&source, &dispatch_info, 0)
+			.unwrap());
+		assert_ok!(Ethereum::validate_transaction(&transaction));
+	});
+}
+
+#[test]
+fn validate_transaction_reports_the_failure_reason() {
+	use fp_evm::TransactionValidationError;
+
+	let (pairs, mut ext) = new_test_ext(1);
+	let alice = &pairs[0];
+
+	ext.execute_with(|| {
+		let transaction = legacy_erc20_creation_transaction(alice);
+		assert_ok!(Ethereum::validate_transaction(&transaction));
+
+		// Wrong chain id.
+		let wrong_chain =
+			legacy_erc20_creation_unsigned_transaction().sign_with_chain_id(&alice.private_key, 1);
+		assert_eq!(
+			Ethereum::validate_transaction(&wrong_chain),
+			Err(TransactionValidationError::InvalidChainId)
+		);
+
+		// Gas limit below the intrinsic cost.
+		let mut unsigned = legacy_erc20_creation_unsigned_transaction();
+		unsigned.gas_limit = U256::from(1);
+		assert_eq!(
+			Ethereum::validate_transaction(&unsigned.sign(&alice.private_key)),
+			Err(TransactionValidationError::GasLimitTooLow)
+		);
+
+		// Gas price below the base fee.
+		let mut unsigned = legacy_erc20_creation_unsigned_transaction();
+		unsigned.gas_price = U256::zero();
+		assert_eq!(
+			Ethereum::validate_transaction(&unsigned.sign(&alice.private_key)),
+			Err(TransactionValidationError::GasPriceTooLow)
+		);
+
+		// Fee exceeds the sender balance.
+		let mut unsigned = legacy_erc20_creation_unsigned_transaction();
+		unsigned.gas_price = U256::from(11_000_000);
+		assert_eq!(
+			Ethereum::validate_transaction(&unsigned.sign(&alice.private_key)),
+			Err(TransactionValidationError::BalanceTooLowForFee)
+		);
+
+		// Blocked sender.
+		BlockedAddresses::set(vec![alice.address]);
+		assert_eq!(
+			Ethereum::validate_transaction(&transaction),
+			Err(TransactionValidationError::BlockedAddress)
+		);
+		BlockedAddresses::set(vec![]);
+
+		// EIP-3607: sender with deployed code.
+		pallet_evm::AccountCodes::<Test>::insert(alice.address, vec![0x00]);
+		assert_eq!(
+			Ethereum::validate_transaction(&transaction),
+			Err(TransactionValidationError::SenderNotEoa)
+		);
+		pallet_evm::AccountCodes::<Test>::remove(alice.address);
+
+		// Nonce already used.
+		frame_system::Pallet::<Test>::inc_account_nonce(&alice.account_id);
+		assert_eq!(
+			Ethereum::validate_transaction(&transaction),
+			Err(TransactionValidationError::TxNonceTooLow)
+		);
+	});
+}
+
 #[test]
 fn proof_size_weight_limit_validation_works() {
 	use pallet_evm::GasWeightMapping;
@@ -512,6 +978,42 @@ fn proof_size_weight_limit_validation_works() {
 	});
 }
 
+#[test]
+fn transact_reports_measured_proof_size_in_actual_weight() {
+	use pallet_evm::GasWeightMapping;
+
+	let (pairs, mut ext) = new_test_ext(2);
+	let alice = &pairs[0];
+	let bob = &pairs[1];
+
+	ext.execute_with(|| {
+		let gas_limit: u64 = 1_000_000;
+		let tx = LegacyUnsignedTransaction {
+			nonce: U256::zero(),
+			gas_price: U256::from(1),
+			gas_limit: U256::from(gas_limit),
+			action: ethereum::TransactionAction::Call(bob.address),
+			value: U256::from(1),
+			input: Vec::new(),
+		}
+		.sign(&alice.private_key);
+		let proof_size_base_cost = TransactionData::from(&tx).proof_size_base_cost();
+
+		let actual_weight =
+			Ethereum::transact(RawOrigin::EthereumTransaction(alice.address).into(), tx)
+				.expect("transaction succeeds")
+				.actual_weight
+				.expect("actual weight is reported");
+
+		let weight_limit =
+			<Test as pallet_evm::Config>::GasWeightMapping::gas_to_weight(gas_limit, true);
+		// The proof size is the one measured while executing, not the one derived from gas,
+		// so any unused proof size can be reclaimed.
+		assert!(actual_weight.proof_size() >= proof_size_base_cost);
+		assert!(actual_weight.proof_size() < weight_limit.proof_size());
+	});
+}
+
 #[test]
 fn proof_size_base_cost_should_keep_the_same_in_execution_and_estimate() {
 	let (pairs, mut ext) = new_test_ext(1);
@@ -546,3 +1048,295 @@ fn proof_size_base_cost_should_keep_the_same_in_execution_and_estimate() {
 		);
 	});
 }
+
+#[test]
+fn pending_gas_used_is_cumulative() {
+	let (pairs, mut ext) = new_test_ext(2);
+	let alice = &pairs[0];
+	let bob = &pairs[1];
+
+	ext.execute_with(|| {
+		assert_eq!(Ethereum::pending_gas_used(), U256::zero());
+
+		let transfer = |nonce: u64| {
+			LegacyUnsignedTransaction {
+				nonce: U256::from(nonce),
+				gas_price: U256::from(1),
+				gas_limit: U256::from(21_000),
+				action: ethereum::TransactionAction::Call(bob.address),
+				value: U256::from(1),
+				input: Default::default(),
+			}
+			.sign(&alice.private_key)
+		};
+
+		assert_ok!(Ethereum::apply_validated_transaction(
+			alice.address,
+			transfer(0)
+		));
+		assert_eq!(Ethereum::pending_gas_used(), U256::from(21_000));
+
+		assert_ok!(Ethereum::apply_validated_transaction(
+			alice.address,
+			transfer(1)
+		));
+		assert_eq!(Ethereum::pending_gas_used(), U256::from(42_000));
+
+		// The gas used is reset with the pending block.
+		<Ethereum as Hooks<u64>>::on_finalize(1);
+		assert_eq!(Ethereum::pending_gas_used(), U256::zero());
+	});
+}
+
+#[test]
+fn calldata_heavy_transaction_is_charged_the_calldata_floor() {
+	let (pairs, mut ext) = new_test_ext(2);
+	let alice = &pairs[0];
+	let bob = &pairs[1];
+	let substrate_alice =
+		<Test as pallet_evm::Config>::AddressMapping::into_account_id(alice.address);
+
+	ext.execute_with(|| {
+		let transaction = |nonce: u64| {
+			LegacyUnsignedTransaction {
+				nonce: U256::from(nonce),
+				gas_price: U256::from(1),
+				gas_limit: U256::from(100_000),
+				action: ethereum::TransactionAction::Call(bob.address),
+				value: U256::zero(),
+				input: vec![0xff; 1_000],
+			}
+			.sign(&alice.private_key)
+		};
+		let used_gas = |transaction: Transaction| {
+			let (_, info) =
+				Ethereum::apply_validated_transaction(alice.address, transaction).unwrap();
+			match info {
+				CallOrCreateInfo::Call(info) => info.used_gas.effective,
+				CallOrCreateInfo::Create(_) => panic!("expected a call"),
+			}
+		};
+		let balance = || Balances::free_balance(&substrate_alice);
+
+		// 21_000 + 16 gas per nonzero byte.
+		assert_eq!(Ethereum::intrinsic_gas(&transaction(0)), 37_000);
+		assert_eq!(used_gas(transaction(0)), U256::from(37_000));
+
+		CalldataFloorPricing::set(true);
+
+		// 21_000 + 10 gas per token, with 4 tokens per nonzero byte.
+		assert_eq!(Ethereum::intrinsic_gas(&transaction(1)), 61_000);
+		let before = balance();
+		assert_eq!(used_gas(transaction(1)), U256::from(61_000));
+		assert_eq!(before - balance(), 61_000);
+
+		// The gas limit must cover the floor.
+		let mut unsigned = LegacyUnsignedTransaction {
+			nonce: U256::from(2),
+			gas_price: U256::from(1),
+			gas_limit: U256::from(50_000),
+			action: ethereum::TransactionAction::Call(bob.address),
+			value: U256::zero(),
+			input: vec![0xff; 1_000],
+		};
+		assert_eq!(
+			Ethereum::validate_transaction(&unsigned.sign(&alice.private_key)),
+			Err(fp_evm::TransactionValidationError::GasLimitTooLow)
+		);
+		unsigned.gas_limit = U256::from(61_000);
+		assert_ok!(Ethereum::validate_transaction(
+			&unsigned.sign(&alice.private_key)
+		));
+	});
+}
+
+#[test]
+fn execution_heavy_transaction_is_not_affected_by_the_calldata_floor() {
+	let (pairs, mut ext) = new_test_ext(1);
+	let alice = &pairs[0];
+
+	ext.execute_with(|| {
+		let used_gas = |nonce: u64| {
+			let mut unsigned = legacy_erc20_creation_unsigned_transaction();
+			unsigned.nonce = U256::from(nonce);
+			let transaction = unsigned.sign(&alice.private_key);
+			let (_, info) =
+				Ethereum::apply_validated_transaction(alice.address, transaction).unwrap();
+			match info {
+				CallOrCreateInfo::Create(info) => info.used_gas,
+				CallOrCreateInfo::Call(_) => panic!("expected a create"),
+			}
+		};
+
+		let standard = used_gas(0);
+
+		CalldataFloorPricing::set(true);
+
+		let floored = used_gas(1);
+		assert_eq!(floored.standard, standard.standard);
+		assert_eq!(floored.effective, standard.effective);
+
+		// The execution costs more than the floor of the init code.
+		let floor = Ethereum::calldata_floor(&(&legacy_erc20_creation_transaction(alice)).into());
+		assert!(floored.effective > U256::from(floor));
+	});
+}
+
+#[test]
+fn transaction_sees_sender_as_origin_and_caller() {
+	let (pairs, mut ext) = new_test_ext(1);
+	let alice = &pairs[0];
+
+	ext.execute_with(|| {
+		// ORIGIN, PUSH1 0, SSTORE, CALLER, PUSH1 1, SSTORE, STOP
+		let contract = deploy(
+			alice,
+			&[0x32, 0x60, 0x00, 0x55, 0x33, 0x60, 0x01, 0x55, 0x00],
+		);
+
+		let transaction = LegacyUnsignedTransaction {
+			nonce: U256::from(1),
+			gas_price: U256::from(1),
+			gas_limit: U256::from(0x100000),
+			action: TransactionAction::Call(contract),
+			value: U256::zero(),
+			input: Vec::new(),
+		}
+		.sign(&alice.private_key);
+		assert_ok!(Ethereum::execute(alice.address, &transaction, None));
+
+		assert_eq!(
+			pallet_evm::AccountStorages::<Test>::get(contract, H256::from_low_u64_be(0)),
+			H256::from(alice.address)
+		);
+		assert_eq!(
+			pallet_evm::AccountStorages::<Test>::get(contract, H256::from_low_u64_be(1)),
+			H256::from(alice.address)
+		);
+	});
+}
+
+#[test]
+fn receipt_logs_bloom_covers_only_its_own_logs() {
+	let (pairs, mut ext) = new_test_ext(2);
+	let alice = &pairs[0];
+	let bob = &pairs[1];
+
+	ext.execute_with(|| {
+		// CALLER, PUSH1 0, PUSH1 0, LOG1, STOP
+		let contract = deploy(alice, &[0x33, 0x60, 0x00, 0x60, 0x00, 0xa1, 0x00]);
+
+		let call = |account: &AccountInfo, nonce: u64| {
+			LegacyUnsignedTransaction {
+				nonce: U256::from(nonce),
+				gas_price: U256::from(1),
+				gas_limit: U256::from(0x100000),
+				action: TransactionAction::Call(contract),
+				value: U256::zero(),
+				input: Vec::new(),
+			}
+			.sign(&account.private_key)
+		};
+		assert_ok!(Ethereum::apply_validated_transaction(
+			alice.address,
+			call(alice, 1)
+		));
+		assert_ok!(Ethereum::apply_validated_transaction(
+			bob.address,
+			call(bob, 0)
+		));
+
+		let blooms = crate::Pending::<Test>::get()
+			.into_iter()
+			.map(|(_, _, receipt)| match receipt {
+				crate::Receipt::Legacy(d)
+				| crate::Receipt::EIP2930(d)
+				| crate::Receipt::EIP1559(d) => {
+					assert_eq!(d.logs_bloom, crate::compute_logs_bloom(&d.logs));
+					d.logs_bloom
+				}
+			})
+			.collect::<Vec<_>>();
+		assert_eq!(blooms.len(), 3);
+		assert_eq!(blooms[0], Default::default());
+		assert_ne!(blooms[1], Default::default());
+		assert_ne!(blooms[1], blooms[2]);
+	});
+}
+
+#[test]
+fn revert_reasons_are_kept_by_transaction_index_until_the_next_block() {
+	let (pairs, mut ext) = new_test_ext(1);
+	let alice = &pairs[0];
+
+	ext.execute_with(|| {
+		// PUSH4 0xdeadbeef, PUSH1 0, MSTORE, PUSH1 4, PUSH1 28, REVERT
+		let reverting = H160::repeat_byte(0x42);
+		pallet_evm::AccountCodes::<Test>::insert(
+			reverting,
+			vec![
+				0x63, 0xde, 0xad, 0xbe, 0xef, 0x60, 0x00, 0x52, 0x60, 0x04, 0x60, 0x1c, 0xfd,
+			],
+		);
+		// PUSH2 0x0800, PUSH1 0, REVERT
+		let reverting_long = H160::repeat_byte(0x43);
+		pallet_evm::AccountCodes::<Test>::insert(
+			reverting_long,
+			vec![0x61, 0x08, 0x00, 0x60, 0x00, 0xfd],
+		);
+
+		let transact = |nonce: u64, action, input: Vec<u8>| {
+			let transaction = LegacyUnsignedTransaction {
+				nonce: U256::from(nonce),
+				gas_price: U256::from(1),
+				gas_limit: U256::from(0x100000),
+				action,
+				value: U256::zero(),
+				input,
+			}
+			.sign(&alice.private_key);
+			assert_ok!(Ethereum::transact(
+				RawOrigin::EthereumTransaction(alice.address).into(),
+				transaction
+			));
+		};
+
+		transact(0, ethereum::TransactionAction::Call(reverting), Vec::new());
+		transact(
+			1,
+			ethereum::TransactionAction::Call(alice.address),
+			Vec::new(),
+		);
+		// PUSH1 0xaa, PUSH1 0, MSTORE8, PUSH1 1, PUSH1 0, REVERT
+		transact(
+			2,
+			ethereum::TransactionAction::Create,
+			vec![0x60, 0xaa, 0x60, 0x00, 0x53, 0x60, 0x01, 0x60, 0x00, 0xfd],
+		);
+		transact(
+			3,
+			ethereum::TransactionAction::Call(reverting_long),
+			Vec::new(),
+		);
+
+		assert_eq!(
+			crate::RevertReasons::<Test>::get(0).map(|data| data.into_inner()),
+			Some(vec![0xde, 0xad, 0xbe, 0xef])
+		);
+		assert_eq!(crate::RevertReasons::<Test>::get(1), None);
+		assert_eq!(
+			crate::RevertReasons::<Test>::get(2).map(|data| data.into_inner()),
+			Some(vec![0xaa])
+		);
+		// Longer revert data is truncated.
+		assert_eq!(
+			crate::RevertReasons::<Test>::get(3).map(|data| data.len()),
+			Some(crate::MAX_REVERT_REASON_LEN as usize)
+		);
+
+		<Ethereum as Hooks<u64>>::on_finalize(1);
+		assert!(crate::RevertReasons::<Test>::get(0).is_some());
+		<Ethereum as Hooks<u64>>::on_initialize(2);
+		assert_eq!(crate::RevertReasons::<Test>::iter().count(), 0);
+	});
+}
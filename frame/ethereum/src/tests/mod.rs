@@ -16,7 +16,8 @@
 // limitations under the License.
 
 use frame_support::{
-	assert_err, assert_ok, dispatch::GetDispatchInfo, unsigned::TransactionValidityError,
+	assert_err, assert_ok, dispatch::GetDispatchInfo, traits::Hooks,
+	unsigned::TransactionValidityError,
 };
 use sp_runtime::{
 	traits::Applyable,
@@ -52,3 +53,475 @@ pub const ERC20_CONTRACT_BYTECODE: &str = include_str!("./res/erc20_contract_byt
 // 	   }
 // }
 pub const TEST_CONTRACT_CODE: &str = "608060405234801561001057600080fd5b50610129806100206000396000f3fe6080604052348015600f57600080fd5b506004361060325760003560e01c8063c2985578146037578063febb0f7e146055575b600080fd5b603d605d565b60405180821515815260200191505060405180910390f35b605b6066565b005b60006001905090565b600060bc576040517f08c379a00000000000000000000000000000000000000000000000000000000081526004018080602001828103825260358152602001806100bf6035913960400191505060405180910390fd5b56fe766572795f6c6f6e675f6572726f725f6d73675f746861745f77655f6578706563745f746f5f62655f7472696d6d65645f61776179a26469706673582212207af96dd688d3a3adc999c619e6073d5b6056c72c79ace04a90ea4835a77d179364736f6c634300060c0033";
+
+/// Deploy `runtime_code` with a legacy transaction of `deployer`, returning the address of the
+/// contract.
+fn deploy(deployer: &AccountInfo, runtime_code: &[u8]) -> H160 {
+	// PUSH1 len, PUSH1 12, PUSH1 0, CODECOPY, PUSH1 len, PUSH1 0, RETURN
+	let len = u8::try_from(runtime_code.len()).expect("runtime code fits PUSH1");
+	let mut init = vec![
+		0x60, len, 0x60, 0x0c, 0x60, 0x00, 0x39, 0x60, len, 0x60, 0x00, 0xf3,
+	];
+	init.extend_from_slice(runtime_code);
+
+	let transaction = LegacyUnsignedTransaction {
+		nonce: pallet_evm::Pallet::<Test>::account_basic(&deployer.address)
+			.0
+			.nonce,
+		gas_price: U256::from(1),
+		gas_limit: U256::from(0x100000),
+		action: TransactionAction::Create,
+		value: U256::zero(),
+		input: init,
+	}
+	.sign(&deployer.private_key);
+
+	match Ethereum::apply_validated_transaction(deployer.address, transaction)
+		.unwrap()
+		.1
+	{
+		CallOrCreateInfo::Create(info) => info.value,
+		CallOrCreateInfo::Call(_) => panic!("expected create info"),
+	}
+}
+
+#[test]
+fn extra_data_is_written_into_block_header() {
+	let (_, mut ext) = new_test_ext(1);
+	ext.execute_with(|| {
+		// Extra data at the `ExtraDataLength` limit.
+		let extra_data = vec![0x42; 30];
+		ExtraData::set(extra_data.clone());
+		<Ethereum as Hooks<u64>>::integrity_test();
+
+		<Ethereum as Hooks<u64>>::on_finalize(1);
+		let block = crate::CurrentBlock::<Test>::get().expect("block is stored");
+		assert_eq!(block.header.extra_data, extra_data);
+	});
+}
+
+#[test]
+#[should_panic(expected = "ExtraData must not be longer than ExtraDataLength")]
+fn extra_data_over_limit_fails_integrity_test() {
+	ExtraData::set(vec![0x42; 31]);
+	<Ethereum as Hooks<u64>>::integrity_test();
+}
+
+#[test]
+fn block_logs_bloom_is_union_of_transaction_blooms() {
+	let (pairs, mut ext) = new_test_ext(2);
+
+	// Init code emitting three `LOG1` with the topics `base`, `base + 1` and `base + 2`.
+	fn emit_logs_init_code(base: u8) -> Vec<u8> {
+		let mut code = Vec::new();
+		for topic in base..base + 3 {
+			// PUSH1 topic, PUSH1 0 (size), PUSH1 0 (offset), LOG1
+			code.extend_from_slice(&[0x60, topic, 0x60, 0x00, 0x60, 0x00, 0xa1]);
+		}
+		code
+	}
+
+	ext.execute_with(|| {
+		for (i, account) in pairs.iter().enumerate() {
+			let transaction = LegacyUnsignedTransaction {
+				nonce: U256::zero(),
+				gas_price: U256::from(1),
+				gas_limit: U256::from(0x100000),
+				action: TransactionAction::Create,
+				value: U256::zero(),
+				input: emit_logs_init_code(1 + 3 * i as u8),
+			}
+			.sign(&account.private_key);
+			assert_ok!(Ethereum::transact(
+				RawOrigin::EthereumTransaction(account.address).into(),
+				transaction,
+			));
+		}
+		<Ethereum as Hooks<u64>>::on_finalize(1);
+
+		let statuses = crate::CurrentTransactionStatuses::<Test>::get().expect("statuses stored");
+		let mut expected = ethereum_types::Bloom::default();
+		for status in &statuses {
+			assert_eq!(status.logs.len(), 3);
+			assert_eq!(status.logs_bloom, crate::compute_logs_bloom(&status.logs));
+			expected.accrue_bloom(&status.logs_bloom);
+		}
+
+		let block = crate::CurrentBlock::<Test>::get().expect("block is stored");
+		assert_eq!(block.header.logs_bloom, expected);
+		let all_logs = statuses
+			.into_iter()
+			.flat_map(|status| status.logs)
+			.collect::<Vec<_>>();
+		assert_eq!(
+			block.header.logs_bloom,
+			crate::compute_logs_bloom(&all_logs)
+		);
+	});
+}
+
+#[test]
+fn logs_bloom_weight_is_part_of_the_transaction_weight() {
+	use crate::WeightInfo;
+	use frame_support::{dispatch::DispatchClass, traits::Get};
+	use pallet_evm::GasWeightMapping;
+
+	let (pairs, mut ext) = new_test_ext(1);
+	let alice = &pairs[0];
+
+	ext.execute_with(|| {
+		// PUSH1 topic, PUSH1 0 (size), PUSH1 0 (offset), LOG1, three times.
+		let mut init_code = Vec::new();
+		for topic in 1..=3 {
+			init_code.extend_from_slice(&[0x60, topic, 0x60, 0x00, 0x60, 0x00, 0xa1]);
+		}
+		let gas_limit = 0x100000u64;
+		let transaction = LegacyUnsignedTransaction {
+			nonce: U256::zero(),
+			gas_price: U256::from(1),
+			gas_limit: U256::from(gas_limit),
+			action: TransactionAction::Create,
+			value: U256::zero(),
+			input: init_code,
+		}
+		.sign(&alice.private_key);
+
+		// The declared weight reserves the bloom computation for as many logs as the gas limit
+		// can pay for, at 375 gas per log.
+		let call = crate::Call::<Test>::transact {
+			transaction: transaction.clone(),
+		};
+		assert_eq!(
+			call.get_dispatch_info().weight,
+			<Test as pallet_evm::Config>::GasWeightMapping::gas_to_weight(gas_limit, true)
+				.saturating_add(<Test as frame_system::Config>::DbWeight::get().writes(1))
+				.saturating_add(<() as WeightInfo>::logs_bloom((gas_limit / 375) as u32))
+		);
+
+		// The actual weight only accounts for the logs emitted, and nothing is registered on the
+		// side of the transaction.
+		let mandatory_weight =
+			|| *frame_system::Pallet::<Test>::block_weight().get(DispatchClass::Mandatory);
+		let before = mandatory_weight();
+		let (post_info, info) =
+			Ethereum::apply_validated_transaction(alice.address, transaction).unwrap();
+		let used_gas = match info {
+			CallOrCreateInfo::Create(info) => info.used_gas,
+			CallOrCreateInfo::Call(_) => panic!("expected a create"),
+		};
+		let gas_weight = <Test as pallet_evm::Config>::GasWeightMapping::gas_to_weight(
+			used_gas.standard.max(used_gas.effective).as_u64(),
+			true,
+		);
+		assert_eq!(
+			post_info.actual_weight.unwrap().ref_time(),
+			gas_weight.ref_time() + <() as WeightInfo>::logs_bloom(3).ref_time()
+		);
+		assert_eq!(mandatory_weight(), before);
+	});
+}
+
+#[test]
+fn block_by_number_returns_the_stored_block() {
+	let (pairs, mut ext) = new_test_ext(1);
+	let alice = &pairs[0];
+
+	ext.execute_with(|| {
+		System::set_block_number(1);
+		let transaction = LegacyUnsignedTransaction {
+			nonce: U256::zero(),
+			gas_price: U256::from(1),
+			gas_limit: U256::from(0x100000),
+			action: TransactionAction::Create,
+			value: U256::zero(),
+			input: hex::decode(TEST_CONTRACT_CODE).unwrap(),
+		}
+		.sign(&alice.private_key);
+		assert_ok!(Ethereum::transact(
+			RawOrigin::EthereumTransaction(alice.address).into(),
+			transaction,
+		));
+		assert_eq!(Ethereum::block_by_number(U256::one()), None);
+
+		<Ethereum as Hooks<u64>>::on_finalize(1);
+
+		let (block, receipts, statuses) =
+			Ethereum::block_by_number(U256::one()).expect("block is stored");
+		assert_eq!(Some(block), crate::CurrentBlock::<Test>::get());
+		assert_eq!(
+			Some(receipts.clone()),
+			crate::CurrentReceipts::<Test>::get()
+		);
+		assert_eq!(
+			Some(statuses.clone()),
+			crate::CurrentTransactionStatuses::<Test>::get()
+		);
+		assert_eq!(receipts.len(), 1);
+		assert_eq!(statuses.len(), 1);
+
+		assert_eq!(Ethereum::block_by_number(U256::zero()), None);
+		assert_eq!(Ethereum::block_by_number(U256::from(2)), None);
+	});
+}
+
+#[test]
+fn raw_receipts_decode_to_the_stored_receipts_and_match_the_receipts_root() {
+	let (pairs, mut ext) = new_test_ext(2);
+	let alice = &pairs[0];
+	let bob = &pairs[1];
+
+	ext.execute_with(|| {
+		System::set_block_number(1);
+		let legacy = LegacyUnsignedTransaction {
+			nonce: U256::zero(),
+			gas_price: U256::from(1),
+			gas_limit: U256::from(21_000),
+			action: TransactionAction::Call(bob.address),
+			value: U256::from(1),
+			input: Vec::new(),
+		}
+		.sign(&alice.private_key);
+		let eip1559 = EIP1559UnsignedTransaction {
+			nonce: U256::one(),
+			max_priority_fee_per_gas: U256::from(1),
+			max_fee_per_gas: U256::from(1),
+			gas_limit: U256::from(21_000),
+			action: TransactionAction::Call(bob.address),
+			value: U256::from(1),
+			input: Vec::new(),
+		}
+		.sign(&alice.private_key, None);
+		for transaction in [legacy, eip1559] {
+			assert_ok!(Ethereum::transact(
+				RawOrigin::EthereumTransaction(alice.address).into(),
+				transaction,
+			));
+		}
+		<Ethereum as Hooks<u64>>::on_finalize(1);
+
+		let block = crate::CurrentBlock::<Test>::get().expect("block is stored");
+		let receipts = crate::CurrentReceipts::<Test>::get().expect("receipts are stored");
+		assert_eq!(receipts.len(), 2);
+
+		// The encoding `debug_getRawReceipts` returns.
+		let raw_receipts = receipts
+			.iter()
+			.map(ethereum::EnvelopedEncodable::encode)
+			.collect::<Vec<_>>();
+		let decoded = raw_receipts
+			.iter()
+			.map(|raw| <crate::Receipt as ethereum::EnvelopedDecodable>::decode(raw).unwrap())
+			.collect::<Vec<_>>();
+		assert_eq!(decoded, receipts);
+		assert!(matches!(decoded[1], crate::Receipt::EIP1559(_)));
+		assert_eq!(
+			ethereum::util::ordered_trie_root(raw_receipts),
+			block.header.receipts_root
+		);
+	});
+}
+
+#[test]
+fn evm_config_info_reports_the_transaction_config() {
+	let (_, mut ext) = new_test_ext(0);
+
+	ext.execute_with(|| {
+		let info = Ethereum::evm_config_info();
+		assert_eq!(info.hardfork_name, b"shanghai".to_vec());
+		assert_eq!(info.max_code_size, Some(0x6000));
+		assert_eq!(info.max_initcode_size, Some(0xC000));
+		assert_eq!(info.gas_transaction_call, 21_000);
+		assert_eq!(info.gas_transaction_create, 53_000);
+
+		// The base gas of ethereum extrinsics is part of the intrinsic cost.
+		ExtrinsicBaseGas::set(1_000);
+		let info = Ethereum::evm_config_info();
+		assert_eq!(info.gas_transaction_call, 22_000);
+		assert_eq!(info.gas_transaction_create, 54_000);
+	});
+
+	let hardfork_name = |config: &evm::Config| fp_rpc::EvmConfigInfo::from(config).hardfork_name;
+	assert_eq!(hardfork_name(&evm::Config::london()), b"london".to_vec());
+	assert_eq!(hardfork_name(&evm::Config::berlin()), b"berlin".to_vec());
+	assert_eq!(
+		hardfork_name(&evm::Config::istanbul()),
+		b"istanbul".to_vec()
+	);
+	assert_eq!(
+		hardfork_name(&evm::Config::frontier()),
+		b"frontier".to_vec()
+	);
+}
+
+#[test]
+fn raw_transaction_decodes_to_the_stored_transaction() {
+	let (pairs, mut ext) = new_test_ext(1);
+	let alice = &pairs[0];
+
+	ext.execute_with(|| {
+		let legacy = LegacyUnsignedTransaction {
+			nonce: U256::zero(),
+			gas_price: U256::from(1),
+			gas_limit: U256::from(0x100000),
+			action: TransactionAction::Create,
+			value: U256::zero(),
+			input: hex::decode(TEST_CONTRACT_CODE).unwrap(),
+		}
+		.sign(&alice.private_key);
+		let eip1559 = EIP1559UnsignedTransaction {
+			nonce: U256::one(),
+			max_priority_fee_per_gas: U256::from(1),
+			max_fee_per_gas: U256::from(1),
+			gas_limit: U256::from(0x100000),
+			action: TransactionAction::Call(H160::repeat_byte(0x11)),
+			value: U256::zero(),
+			input: vec![0x42],
+		}
+		.sign(&alice.private_key, None);
+
+		for transaction in [legacy.clone(), eip1559.clone()] {
+			assert_ok!(Ethereum::transact(
+				RawOrigin::EthereumTransaction(alice.address).into(),
+				transaction,
+			));
+		}
+		<Ethereum as Hooks<u64>>::on_finalize(1);
+
+		for transaction in [legacy, eip1559] {
+			let raw = Ethereum::raw_transaction(transaction.hash()).expect("transaction is stored");
+			let decoded: Transaction =
+				ethereum::EnvelopedDecodable::decode(&raw).expect("valid encoding");
+			assert_eq!(decoded, transaction);
+		}
+		assert_eq!(Ethereum::raw_transaction(H256::repeat_byte(0x01)), None);
+	});
+}
+
+#[test]
+fn ethereum_state_root_matches_reference_trie() {
+	use pallet_evm::Account;
+
+	// Root of an empty trie.
+	assert_eq!(
+		crate::ethereum_state_root(Vec::new()),
+		H256::from_str("0x56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421")
+			.unwrap()
+	);
+
+	// Reference root computed with an independent Merkle Patricia trie implementation.
+	let accounts = vec![
+		(
+			H160::from_low_u64_be(1),
+			Account {
+				nonce: U256::zero(),
+				balance: U256::from(1000),
+			},
+			Vec::new(),
+			Vec::new(),
+		),
+		(
+			H160::from_low_u64_be(2),
+			Account {
+				nonce: U256::one(),
+				balance: U256::zero(),
+			},
+			vec![0x60, 0x00],
+			vec![
+				(H256::from_low_u64_be(0), H256::from_low_u64_be(0x2a)),
+				(H256::from_low_u64_be(1), H256::repeat_byte(0xff)),
+				// Zero values are not part of the storage trie.
+				(H256::from_low_u64_be(2), H256::zero()),
+			],
+		),
+	];
+	assert_eq!(
+		crate::ethereum_state_root(accounts),
+		H256::from_str("0x0815d31e1ba83ec02f1fa5b4f5482c9aff6d50e157cdc2ce07a82bd3e3c428ee")
+			.unwrap()
+	);
+}
+
+#[test]
+fn ethereum_state_root_is_bounded_by_max_state_root_entries() {
+	use frame_support::traits::{LockableCurrency, WithdrawReasons};
+	use pallet_evm::Account;
+
+	let (pairs, mut ext) = new_test_ext(1);
+	ext.execute_with(|| {
+		// Locked funds are part of the total balance, which is the one in the root.
+		Balances::set_lock(
+			*b"testlock",
+			&pairs[0].account_id,
+			1_000,
+			WithdrawReasons::all(),
+		);
+		let contract = H160::from_low_u64_be(2);
+		let code = vec![0x60, 0x00];
+		pallet_evm::AccountCodes::<Test>::insert(contract, code.clone());
+		let storage: Vec<_> = (0..3)
+			.map(|index| (H256::from_low_u64_be(index), H256::repeat_byte(0x01)))
+			.collect();
+		for (key, value) in &storage {
+			pallet_evm::AccountStorages::<Test>::insert(contract, key, value);
+		}
+		let addresses: std::collections::BTreeSet<_> =
+			[pairs[0].address, contract].into_iter().collect();
+
+		// Two keys, two basic accounts and codes, and three storage entries.
+		let entries = 2 + 2 * 2 + 3;
+		assert_eq!(
+			Ethereum::state_root_of(addresses.clone(), entries - 1),
+			None
+		);
+		assert_eq!(
+			Ethereum::state_root_of(addresses, entries),
+			Some(crate::ethereum_state_root(vec![
+				(
+					pairs[0].address,
+					Account {
+						nonce: U256::zero(),
+						balance: U256::from(10_000_000),
+					},
+					Vec::new(),
+					Vec::new(),
+				),
+				(
+					contract,
+					Account {
+						nonce: U256::zero(),
+						balance: U256::zero(),
+					},
+					code,
+					storage,
+				),
+			]))
+		);
+	});
+}
+
+#[test]
+fn stale_block_hashes_are_pruned_in_batches_after_reducing_the_window() {
+	let (_, mut ext) = new_test_ext(1);
+	ext.execute_with(|| {
+		let contains = |number: u64| crate::BlockHash::<Test>::contains_key(U256::from(number));
+
+		// The hashes of the genesis block and of the 250 blocks before block 301.
+		for number in std::iter::once(0).chain(51..=300) {
+			crate::BlockHash::<Test>::insert(U256::from(number), H256::repeat_byte(1));
+		}
+
+		// With the full window there is nothing left behind.
+		<Ethereum as Hooks<u64>>::on_initialize(301);
+		assert!(contains(51));
+
+		// Block 301 prunes block 200, leaving blocks 51 to 199 behind.
+		BlockHashCount::set(100);
+		BlockHashPruningLimit::set(50);
+		for oldest_kept in [101, 151, 200, 200] {
+			<Ethereum as Hooks<u64>>::on_initialize(301);
+			assert!(!contains(oldest_kept - 1));
+			assert!(contains(oldest_kept));
+		}
+		assert!(contains(0));
+	});
+}
@@ -0,0 +1,61 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Weights for pallet_ethereum
+//!
+//! THESE WEIGHTS ARE PLACEHOLDERS, NOT BENCHMARK RESULTS. They are a rough estimate of the cost of
+//! folding logs into a bloom filter, and must be replaced by the output of the `logs_bloom`
+//! benchmark before being relied on:
+//!
+//! ./target/release/frontier-template-node benchmark pallet --chain=dev --steps=50 --repeat=20
+//! --pallet=pallet_ethereum --extrinsic=* --wasm-execution=compiled
+//! --output=./frame/ethereum/src/weights.rs --header=HEADER-APACHE2
+//! --template=./scripts/frame-weight-template.hbs
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(missing_docs)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use core::marker::PhantomData;
+
+/// Weight functions needed for pallet_ethereum.
+pub trait WeightInfo {
+	fn logs_bloom(l: u32, ) -> Weight;
+}
+
+/// Weights for pallet_ethereum using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	/// The range of component `l` is `[0, 1000]`.
+	fn logs_bloom(l: u32, ) -> Weight {
+		// Placeholder: no storage is accessed, so there is no proof size.
+		Weight::from_parts(1_000_000, 0)
+			.saturating_add(Weight::from_parts(4_000_000, 0).saturating_mul(l.into()))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	/// The range of component `l` is `[0, 1000]`.
+	fn logs_bloom(l: u32, ) -> Weight {
+		// Placeholder: no storage is accessed, so there is no proof size.
+		Weight::from_parts(1_000_000, 0)
+			.saturating_add(Weight::from_parts(4_000_000, 0).saturating_mul(l.into()))
+	}
+}
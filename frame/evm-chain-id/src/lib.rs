@@ -21,6 +21,10 @@
 //! It can simplify setting up multiple networks with different chain ID by configuring the
 //! chain spec without requiring changes to the runtime config.
 //!
+//! The chain id can be changed by root through `set_chain_id`. Using this pallet as
+//! `pallet_evm::Config::ChainId` makes transaction validation pick up the new value right away,
+//! so transactions signed for the previous chain id are rejected from then on.
+//!
 //! **NOTE**: we recommend that the production chains still use the const parameter type, as
 //! this extra storage access would imply some performance penalty.
 
@@ -33,6 +37,7 @@ pub use pallet::*;
 #[frame_support::pallet]
 pub mod pallet {
 	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
 
 	const STORAGE_VERSION: StorageVersion = StorageVersion::new(0);
 
@@ -53,6 +58,18 @@ pub mod pallet {
 	#[pallet::storage]
 	pub type ChainId<T> = StorageValue<_, u64, ValueQuery>;
 
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Set the EVM chain ID.
+		#[pallet::call_index(0)]
+		#[pallet::weight(T::DbWeight::get().writes(1))]
+		pub fn set_chain_id(origin: OriginFor<T>, chain_id: u64) -> DispatchResult {
+			ensure_root(origin)?;
+			ChainId::<T>::put(chain_id);
+			Ok(())
+		}
+	}
+
 	#[pallet::genesis_config]
 	#[derive(frame_support::DefaultNoBound)]
 	pub struct GenesisConfig<T> {
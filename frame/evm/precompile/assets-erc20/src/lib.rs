@@ -0,0 +1,331 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! ERC-20 precompile set for fungible assets. Every asset gets its own precompile, whose address
+//! is derived from the asset id (see [`AddressToAssetId`]). Calls are proxied to the `fungibles`
+//! implementation of the runtime, typically `pallet_assets`.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use core::marker::PhantomData;
+use fp_evm::ExitError;
+use frame_support::traits::{
+	fungibles::{
+		approvals::{Inspect as ApprovalsInspect, Mutate as ApprovalsMutate},
+		metadata::Inspect as MetadataInspect,
+		Inspect, Mutate,
+	},
+	tokens::Preservation,
+	Get,
+};
+use pallet_evm::AddressMapping;
+use precompile_utils::{prelude::*, EvmResult};
+use sp_core::{H160, U256};
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+/// Solidity selector of the Transfer log, which is the Keccak of the Log signature.
+pub const SELECTOR_LOG_TRANSFER: [u8; 32] = keccak256!("Transfer(address,address,uint256)");
+
+/// Solidity selector of the Approval log, which is the Keccak of the Log signature.
+pub const SELECTOR_LOG_APPROVAL: [u8; 32] = keccak256!("Approval(address,address,uint256)");
+
+/// Maps the address of an asset precompile to the asset it proxies to, and back.
+pub trait AddressToAssetId<AssetId> {
+	/// Asset id of the given address, if it is in the address range of the precompile set.
+	fn address_to_asset_id(address: H160) -> Option<AssetId>;
+
+	/// Address of the precompile of the given asset.
+	fn asset_id_to_address(asset_id: AssetId) -> H160;
+}
+
+/// Address scheme placing the asset id, as a big-endian `u128`, after a 4 bytes `Prefix`.
+/// The same prefix can be given to `PrecompileSetStartingWith` to route the whole range to the
+/// precompile set.
+pub struct PrefixedAssetIdMapping<Prefix>(PhantomData<Prefix>);
+
+impl<Prefix, AssetId> AddressToAssetId<AssetId> for PrefixedAssetIdMapping<Prefix>
+where
+	Prefix: Get<&'static [u8]>,
+	AssetId: Into<u128> + TryFrom<u128>,
+{
+	fn address_to_asset_id(address: H160) -> Option<AssetId> {
+		let asset_id = address.as_bytes().strip_prefix(Prefix::get())?;
+		let asset_id: [u8; 16] = asset_id.try_into().ok()?;
+		AssetId::try_from(u128::from_be_bytes(asset_id)).ok()
+	}
+
+	fn asset_id_to_address(asset_id: AssetId) -> H160 {
+		let prefix = Prefix::get();
+		let mut address = [0u8; 20];
+		address[..prefix.len()].copy_from_slice(prefix);
+		address[4..].copy_from_slice(&asset_id.into().to_be_bytes());
+		H160(address)
+	}
+}
+
+type AssetIdOf<Runtime, Assets> =
+	<Assets as Inspect<<Runtime as frame_system::Config>::AccountId>>::AssetId;
+type BalanceOf<Runtime, Assets> =
+	<Assets as Inspect<<Runtime as frame_system::Config>::AccountId>>::Balance;
+
+/// ERC-20 interface to the assets of `Assets`. Addresses that `AssetIdMapping` maps to an asset
+/// that does not exist are not considered precompiles.
+pub struct Erc20AssetsPrecompileSet<Runtime, Assets, AssetIdMapping>(
+	PhantomData<(Runtime, Assets, AssetIdMapping)>,
+);
+
+impl<Runtime, Assets, AssetIdMapping> Default
+	for Erc20AssetsPrecompileSet<Runtime, Assets, AssetIdMapping>
+{
+	fn default() -> Self {
+		Self(PhantomData)
+	}
+}
+
+#[precompile_utils::precompile]
+#[precompile::precompile_set]
+impl<Runtime, Assets, AssetIdMapping> Erc20AssetsPrecompileSet<Runtime, Assets, AssetIdMapping>
+where
+	Runtime: pallet_evm::Config,
+	Assets: Mutate<Runtime::AccountId>
+		+ MetadataInspect<Runtime::AccountId>
+		+ ApprovalsMutate<Runtime::AccountId>,
+	BalanceOf<Runtime, Assets>: TryFrom<U256> + Into<U256>,
+	AssetIdMapping: AddressToAssetId<AssetIdOf<Runtime, Assets>>,
+{
+	/// Maps the address to the asset it proxies to, if that asset exists.
+	#[precompile::discriminant]
+	fn discriminant(address: H160, gas: u64) -> DiscriminantResult<AssetIdOf<Runtime, Assets>> {
+		let extra_cost = RuntimeHelper::<Runtime>::db_read_gas_cost();
+		if gas < extra_cost {
+			return DiscriminantResult::OutOfGas;
+		}
+
+		match AssetIdMapping::address_to_asset_id(address) {
+			Some(asset_id) if Assets::asset_exists(asset_id.clone()) => {
+				DiscriminantResult::Some(asset_id, extra_cost)
+			}
+			_ => DiscriminantResult::None(extra_cost),
+		}
+	}
+
+	#[precompile::public("totalSupply()")]
+	#[precompile::view]
+	fn total_supply(
+		asset_id: AssetIdOf<Runtime, Assets>,
+		handle: &mut impl PrecompileHandle,
+	) -> EvmResult<U256> {
+		Self::record_db_cost(handle, 1, 0)?;
+
+		Ok(Assets::total_issuance(asset_id).into())
+	}
+
+	#[precompile::public("balanceOf(address)")]
+	#[precompile::view]
+	fn balance_of(
+		asset_id: AssetIdOf<Runtime, Assets>,
+		handle: &mut impl PrecompileHandle,
+		who: Address,
+	) -> EvmResult<U256> {
+		Self::record_db_cost(handle, 1, 0)?;
+
+		let who = Runtime::AddressMapping::into_account_id(who.into());
+		Ok(Assets::balance(asset_id, &who).into())
+	}
+
+	#[precompile::public("allowance(address,address)")]
+	#[precompile::view]
+	fn allowance(
+		asset_id: AssetIdOf<Runtime, Assets>,
+		handle: &mut impl PrecompileHandle,
+		owner: Address,
+		spender: Address,
+	) -> EvmResult<U256> {
+		Self::record_db_cost(handle, 1, 0)?;
+
+		let owner = Runtime::AddressMapping::into_account_id(owner.into());
+		let spender = Runtime::AddressMapping::into_account_id(spender.into());
+		Ok(Assets::allowance(asset_id, &owner, &spender).into())
+	}
+
+	/// Sets the allowance of `spender` to `value`. The `fungibles` approvals can only be
+	/// increased, so lowering an existing allowance is rejected.
+	#[precompile::public("approve(address,uint256)")]
+	fn approve(
+		asset_id: AssetIdOf<Runtime, Assets>,
+		handle: &mut impl PrecompileHandle,
+		spender: Address,
+		value: U256,
+	) -> EvmResult<bool> {
+		handle.record_log_costs_manual(3, 32)?;
+		Self::record_db_cost(handle, 2, 2)?;
+
+		let caller = handle.context().caller;
+		let owner = Runtime::AddressMapping::into_account_id(caller);
+		let delegate = Runtime::AddressMapping::into_account_id(spender.into());
+
+		let current: U256 = Assets::allowance(asset_id.clone(), &owner, &delegate).into();
+		let increase = value
+			.checked_sub(current)
+			.ok_or_else(|| revert("Lowering an existing allowance is not supported"))?;
+		let increase = Self::u256_to_balance(increase)?;
+
+		Assets::approve(asset_id, &owner, &delegate, increase)
+			.map_err(TryDispatchError::Substrate)?;
+
+		log3(
+			handle.context().address,
+			SELECTOR_LOG_APPROVAL,
+			caller,
+			spender.0,
+			solidity::encode_event_data(value),
+		)
+		.record(handle)?;
+
+		Ok(true)
+	}
+
+	#[precompile::public("transfer(address,uint256)")]
+	fn transfer(
+		asset_id: AssetIdOf<Runtime, Assets>,
+		handle: &mut impl PrecompileHandle,
+		to: Address,
+		value: U256,
+	) -> EvmResult<bool> {
+		handle.record_log_costs_manual(3, 32)?;
+		Self::record_db_cost(handle, 3, 2)?;
+
+		let caller = handle.context().caller;
+		let source = Runtime::AddressMapping::into_account_id(caller);
+		let dest = Runtime::AddressMapping::into_account_id(to.into());
+		let amount = Self::u256_to_balance(value)?;
+
+		<Assets as Mutate<_>>::transfer(asset_id, &source, &dest, amount, Preservation::Expendable)
+			.map_err(TryDispatchError::Substrate)?;
+
+		log3(
+			handle.context().address,
+			SELECTOR_LOG_TRANSFER,
+			caller,
+			to.0,
+			solidity::encode_event_data(value),
+		)
+		.record(handle)?;
+
+		Ok(true)
+	}
+
+	#[precompile::public("transferFrom(address,address,uint256)")]
+	fn transfer_from(
+		asset_id: AssetIdOf<Runtime, Assets>,
+		handle: &mut impl PrecompileHandle,
+		from: Address,
+		to: Address,
+		value: U256,
+	) -> EvmResult<bool> {
+		handle.record_log_costs_manual(3, 32)?;
+		Self::record_db_cost(handle, 4, 3)?;
+
+		let caller = handle.context().caller;
+		let owner = Runtime::AddressMapping::into_account_id(from.into());
+		let dest = Runtime::AddressMapping::into_account_id(to.into());
+		let amount = Self::u256_to_balance(value)?;
+
+		// Spending one's own tokens does not require an approval.
+		let result = if caller == from.0 {
+			<Assets as Mutate<_>>::transfer(
+				asset_id,
+				&owner,
+				&dest,
+				amount,
+				Preservation::Expendable,
+			)
+			.map(|_| ())
+		} else {
+			let delegate = Runtime::AddressMapping::into_account_id(caller);
+			Assets::transfer_from(asset_id, &owner, &delegate, &dest, amount)
+		};
+		result.map_err(TryDispatchError::Substrate)?;
+
+		log3(
+			handle.context().address,
+			SELECTOR_LOG_TRANSFER,
+			from.0,
+			to.0,
+			solidity::encode_event_data(value),
+		)
+		.record(handle)?;
+
+		Ok(true)
+	}
+
+	#[precompile::public("name()")]
+	#[precompile::view]
+	fn name(
+		asset_id: AssetIdOf<Runtime, Assets>,
+		handle: &mut impl PrecompileHandle,
+	) -> EvmResult<UnboundedString> {
+		Self::record_db_cost(handle, 1, 0)?;
+
+		Ok(Assets::name(asset_id).into())
+	}
+
+	#[precompile::public("symbol()")]
+	#[precompile::view]
+	fn symbol(
+		asset_id: AssetIdOf<Runtime, Assets>,
+		handle: &mut impl PrecompileHandle,
+	) -> EvmResult<UnboundedString> {
+		Self::record_db_cost(handle, 1, 0)?;
+
+		Ok(Assets::symbol(asset_id).into())
+	}
+
+	#[precompile::public("decimals()")]
+	#[precompile::view]
+	fn decimals(
+		asset_id: AssetIdOf<Runtime, Assets>,
+		handle: &mut impl PrecompileHandle,
+	) -> EvmResult<u8> {
+		Self::record_db_cost(handle, 1, 0)?;
+
+		Ok(Assets::decimals(asset_id))
+	}
+
+	/// Record the gas cost of the given number of DB reads and writes, as converted by the
+	/// runtime `GasWeightMapping`.
+	fn record_db_cost(handle: &mut impl PrecompileHandle, reads: u64, writes: u64) -> EvmResult {
+		let cost = RuntimeHelper::<Runtime>::db_read_gas_cost()
+			.saturating_mul(reads)
+			.saturating_add(RuntimeHelper::<Runtime>::db_write_gas_cost().saturating_mul(writes));
+		handle.record_cost(cost)?;
+
+		Ok(())
+	}
+
+	fn u256_to_balance(value: U256) -> EvmResult<BalanceOf<Runtime, Assets>> {
+		value.try_into().map_err(|_| {
+			RevertReason::value_is_too_large("balance type")
+				.in_field("value")
+				.into()
+		})
+	}
+}
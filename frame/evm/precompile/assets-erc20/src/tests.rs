@@ -0,0 +1,279 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+	mock::{
+		AccountId, AssetId, AssetIdMapping, Assets, ExtBuilder, PCall, Precompiles,
+		PrecompilesValue, Runtime, RuntimeOrigin,
+	},
+	AddressToAssetId, SELECTOR_LOG_APPROVAL, SELECTOR_LOG_TRANSFER,
+};
+use frame_support::assert_ok;
+use precompile_utils::{prelude::*, testing::*};
+use sp_core::{H160, U256};
+
+const ASSET_ID: AssetId = 1;
+
+fn precompiles() -> Precompiles<Runtime> {
+	PrecompilesValue::get()
+}
+
+fn asset_address() -> H160 {
+	AssetIdMapping::asset_id_to_address(ASSET_ID)
+}
+
+// Creates the asset and mints 1000 units to Alice.
+fn create_asset() {
+	assert_ok!(Assets::force_create(
+		RuntimeOrigin::root(),
+		ASSET_ID,
+		Alice.into(),
+		true,
+		1
+	));
+	assert_ok!(Assets::force_set_metadata(
+		RuntimeOrigin::root(),
+		ASSET_ID,
+		b"Test Token".to_vec(),
+		b"TEST".to_vec(),
+		12,
+		false
+	));
+	assert_ok!(Assets::mint(
+		RuntimeOrigin::signed(Alice.into()),
+		ASSET_ID,
+		Alice.into(),
+		1000
+	));
+}
+
+#[test]
+fn asset_address_round_trips() {
+	let address = asset_address();
+	assert_eq!(&address.as_bytes()[..4], &[255u8; 4]);
+	assert_eq!(
+		<AssetIdMapping as AddressToAssetId<AssetId>>::address_to_asset_id(address),
+		Some(ASSET_ID)
+	);
+	assert_eq!(
+		<AssetIdMapping as AddressToAssetId<AssetId>>::address_to_asset_id(H160::repeat_byte(1)),
+		None
+	);
+}
+
+#[test]
+fn unknown_asset_is_not_a_precompile() {
+	ExtBuilder::default().build().execute_with(|| {
+		precompiles()
+			.prepare_test(Alice, asset_address(), PCall::total_supply {})
+			.execute_none();
+	})
+}
+
+#[test]
+fn metadata_and_supply() {
+	ExtBuilder::default().build().execute_with(|| {
+		create_asset();
+
+		precompiles()
+			.prepare_test(Alice, asset_address(), PCall::name {})
+			.execute_returns(UnboundedString::from("Test Token"));
+		precompiles()
+			.prepare_test(Alice, asset_address(), PCall::symbol {})
+			.execute_returns(UnboundedString::from("TEST"));
+		precompiles()
+			.prepare_test(Alice, asset_address(), PCall::decimals {})
+			.execute_returns(12u8);
+		precompiles()
+			.prepare_test(Alice, asset_address(), PCall::total_supply {})
+			.execute_returns(U256::from(1000));
+		precompiles()
+			.prepare_test(
+				Bob,
+				asset_address(),
+				PCall::balance_of {
+					who: Address(Alice.into()),
+				},
+			)
+			.execute_returns(U256::from(1000));
+	})
+}
+
+#[test]
+fn transfer_moves_balance_and_emits_log() {
+	ExtBuilder::default().build().execute_with(|| {
+		create_asset();
+
+		precompiles()
+			.prepare_test(
+				Alice,
+				asset_address(),
+				PCall::transfer {
+					to: Address(Bob.into()),
+					value: 400.into(),
+				},
+			)
+			.expect_log(log3(
+				asset_address(),
+				SELECTOR_LOG_TRANSFER,
+				Alice,
+				Bob,
+				solidity::encode_event_data(U256::from(400)),
+			))
+			.execute_returns(true);
+
+		assert_eq!(Assets::balance(ASSET_ID, AccountId::from(Alice)), 600);
+		assert_eq!(Assets::balance(ASSET_ID, AccountId::from(Bob)), 400);
+	})
+}
+
+#[test]
+fn transfer_more_than_balance_reverts() {
+	ExtBuilder::default().build().execute_with(|| {
+		create_asset();
+
+		precompiles()
+			.prepare_test(
+				Alice,
+				asset_address(),
+				PCall::transfer {
+					to: Address(Bob.into()),
+					value: 1001.into(),
+				},
+			)
+			.execute_reverts(|output| output.starts_with(b"Dispatched call failed"));
+
+		assert_eq!(Assets::balance(ASSET_ID, AccountId::from(Alice)), 1000);
+	})
+}
+
+#[test]
+fn approve_and_transfer_from() {
+	ExtBuilder::default().build().execute_with(|| {
+		create_asset();
+
+		precompiles()
+			.prepare_test(
+				Alice,
+				asset_address(),
+				PCall::approve {
+					spender: Address(Bob.into()),
+					value: 500.into(),
+				},
+			)
+			.expect_log(log3(
+				asset_address(),
+				SELECTOR_LOG_APPROVAL,
+				Alice,
+				Bob,
+				solidity::encode_event_data(U256::from(500)),
+			))
+			.execute_returns(true);
+
+		precompiles()
+			.prepare_test(
+				Charlie,
+				asset_address(),
+				PCall::allowance {
+					owner: Address(Alice.into()),
+					spender: Address(Bob.into()),
+				},
+			)
+			.execute_returns(U256::from(500));
+
+		precompiles()
+			.prepare_test(
+				Bob,
+				asset_address(),
+				PCall::transfer_from {
+					from: Address(Alice.into()),
+					to: Address(Charlie.into()),
+					value: 400.into(),
+				},
+			)
+			.expect_log(log3(
+				asset_address(),
+				SELECTOR_LOG_TRANSFER,
+				Alice,
+				Charlie,
+				solidity::encode_event_data(U256::from(400)),
+			))
+			.execute_returns(true);
+
+		assert_eq!(Assets::balance(ASSET_ID, AccountId::from(Alice)), 600);
+		assert_eq!(Assets::balance(ASSET_ID, AccountId::from(Charlie)), 400);
+
+		precompiles()
+			.prepare_test(
+				Charlie,
+				asset_address(),
+				PCall::allowance {
+					owner: Address(Alice.into()),
+					spender: Address(Bob.into()),
+				},
+			)
+			.execute_returns(U256::from(100));
+	})
+}
+
+#[test]
+fn transfer_from_without_approval_reverts() {
+	ExtBuilder::default().build().execute_with(|| {
+		create_asset();
+
+		precompiles()
+			.prepare_test(
+				Bob,
+				asset_address(),
+				PCall::transfer_from {
+					from: Address(Alice.into()),
+					to: Address(Bob.into()),
+					value: 1.into(),
+				},
+			)
+			.execute_reverts(|output| output.starts_with(b"Dispatched call failed"));
+	})
+}
+
+#[test]
+fn lowering_an_allowance_reverts() {
+	ExtBuilder::default().build().execute_with(|| {
+		create_asset();
+
+		precompiles()
+			.prepare_test(
+				Alice,
+				asset_address(),
+				PCall::approve {
+					spender: Address(Bob.into()),
+					value: 500.into(),
+				},
+			)
+			.execute_returns(true);
+
+		precompiles()
+			.prepare_test(
+				Alice,
+				asset_address(),
+				PCall::approve {
+					spender: Address(Bob.into()),
+					value: 100.into(),
+				},
+			)
+			.execute_reverts(|output| output == b"Lowering an existing allowance is not supported");
+	})
+}
@@ -0,0 +1,150 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use alloc::vec::Vec;
+use curve25519_dalek::constants::RISTRETTO_BASEPOINT_COMPRESSED;
+use frame_benchmarking::benchmarks;
+use hex_literal::hex;
+use sp_core::{H160, H256};
+// Frontier
+use fp_evm::{
+	Context, ExitError, ExitReason, LinearCostPrecompile, Precompile, PrecompileHandle,
+	PrecompileResult, Transfer,
+};
+use pallet_evm_precompile_bls12381::Bls12381Pairing;
+use pallet_evm_precompile_bw6761::Bw6761Pairing;
+use pallet_evm_precompile_curve25519::{Curve25519Add, Curve25519ScalarMul};
+
+use super::*;
+
+/// `2*G1, 3*G2` pair, taken from the BLS12-381 pairing test vectors.
+const BLS12381_PAIR: [u8; 384] = hex!("000000000000000000000000000000000572cbea904d67468808c8eb50a9450c9721db309128012543902d0ac358a62ae28f75bb8f1c7c42c39a8c5529bf0f4e00000000000000000000000000000000166a9d8cabc673a322fda673779d8e3822ba3ecb8670e461f73bb9021d5fd76a4c56d9d4cd16bd1bba86881979749d2800000000000000000000000000000000122915c824a0857e2ee414a3dccb23ae691ae54329781315a0c75df1c04d6d7a50a030fc866f09d516020ef82324afae0000000000000000000000000000000009380275bbc8e5dcea7dc4dd7e0550ff2ac480905396eda55062650f8d251c96eb480673937cc6d9d6a44aaa56ca66dc000000000000000000000000000000000b21da7955969e61010c7a1abc1a6f0136961d1e3b20b1a7326ac738fef5c721479dfd948b52fdf2455e44813ecfd8920000000000000000000000000000000008f239ba329b3967fe48d718a36cfe5f62a7e42e0bf1c1ed714150a166bfbd6bcf6b3b58b975b9edea56d53f23a0e849");
+
+/// G1/G2 pair, taken from the `g2_pairing_2` BW6-761 pairing test vector.
+const BW6761_PAIR: [u8; 384] = hex!("001f7550c3109fe2465eb890801715c3a7044bf49b6fa35322e9be221c90548a5c684cdb3c55fd2435c4b18353ab08a72f55422531a0e8ac49c03f4cd88ffc38a68655728c4a78a57f122a7f312341d118113c48bbb4cf23285aaa0a717f7e1b0005e47135c9df45b96095e95a5d895a36520205c036ff97770078c10432e446920f3320dc6005d5dd66535a7c13e9e9f82bdeda7eb3cd1b1bfe082abf73b4858a3b274973092b21a6290b766d1fd7dfbb98ee647f6eb437aeab8bcb0363e4a400f82dd2bfc3b0cc3b2ad1c9bb9850d84fc8a2fe9faaf949e84d662e63c9884dd3b024e150b5ea87b2cc6a188129a96ca1387378745fd34bf31cd287116df8b6c8ab96ace5fe62b847b8ff31c24a00e520e8027fc2080103b4118f5489dbffca00365145fb95fda482c724149a6a59f8b17483bad516ce1c7504ccca3ac94ae6f1cb2044ea046f66cfb34edade18e0251ca1e501a61424901994e537de125900e17b434b355a10e71ff6a5a76f63167672d6910735c224e9c005c8e0fbdff1bc");
+
+/// Minimal handle to run precompiles outside of the EVM, gas is not metered.
+struct BenchmarkHandle {
+	input: Vec<u8>,
+	context: Context,
+}
+
+impl BenchmarkHandle {
+	fn new(input: Vec<u8>) -> Self {
+		Self {
+			input,
+			context: Context {
+				address: H160::zero(),
+				caller: H160::zero(),
+				apparent_value: 0.into(),
+			},
+		}
+	}
+}
+
+impl PrecompileHandle for BenchmarkHandle {
+	fn call(
+		&mut self,
+		_: H160,
+		_: Option<Transfer>,
+		_: Vec<u8>,
+		_: Option<u64>,
+		_: bool,
+		_: &Context,
+	) -> (ExitReason, Vec<u8>) {
+		unimplemented!()
+	}
+
+	fn record_cost(&mut self, _: u64) -> Result<(), ExitError> {
+		Ok(())
+	}
+
+	fn record_external_cost(
+		&mut self,
+		_: Option<u64>,
+		_: Option<u64>,
+		_: Option<u64>,
+	) -> Result<(), ExitError> {
+		Ok(())
+	}
+
+	fn refund_external_cost(&mut self, _: Option<u64>, _: Option<u64>) {}
+
+	fn log(&mut self, _: H160, _: Vec<H256>, _: Vec<u8>) -> Result<(), ExitError> {
+		unimplemented!()
+	}
+
+	fn remaining_gas(&self) -> u64 {
+		u64::MAX
+	}
+
+	fn code_address(&self) -> H160 {
+		H160::zero()
+	}
+
+	fn input(&self) -> &[u8] {
+		&self.input
+	}
+
+	fn context(&self) -> &Context {
+		&self.context
+	}
+
+	fn is_static(&self) -> bool {
+		false
+	}
+
+	fn gas_limit(&self) -> Option<u64> {
+		None
+	}
+}
+
+fn execute<P: Precompile>(input: Vec<u8>) -> PrecompileResult {
+	P::execute(&mut BenchmarkHandle::new(input))
+}
+
+benchmarks! {
+	curve25519_add_n_points {
+		let n in 1 .. 10;
+		let input = RISTRETTO_BASEPOINT_COMPRESSED.to_bytes().repeat(n as usize);
+	}: {
+		<Curve25519Add as LinearCostPrecompile>::execute(&input, 0).expect("valid input");
+	}
+
+	curve25519_scalar_mul {
+		let mut input = [0xffu8; 32].to_vec();
+		input.extend_from_slice(RISTRETTO_BASEPOINT_COMPRESSED.as_bytes());
+	}: {
+		<Curve25519ScalarMul as LinearCostPrecompile>::execute(&input, 0).expect("valid input");
+	}
+
+	bls12381_pairing_n_pairs {
+		let n in 1 .. 10;
+		let input = BLS12381_PAIR.repeat(n as usize);
+	}: {
+		execute::<Bls12381Pairing>(input).expect("valid input");
+	}
+
+	bw6761_pairing_n_pairs {
+		let n in 1 .. 10;
+		let input = BW6761_PAIR.repeat(n as usize);
+	}: {
+		execute::<Bw6761Pairing>(input).expect("valid input");
+	}
+
+	impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
+}
@@ -0,0 +1,56 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Benchmarks for the curve precompiles.
+//!
+//! The weighted curve25519 precompiles, `Curve25519AddWeighted` and
+//! `Curve25519ScalarMulWeighted`, charge gas from the weights generated here, see
+//! [`weights::SubstrateWeight`]. The BLS12-381 and BW6-761 pairing precompiles keep the gas
+//! schedules of their EIPs, their benchmarks let chains check those schedules against the
+//! weight they actually consume.
+//!
+//! Like `frame_system_benchmarking`, this is not a pallet to add to the runtime: implement
+//! [`Config`] for the runtime in `dispatch_benchmark` and list [`Pallet`] in `define_benchmarks!`.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+#[cfg(all(feature = "runtime-benchmarks", test))]
+mod mock;
+pub mod weights;
+
+pub use self::weights::WeightInfo;
+
+use core::marker::PhantomData;
+use frame_support::weights::Weight;
+
+pub struct Pallet<T: Config>(PhantomData<T>);
+pub trait Config: frame_system::Config {}
+
+impl<T: frame_system::Config> pallet_evm_precompile_curve25519::WeightInfo
+	for weights::SubstrateWeight<T>
+{
+	fn curve25519_add_n_points(n: u32) -> Weight {
+		<Self as WeightInfo>::curve25519_add_n_points(n)
+	}
+	fn curve25519_scalar_mul() -> Weight {
+		<Self as WeightInfo>::curve25519_scalar_mul()
+	}
+}
@@ -0,0 +1,88 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Weights for pallet_evm_precompile_benchmarking
+//!
+//! THESE WEIGHTS ARE PLACEHOLDERS, NOT BENCHMARK RESULTS. They are a rough estimate of the cost of
+//! the curve operations, and must be replaced by the output of the benchmarks of this crate before
+//! being used to price precompile gas:
+//!
+//! ./target/release/frontier-template-node benchmark pallet --chain=dev --steps=50 --repeat=20
+//! --pallet=pallet_evm_precompile_benchmarking --extrinsic=* --wasm-execution=compiled
+//! --output=./frame/evm/precompile/benchmarking/src/weights.rs --header=HEADER-APACHE2
+//! --template=./scripts/frame-weight-template.hbs
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(missing_docs)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use core::marker::PhantomData;
+
+/// Weight functions needed for pallet_evm_precompile_benchmarking.
+pub trait WeightInfo {
+	fn curve25519_add_n_points(n: u32, ) -> Weight;
+	fn curve25519_scalar_mul() -> Weight;
+	fn bls12381_pairing_n_pairs(n: u32, ) -> Weight;
+	fn bw6761_pairing_n_pairs(n: u32, ) -> Weight;
+}
+
+/// Weights for pallet_evm_precompile_benchmarking using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	/// The range of component `n` is `[1, 10]`.
+	fn curve25519_add_n_points(n: u32, ) -> Weight {
+		Weight::from_parts(2_164_000, 0)
+			.saturating_add(Weight::from_parts(5_428_000, 0).saturating_mul(n.into()))
+	}
+	fn curve25519_scalar_mul() -> Weight {
+		Weight::from_parts(40_000_000, 0)
+	}
+	/// The range of component `n` is `[1, 10]`.
+	fn bls12381_pairing_n_pairs(n: u32, ) -> Weight {
+		Weight::from_parts(641_000_000, 0)
+			.saturating_add(Weight::from_parts(743_000_000, 0).saturating_mul(n.into()))
+	}
+	/// The range of component `n` is `[1, 10]`.
+	fn bw6761_pairing_n_pairs(n: u32, ) -> Weight {
+		Weight::from_parts(1_520_000_000, 0)
+			.saturating_add(Weight::from_parts(1_730_000_000, 0).saturating_mul(n.into()))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	/// The range of component `n` is `[1, 10]`.
+	fn curve25519_add_n_points(n: u32, ) -> Weight {
+		Weight::from_parts(2_164_000, 0)
+			.saturating_add(Weight::from_parts(5_428_000, 0).saturating_mul(n.into()))
+	}
+	fn curve25519_scalar_mul() -> Weight {
+		Weight::from_parts(40_000_000, 0)
+	}
+	/// The range of component `n` is `[1, 10]`.
+	fn bls12381_pairing_n_pairs(n: u32, ) -> Weight {
+		Weight::from_parts(641_000_000, 0)
+			.saturating_add(Weight::from_parts(743_000_000, 0).saturating_mul(n.into()))
+	}
+	/// The range of component `n` is `[1, 10]`.
+	fn bw6761_pairing_n_pairs(n: u32, ) -> Weight {
+		Weight::from_parts(1_520_000_000, 0)
+			.saturating_add(Weight::from_parts(1_730_000_000, 0).saturating_mul(n.into()))
+	}
+}
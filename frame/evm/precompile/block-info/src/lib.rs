@@ -0,0 +1,93 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Block info precompile. This precompile exposes the block timestamp and the hashes of recent
+//! blocks. Unlike the `BLOCKHASH` opcode, which only sees the last 256 blocks, block hashes are
+//! available for as long as they are kept by `frame_system` (see `BlockHashCount`).
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use core::marker::PhantomData;
+use frame_support::traits::Time;
+use pallet_evm::BlockHashMapping;
+use precompile_utils::{prelude::*, EvmResult};
+use sp_core::{H256, U256};
+use sp_runtime::traits::UniqueSaturatedInto;
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+/// Fixed gas cost charged for each lookup.
+pub const LOOKUP_GAS_COST: u64 = 2_100;
+// Storage key for the timestamp: Twox128(16) + Twox128(16) + Moment(8)
+pub const TIMESTAMP_PROOF_SIZE: u64 = 40;
+// Storage key for a block hash: Twox128(16) + Twox128(16) + Twox64Concat(8) + BlockNumber(4) +
+// Hash(32)
+pub const BLOCK_HASH_PROOF_SIZE: u64 = 76;
+
+#[derive(Debug, Clone)]
+pub struct BlockInfoPrecompile<Runtime>(PhantomData<Runtime>);
+
+#[precompile_utils::precompile]
+impl<Runtime> BlockInfoPrecompile<Runtime>
+where
+	Runtime: pallet_evm::Config,
+{
+	/// Timestamp of the current block, in milliseconds.
+	#[precompile::public("timestamp()")]
+	#[precompile::view]
+	fn timestamp(handle: &mut impl PrecompileHandle) -> EvmResult<U256> {
+		handle.record_cost(LOOKUP_GAS_COST)?;
+		handle.record_external_cost(None, Some(TIMESTAMP_PROOF_SIZE), None)?;
+
+		let now: u128 = <Runtime as pallet_evm::Config>::Timestamp::now().unique_saturated_into();
+		Ok(U256::from(now))
+	}
+
+	/// Hash of the parent block.
+	#[precompile::public("parentHash()")]
+	#[precompile::view]
+	fn parent_hash(handle: &mut impl PrecompileHandle) -> EvmResult<H256> {
+		let current = Self::current_block_number();
+		Self::block_hash_at(handle, U256::from(current.saturating_sub(1)))
+	}
+
+	/// Hash of the given block. Returns zero for the current and future blocks, as well as for
+	/// blocks whose hash is no longer kept by the runtime.
+	#[precompile::public("blockHash(uint256)")]
+	#[precompile::view]
+	fn block_hash(handle: &mut impl PrecompileHandle, number: U256) -> EvmResult<H256> {
+		Self::block_hash_at(handle, number)
+	}
+
+	fn block_hash_at(handle: &mut impl PrecompileHandle, number: U256) -> EvmResult<H256> {
+		handle.record_cost(LOOKUP_GAS_COST)?;
+		handle.record_external_cost(None, Some(BLOCK_HASH_PROOF_SIZE), None)?;
+
+		if number >= U256::from(Self::current_block_number()) {
+			return Ok(H256::default());
+		}
+
+		Ok(<Runtime as pallet_evm::Config>::BlockHashMapping::block_hash(number.as_u32()))
+	}
+
+	fn current_block_number() -> u32 {
+		frame_system::Pallet::<Runtime>::block_number().unique_saturated_into()
+	}
+}
@@ -0,0 +1,88 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+	mock::{ExtBuilder, PCall, Precompiles, PrecompilesValue, Runtime, System, Timestamp},
+	LOOKUP_GAS_COST,
+};
+use precompile_utils::testing::*;
+use sp_core::{H256, U256};
+
+fn precompiles() -> Precompiles<Runtime> {
+	PrecompilesValue::get()
+}
+
+#[test]
+fn timestamp_returns_block_timestamp() {
+	ExtBuilder::default().build().execute_with(|| {
+		Timestamp::set_timestamp(1_700_000_000_000);
+
+		precompiles()
+			.prepare_test(Alice, Precompile1, PCall::timestamp {})
+			.expect_cost(LOOKUP_GAS_COST)
+			.execute_returns(U256::from(1_700_000_000_000u64));
+	})
+}
+
+#[test]
+fn parent_hash_returns_previous_block_hash() {
+	ExtBuilder::default().build().execute_with(|| {
+		let parent_hash = H256::repeat_byte(0x11);
+		frame_system::BlockHash::<Runtime>::insert(9, parent_hash);
+		System::set_block_number(10);
+
+		precompiles()
+			.prepare_test(Alice, Precompile1, PCall::parent_hash {})
+			.expect_cost(LOOKUP_GAS_COST)
+			.execute_returns(parent_hash);
+	})
+}
+
+#[test]
+fn block_hash_reads_beyond_blockhash_opcode_window() {
+	ExtBuilder::default().build().execute_with(|| {
+		let old_hash = H256::repeat_byte(0x22);
+		frame_system::BlockHash::<Runtime>::insert(10, old_hash);
+		// The `BLOCKHASH` opcode only sees the last 256 blocks.
+		System::set_block_number(1_000);
+
+		precompiles()
+			.prepare_test(
+				Alice,
+				Precompile1,
+				PCall::block_hash {
+					number: U256::from(10),
+				},
+			)
+			.expect_cost(LOOKUP_GAS_COST)
+			.execute_returns(old_hash);
+	})
+}
+
+#[test]
+fn block_hash_of_current_or_future_block_is_zero() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(10);
+		frame_system::BlockHash::<Runtime>::insert(10, H256::repeat_byte(0x33));
+
+		for number in [U256::from(10), U256::from(11), U256::MAX] {
+			precompiles()
+				.prepare_test(Alice, Precompile1, PCall::block_hash { number })
+				.execute_returns(H256::default());
+		}
+	})
+}
@@ -21,12 +21,44 @@
 extern crate alloc;
 
 use alloc::vec::Vec;
+use core::marker::PhantomData;
 use curve25519_dalek::{
 	ristretto::{CompressedRistretto, RistrettoPoint},
 	scalar::Scalar,
 	traits::Identity,
 };
-use fp_evm::{ExitError, ExitSucceed, LinearCostPrecompile, PrecompileFailure};
+use fp_evm::{
+	ExitError, ExitSucceed, LinearCostPrecompile, PrecompileFailure, PrecompileHandle,
+	PrecompileOutput, PrecompileResult,
+};
+use frame_support::{traits::Get, weights::Weight};
+
+/// Weight functions needed for the weighted curve25519 precompiles.
+pub trait WeightInfo {
+	fn curve25519_add_n_points(n: u32) -> Weight;
+	fn curve25519_scalar_mul() -> Weight;
+}
+
+/// Placeholder weights for tests only, do not use in production.
+///
+/// Runtimes should use the weights generated by the `pallet-evm-precompile-benchmarking`
+/// benchmarks instead.
+impl WeightInfo for () {
+	fn curve25519_add_n_points(n: u32) -> Weight {
+		Weight::from_parts(5_000_000, 0)
+			.saturating_add(Weight::from_parts(10_000_000, 0).saturating_mul(n.into()))
+	}
+	fn curve25519_scalar_mul() -> Weight {
+		Weight::from_parts(50_000_000, 0)
+	}
+}
+
+/// Converts a weight into gas, rounding up to the next whole unit of gas.
+fn weight_to_gas<WeightPerGas: Get<Weight>>(weight: Weight) -> u64 {
+	weight
+		.ref_time()
+		.div_ceil(WeightPerGas::get().ref_time().max(1))
+}
 
 // Adds at most 10 curve25519 points and returns the CompressedRistretto bytes representation
 pub struct Curve25519Add;
@@ -71,6 +103,30 @@ impl LinearCostPrecompile for Curve25519Add {
 	}
 }
 
+/// [`Curve25519Add`] charging gas from the benchmarked weights `WI` instead of a linear cost,
+/// converted with the runtime `WeightPerGas`.
+pub struct Curve25519AddWeighted<WI, WeightPerGas>(PhantomData<(WI, WeightPerGas)>);
+
+impl<WI, WeightPerGas> fp_evm::Precompile for Curve25519AddWeighted<WI, WeightPerGas>
+where
+	WI: WeightInfo,
+	WeightPerGas: Get<Weight>,
+{
+	fn execute(handle: &mut impl PrecompileHandle) -> PrecompileResult {
+		let n_points = (handle.input().len() / 32) as u32;
+		let weight = WI::curve25519_add_n_points(n_points);
+		handle.record_cost(weight_to_gas::<WeightPerGas>(weight))?;
+		handle.record_external_cost(None, Some(weight.proof_size()), None)?;
+
+		let (exit_status, output) =
+			<Curve25519Add as LinearCostPrecompile>::execute(handle.input(), 0)?;
+		Ok(PrecompileOutput {
+			exit_status,
+			output,
+		})
+	}
+}
+
 // Multiplies a scalar field element with an elliptic curve point
 pub struct Curve25519ScalarMul;
 
@@ -107,10 +163,78 @@ impl LinearCostPrecompile for Curve25519ScalarMul {
 	}
 }
 
+/// [`Curve25519ScalarMul`] charging gas from the benchmarked weights `WI` instead of a linear
+/// cost, converted with the runtime `WeightPerGas`.
+pub struct Curve25519ScalarMulWeighted<WI, WeightPerGas>(PhantomData<(WI, WeightPerGas)>);
+
+impl<WI, WeightPerGas> fp_evm::Precompile for Curve25519ScalarMulWeighted<WI, WeightPerGas>
+where
+	WI: WeightInfo,
+	WeightPerGas: Get<Weight>,
+{
+	fn execute(handle: &mut impl PrecompileHandle) -> PrecompileResult {
+		let weight = WI::curve25519_scalar_mul();
+		handle.record_cost(weight_to_gas::<WeightPerGas>(weight))?;
+		handle.record_external_cost(None, Some(weight.proof_size()), None)?;
+
+		let (exit_status, output) =
+			<Curve25519ScalarMul as LinearCostPrecompile>::execute(handle.input(), 0)?;
+		Ok(PrecompileOutput {
+			exit_status,
+			output,
+		})
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
 	use curve25519_dalek::constants;
+	use fp_evm::Context;
+	use frame_support::parameter_types;
+	use pallet_evm_test_vector_support::MockHandle;
+
+	parameter_types! {
+		pub WeightPerGas: Weight = Weight::from_parts(20_000, 0);
+	}
+
+	fn execute_weighted<P: fp_evm::Precompile>(input: Vec<u8>) -> (Vec<u8>, u64) {
+		let context = Context {
+			address: Default::default(),
+			caller: Default::default(),
+			apparent_value: From::from(0),
+		};
+		let mut handle = MockHandle::new(input, None, context);
+		let output = P::execute(&mut handle).expect("valid input").output;
+		(output, handle.gas_used)
+	}
+
+	#[test]
+	fn test_weighted_variants_charge_the_weights() {
+		let point = constants::RISTRETTO_BASEPOINT_POINT.compress().to_bytes();
+		let (output, gas_used) =
+			execute_weighted::<Curve25519AddWeighted<(), WeightPerGas>>(point.repeat(2));
+		assert_eq!(
+			output,
+			<Curve25519Add as LinearCostPrecompile>::execute(&point.repeat(2), 0)
+				.unwrap()
+				.1
+		);
+		// 5_000_000 + 2 * 10_000_000 weight at 20_000 weight per gas.
+		assert_eq!(gas_used, 1_250);
+
+		let mut input = Scalar::from(999u64).to_bytes().to_vec();
+		input.extend_from_slice(&point);
+		let (output, gas_used) =
+			execute_weighted::<Curve25519ScalarMulWeighted<(), WeightPerGas>>(input.clone());
+		assert_eq!(
+			output,
+			<Curve25519ScalarMul as LinearCostPrecompile>::execute(&input, 0)
+				.unwrap()
+				.1
+		);
+		assert_eq!(gas_used, 2_500);
+	}
 
 	#[test]
 	fn test_sum() -> Result<(), PrecompileFailure> {
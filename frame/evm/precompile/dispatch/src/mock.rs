@@ -20,7 +20,7 @@
 use core::str::FromStr;
 use frame_support::{
 	derive_impl, parameter_types,
-	traits::{ConstU32, FindAuthor},
+	traits::{ConstBool, ConstU32, ExistenceRequirement, FindAuthor},
 	weights::Weight,
 	ConsensusEngineId,
 };
@@ -131,9 +131,12 @@ impl FindAuthor<H160> for FindAuthorTruncated {
 	}
 }
 parameter_types! {
-	pub BlockGasLimit: U256 = U256::max_value();
+	pub BlockGasLimit: U256 = U256::from(15_000_000);
 	pub WeightPerGas: Weight = Weight::from_parts(20_000, 0);
 	pub SuicideQuickClearLimit: u32 = 0;
+	pub SuicideIdleClearLimit: u32 = 0;
+	pub const WithdrawExistenceRequirement: ExistenceRequirement = ExistenceRequirement::AllowDeath;
+	pub const Reentrancy: pallet_evm::ReentrancyPolicy = pallet_evm::ReentrancyPolicy::Forbid;
 }
 impl pallet_evm::Config for Test {
 	type AccountProvider = pallet_evm::FrameSystemAccountProvider<Self>;
@@ -145,6 +148,7 @@ impl pallet_evm::Config for Test {
 	type CallOrigin = EnsureAddressRoot<Self::AccountId>;
 
 	type WithdrawOrigin = EnsureAddressNever<Self::AccountId>;
+	type WithdrawExistenceRequirement = WithdrawExistenceRequirement;
 	type AddressMapping = IdentityAddressMapping;
 	type Currency = Balances;
 
@@ -158,8 +162,16 @@ impl pallet_evm::Config for Test {
 	type OnCreate = ();
 	type FindAuthor = FindAuthorTruncated;
 	type SuicideQuickClearLimit = SuicideQuickClearLimit;
+	type SuicideIdleClearLimit = SuicideIdleClearLimit;
 	type GasLimitPovSizeRatio = ();
 	type GasLimitStorageGrowthRatio = ();
+	type MaxAccountBalance = ();
+	type Reentrancy = Reentrancy;
+	type RestrictSelfdestruct = ConstBool<false>;
+	type RemoveTouchedEmptyAccounts = ConstBool<false>;
+	type PrevRandao = ();
+	type MaxCallDataSize = ConstU32<{ u32::MAX }>;
+	type BlockHashWindow = ();
 	type Timestamp = Timestamp;
 	type WeightInfo = ();
 }
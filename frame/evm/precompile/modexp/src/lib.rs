@@ -31,7 +31,15 @@ use fp_evm::{
 	PrecompileResult,
 };
 
-pub struct Modexp;
+/// Modexp precompile with the EIP-198 bound of 1024 bytes on each input length.
+pub type Modexp = ModexpWithMaxLength<1024>;
+
+/// Modexp precompile accepting base, exponent and modulus lengths of up to `MAX_LENGTH` bytes.
+///
+/// Chains that need larger operands can register this with a raised bound. The
+/// EIP-2565 pricing already scales quadratically with the operand length, so no
+/// further gas adjustment is needed.
+pub struct ModexpWithMaxLength<const MAX_LENGTH: usize>;
 
 const MIN_GAS_COST: u64 = 200;
 
@@ -53,8 +61,8 @@ fn calculate_gas_cost(
 
 		// Note: can't overflow because we take words to be some u64 value / 8, which is
 		// necessarily less than sqrt(u64::MAX).
-		// Additionally, both base_length and mod_length are bounded to 1024, so this has
-		// an upper bound of roughly (1024 / 8) squared
+		// Additionally, both base_length and mod_length are bounded to `MAX_LENGTH`, so
+		// this has an upper bound of roughly (MAX_LENGTH / 8) squared
 		words * words
 	}
 
@@ -71,11 +79,11 @@ fn calculate_gas_cost(
 			// (8 * (exp_length - 32)) + ((exponent & (2**256 - 1)).bit_length() - 1)
 			//
 			// Notes:
-			// * exp_length is bounded to 1024 and is > 32
+			// * exp_length is bounded to `MAX_LENGTH` and is > 32
 			// * exponent can be zero, so we subtract 1 after adding the other terms (whose sum
 			//   must be > 0)
 			// * the addition can't overflow because the terms are both capped at roughly
-			//   8 * max size of exp_length (1024)
+			//   8 * max size of exp_length (`MAX_LENGTH`)
 			// * the EIP spec is written in python, in which (exponent & (2**256 - 1)) takes the
 			//   FIRST 32 bytes. However this `BigUint` `&` operator takes the LAST 32 bytes.
 			//   We thus instead take the bytes manually.
@@ -91,7 +99,7 @@ fn calculate_gas_cost(
 	let iteration_count = calculate_iteration_count(exponent, exponent_bytes);
 	max(
 		MIN_GAS_COST,
-		multiplication_complexity * iteration_count / 3,
+		multiplication_complexity.saturating_mul(iteration_count) / 3,
 	)
 	.saturating_mul(if mod_is_even { 20 } else { 1 })
 }
@@ -122,14 +130,14 @@ fn read_input(source: &[u8], target: &mut [u8], source_offset: &mut usize) {
 // 6) modulus, size as described above
 //
 //
-// NOTE: input sizes are bound to 1024 bytes, with the expectation
-//       that gas limits would be applied before actual computation.
+// NOTE: input sizes are bound to `MAX_LENGTH` bytes (1024 for `Modexp`), with the
+//       expectation that gas limits would be applied before actual computation.
 //
 //       maximum stack size will also prevent abuse.
 //
 //       see: https://eips.ethereum.org/EIPS/eip-198
 
-impl Precompile for Modexp {
+impl<const MAX_LENGTH: usize> Precompile for ModexpWithMaxLength<MAX_LENGTH> {
 	fn execute(handle: &mut impl PrecompileHandle) -> PrecompileResult {
 		let input = handle.input();
 		let mut input_offset = 0;
@@ -144,7 +152,7 @@ impl Precompile for Modexp {
 		read_input(input, &mut mod_len_buf, &mut input_offset);
 
 		// reasonable assumption: this must fit within the Ethereum EVM's max stack size
-		let max_size_big = BigUint::from_u32(1024).expect("can't create BigUint");
+		let max_size_big = BigUint::from_usize(MAX_LENGTH).expect("can't create BigUint");
 
 		let base_len_big = BigUint::from_bytes_be(&base_len_buf);
 		if base_len_big > max_size_big {
@@ -521,4 +529,81 @@ mod tests {
 
 		assert_eq!(handle.gas_used, 7104 * 20); // gas used when ran in geth (x20)
 	}
+
+	fn raised_bound_input(mod_len: usize) -> Vec<u8> {
+		let mut input = Vec::new();
+		for len in [1usize, 1, mod_len] {
+			let mut len_buf = [0u8; 32];
+			len_buf[24..].copy_from_slice(&(len as u64).to_be_bytes());
+			input.extend_from_slice(&len_buf);
+		}
+		// 2 ^ 1 % (2^(8 * mod_len) - 1) == 2
+		input.push(2);
+		input.push(1);
+		input.extend(core::iter::repeat(0xff).take(mod_len));
+		input
+	}
+
+	#[test]
+	fn test_default_bound_rejects_long_modulus() {
+		let context: Context = Context {
+			address: Default::default(),
+			caller: Default::default(),
+			apparent_value: From::from(0),
+		};
+
+		let mut handle = MockHandle::new(raised_bound_input(2048), Some(100_000), context);
+
+		match Modexp::execute(&mut handle) {
+			Ok(_) => {
+				panic!("Test not expected to pass");
+			}
+			Err(e) => {
+				assert_eq!(
+					e,
+					PrecompileFailure::Error {
+						exit_status: ExitError::Other("unreasonably large modulus length".into())
+					}
+				);
+			}
+		}
+	}
+
+	#[test]
+	fn test_raised_bound_accepts_long_modulus() {
+		let context: Context = Context {
+			address: Default::default(),
+			caller: Default::default(),
+			apparent_value: From::from(0),
+		};
+
+		let mut handle = MockHandle::new(raised_bound_input(2048), Some(100_000), context);
+
+		let precompile_result = ModexpWithMaxLength::<4096>::execute(&mut handle)
+			.expect("Modexp::execute() returned error");
+
+		assert_eq!(precompile_result.output.len(), 2048); // should be same length as mod
+		let result = BigUint::from_bytes_be(&precompile_result.output[..]);
+		assert_eq!(result, BigUint::from(2u32));
+		// (2048 / 8) ^ 2 * 1 / 3, odd modulus
+		assert_eq!(handle.gas_used, 21845);
+	}
+
+	#[test]
+	fn test_raised_bound_still_enforced() {
+		let context: Context = Context {
+			address: Default::default(),
+			caller: Default::default(),
+			apparent_value: From::from(0),
+		};
+
+		let mut handle = MockHandle::new(raised_bound_input(4097), Some(100_000), context);
+
+		assert_eq!(
+			ModexpWithMaxLength::<4096>::execute(&mut handle).err(),
+			Some(PrecompileFailure::Error {
+				exit_status: ExitError::Other("unreasonably large modulus length".into())
+			})
+		);
+	}
 }
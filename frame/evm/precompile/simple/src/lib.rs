@@ -26,18 +26,33 @@ use core::cmp::min;
 use fp_evm::{ExitError, ExitSucceed, LinearCostPrecompile, PrecompileFailure};
 
 /// The identity precompile.
-pub struct Identity;
+pub type Identity = BoundedIdentity<{ usize::MAX }>;
 
-impl LinearCostPrecompile for Identity {
+/// The identity precompile, rejecting inputs longer than `MAX_INPUT_LENGTH` bytes.
+///
+/// The linear cost is charged before the input is copied, and an oversized input fails with
+/// `OutOfGas` instead of being copied into the output.
+pub struct BoundedIdentity<const MAX_INPUT_LENGTH: usize>;
+
+impl<const MAX_INPUT_LENGTH: usize> LinearCostPrecompile for BoundedIdentity<MAX_INPUT_LENGTH> {
 	const BASE: u64 = 15;
 	const WORD: u64 = 3;
 
 	fn execute(input: &[u8], _: u64) -> Result<(ExitSucceed, Vec<u8>), PrecompileFailure> {
+		if input.len() > MAX_INPUT_LENGTH {
+			return Err(PrecompileFailure::Error {
+				exit_status: ExitError::OutOfGas,
+			});
+		}
 		Ok((ExitSucceed::Returned, input.to_vec()))
 	}
 }
 
 /// The ecrecover precompile.
+///
+/// Matches mainnet behavior: returns the left-padded 32-byte signer address, or an empty output
+/// when `v` is not 27 or 28 or the signature cannot be recovered. High-s signatures are accepted,
+/// since malleability is only restricted for transaction signatures.
 pub struct ECRecover;
 
 impl LinearCostPrecompile for ECRecover {
@@ -137,9 +152,9 @@ impl LinearCostPrecompile for ECRecoverPublicKey {
 #[cfg(test)]
 mod tests {
 	use super::*;
-	use pallet_evm_test_vector_support::test_precompile_test_vectors;
+	use fp_evm::{Context, Precompile};
+	use pallet_evm_test_vector_support::{test_precompile_test_vectors, MockHandle};
 
-	// TODO: this fails on the test "InvalidHighV-bits-1" where it is expected to return ""
 	#[test]
 	fn process_consensus_tests_for_ecrecover() -> Result<(), String> {
 		test_precompile_test_vectors::<ECRecover>("../testdata/ecRecover.json")?;
@@ -157,4 +172,45 @@ mod tests {
 		test_precompile_test_vectors::<Ripemd160>("../testdata/common_ripemd.json")?;
 		Ok(())
 	}
+
+	fn context() -> Context {
+		Context {
+			address: Default::default(),
+			caller: Default::default(),
+			apparent_value: From::from(0),
+		}
+	}
+
+	#[test]
+	fn identity_input_beyond_gas_limit_fails_before_copy() {
+		// 1 MiB of input costs 15 + 3 * 32768 gas, far above the limit.
+		let mut handle = MockHandle::new(vec![0u8; 1 << 20], Some(10_000), context());
+
+		assert_eq!(
+			<Identity as Precompile>::execute(&mut handle).err(),
+			Some(PrecompileFailure::Error {
+				exit_status: ExitError::OutOfGas
+			})
+		);
+		assert_eq!(handle.gas_used, 0);
+	}
+
+	#[test]
+	fn bounded_identity_rejects_oversized_input() {
+		let mut handle = MockHandle::new(vec![0u8; 33], Some(10_000), context());
+
+		assert_eq!(
+			<BoundedIdentity<32> as Precompile>::execute(&mut handle).err(),
+			Some(PrecompileFailure::Error {
+				exit_status: ExitError::OutOfGas
+			})
+		);
+		// The linear cost of two words was charged before the size check.
+		assert_eq!(handle.gas_used, 15 + 3 * 2);
+
+		let mut handle = MockHandle::new(vec![0x42u8; 32], Some(10_000), context());
+		let output = <BoundedIdentity<32> as Precompile>::execute(&mut handle)
+			.expect("input within the bound");
+		assert_eq!(output.output, vec![0x42u8; 32]);
+	}
 }
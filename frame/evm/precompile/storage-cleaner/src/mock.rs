@@ -18,10 +18,10 @@
 //! Test mock for unit tests and benchmarking
 
 use crate::{StorageCleanerPrecompile, StorageCleanerPrecompileCall};
-use frame_support::{parameter_types, weights::Weight};
+use frame_support::{parameter_types, traits::ExistenceRequirement, weights::Weight};
 use pallet_evm::{EnsureAddressNever, EnsureAddressRoot, IdentityAddressMapping};
 use precompile_utils::{precompile_set::*, testing::*};
-use sp_core::{ConstU32, H256, U256};
+use sp_core::{ConstBool, ConstU32, H256, U256};
 use sp_runtime::{
 	traits::{BlakeTwo256, IdentityLookup},
 	BuildStorage,
@@ -123,6 +123,9 @@ parameter_types! {
 	pub WeightPerGas: Weight = Weight::from_parts(20_000, 0);
 	pub PrecompilesValue: Precompiles<Runtime> = Precompiles::new();
 	pub SuicideQuickClearLimit: u32 = 0;
+	pub SuicideIdleClearLimit: u32 = 0;
+	pub const WithdrawExistenceRequirement: ExistenceRequirement = ExistenceRequirement::AllowDeath;
+	pub const Reentrancy: pallet_evm::ReentrancyPolicy = pallet_evm::ReentrancyPolicy::Forbid;
 }
 
 impl pallet_evm::Config for Runtime {
@@ -132,6 +135,7 @@ impl pallet_evm::Config for Runtime {
 	type WeightPerGas = WeightPerGas;
 	type CallOrigin = EnsureAddressRoot<Self::AccountId>;
 	type WithdrawOrigin = EnsureAddressNever<Self::AccountId>;
+	type WithdrawExistenceRequirement = WithdrawExistenceRequirement;
 	type AddressMapping = IdentityAddressMapping;
 	type Currency = Balances;
 	type RuntimeEvent = RuntimeEvent;
@@ -146,9 +150,17 @@ impl pallet_evm::Config for Runtime {
 	type OnCreate = ();
 	type GasLimitPovSizeRatio = GasLimitPovSizeRatio;
 	type GasLimitStorageGrowthRatio = GasLimitStorageGrowthRatio;
+	type MaxAccountBalance = ();
+	type Reentrancy = Reentrancy;
+	type RestrictSelfdestruct = ConstBool<false>;
+	type RemoveTouchedEmptyAccounts = ConstBool<false>;
+	type PrevRandao = ();
+	type MaxCallDataSize = ConstU32<{ u32::MAX }>;
+	type BlockHashWindow = ();
 	type Timestamp = Timestamp;
 	type WeightInfo = ();
 	type SuicideQuickClearLimit = SuicideQuickClearLimit;
+	type SuicideIdleClearLimit = SuicideIdleClearLimit;
 }
 
 /// Build test externalities, prepopulated with data for testing the precompile.
@@ -0,0 +1,259 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Precompile verifying the signatures of Substrate accounts, so that contracts can check
+//! messages signed by sr25519 or ed25519 keys.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![warn(unused_crate_dependencies)]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+use fp_evm::{
+	ExitError, ExitSucceed, Precompile, PrecompileFailure, PrecompileHandle, PrecompileOutput,
+	PrecompileResult,
+};
+use sp_core::{ed25519, sr25519, ConstU64, Get};
+
+/// Scheme byte selecting sr25519 verification.
+pub const SCHEME_SR25519: u8 = 0;
+/// Scheme byte selecting ed25519 verification.
+pub const SCHEME_ED25519: u8 = 1;
+
+const PUBLIC_KEY_LEN: usize = 32;
+const SIGNATURE_LEN: usize = 64;
+
+/// Verifies a sr25519 or ed25519 signature.
+///
+/// The input is laid out as:
+/// 1) scheme (1 byte), [`SCHEME_SR25519`] or [`SCHEME_ED25519`]
+/// 2) public key (32 bytes)
+/// 3) message (any length)
+/// 4) signature (64 bytes)
+///
+/// The output is a 32 bytes big-endian boolean, `1` if the signature is valid. The gas cost is
+/// the base cost of the scheme, as given by `Sr25519Cost` and `Ed25519Cost`, plus `WordCost` per
+/// 32 bytes word of the message, which is hashed by the verification.
+pub struct SubstrateSignatureVerify<
+	Sr25519Cost = ConstU64<3_000>,
+	Ed25519Cost = ConstU64<2_000>,
+	WordCost = ConstU64<12>,
+>(PhantomData<(Sr25519Cost, Ed25519Cost, WordCost)>);
+
+impl<Sr25519Cost, Ed25519Cost, WordCost> Precompile
+	for SubstrateSignatureVerify<Sr25519Cost, Ed25519Cost, WordCost>
+where
+	Sr25519Cost: Get<u64>,
+	Ed25519Cost: Get<u64>,
+	WordCost: Get<u64>,
+{
+	fn execute(handle: &mut impl PrecompileHandle) -> PrecompileResult {
+		let input = handle.input();
+		if input.len() < 1 + PUBLIC_KEY_LEN + SIGNATURE_LEN {
+			return Err(PrecompileFailure::Error {
+				exit_status: ExitError::Other("input must contain at least 97 bytes".into()),
+			});
+		}
+
+		let scheme = input[0];
+		let mut public_key = [0u8; PUBLIC_KEY_LEN];
+		public_key.copy_from_slice(&input[1..1 + PUBLIC_KEY_LEN]);
+		let (message, signature) =
+			input[1 + PUBLIC_KEY_LEN..].split_at(input.len() - 1 - PUBLIC_KEY_LEN - SIGNATURE_LEN);
+		let mut signature_bytes = [0u8; SIGNATURE_LEN];
+		signature_bytes.copy_from_slice(signature);
+		let message = message.to_vec();
+		let words = (message.len() as u64).saturating_add(31) / 32;
+		let message_cost = WordCost::get().saturating_mul(words);
+
+		let valid = match scheme {
+			SCHEME_SR25519 => {
+				handle.record_cost(Sr25519Cost::get().saturating_add(message_cost))?;
+				sp_io::crypto::sr25519_verify(
+					&sr25519::Signature::from_raw(signature_bytes),
+					&message,
+					&sr25519::Public::from_raw(public_key),
+				)
+			}
+			SCHEME_ED25519 => {
+				handle.record_cost(Ed25519Cost::get().saturating_add(message_cost))?;
+				sp_io::crypto::ed25519_verify(
+					&ed25519::Signature::from_raw(signature_bytes),
+					&message,
+					&ed25519::Public::from_raw(public_key),
+				)
+			}
+			_ => {
+				return Err(PrecompileFailure::Error {
+					exit_status: ExitError::Other("unknown signature scheme".into()),
+				})
+			}
+		};
+
+		let mut output = [0u8; 32];
+		output[31] = valid as u8;
+
+		Ok(PrecompileOutput {
+			exit_status: ExitSucceed::Returned,
+			output: output.to_vec(),
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use fp_evm::Context;
+	use pallet_evm_test_vector_support::MockHandle;
+	use sp_core::Pair;
+
+	fn input(scheme: u8, public_key: &[u8], message: &[u8], signature: &[u8]) -> Vec<u8> {
+		let mut input = Vec::new();
+		input.push(scheme);
+		input.extend_from_slice(public_key);
+		input.extend_from_slice(message);
+		input.extend_from_slice(signature);
+		input
+	}
+
+	fn execute(input: Vec<u8>) -> (Result<Vec<u8>, PrecompileFailure>, u64) {
+		let context: Context = Context {
+			address: Default::default(),
+			caller: Default::default(),
+			apparent_value: From::from(0),
+		};
+		let mut handle = MockHandle::new(input, None, context);
+		let result =
+			SubstrateSignatureVerify::<ConstU64<3_000>, ConstU64<2_000>, ConstU64<12>>::execute(
+				&mut handle,
+			)
+			.map(|output| output.output);
+		(result, handle.gas_used)
+	}
+
+	fn bool_output(valid: bool) -> Vec<u8> {
+		let mut output = vec![0u8; 32];
+		output[31] = valid as u8;
+		output
+	}
+
+	#[test]
+	fn test_verify_ed25519_rfc8032_vector() {
+		// RFC 8032, section 7.1, TEST 2.
+		let public_key =
+			hex::decode("3d4017c3e843895a92b70aa74d1b7ebc9c982ccf2ec4968cc0cd55f12af4660c")
+				.unwrap();
+		let message = hex::decode("72").unwrap();
+		let signature = hex::decode(
+			"92a009a9f0d4cab8720e820b5f642540a2b27b5416503f8fb3762223ebdb69da\
+			085ac1e43e15996e458f3613d0f11d8c387b2eaeb4302aeeb00d291612bb0c00",
+		)
+		.unwrap();
+
+		let (result, gas_used) = execute(input(SCHEME_ED25519, &public_key, &message, &signature));
+		assert_eq!(result, Ok(bool_output(true)));
+		// One word of message.
+		assert_eq!(gas_used, 2_000 + 12);
+
+		let (result, _) = execute(input(SCHEME_ED25519, &public_key, b"s", &signature));
+		assert_eq!(result, Ok(bool_output(false)));
+	}
+
+	#[test]
+	fn test_verify_sr25519() {
+		let pair = sr25519::Pair::from_seed(&[7u8; 32]);
+		let message = b"frontier substrate signature precompile";
+		let signature = pair.sign(message);
+
+		let (result, gas_used) = execute(input(
+			SCHEME_SR25519,
+			&pair.public().0,
+			message,
+			&signature.0,
+		));
+		assert_eq!(result, Ok(bool_output(true)));
+		// The message is 39 bytes, two words.
+		assert_eq!(gas_used, 3_000 + 2 * 12);
+
+		// A signature is not valid for another key, nor under the other scheme.
+		let other = sr25519::Pair::from_seed(&[8u8; 32]);
+		let (result, _) = execute(input(
+			SCHEME_SR25519,
+			&other.public().0,
+			message,
+			&signature.0,
+		));
+		assert_eq!(result, Ok(bool_output(false)));
+
+		let (result, _) = execute(input(
+			SCHEME_ED25519,
+			&pair.public().0,
+			message,
+			&signature.0,
+		));
+		assert_eq!(result, Ok(bool_output(false)));
+	}
+
+	#[test]
+	fn test_verify_ed25519_signed_message() {
+		let pair = ed25519::Pair::from_seed(&[7u8; 32]);
+		let message = b"frontier substrate signature precompile";
+		let signature = pair.sign(message);
+
+		let (result, _) = execute(input(
+			SCHEME_ED25519,
+			&pair.public().0,
+			message,
+			&signature.0,
+		));
+		assert_eq!(result, Ok(bool_output(true)));
+
+		// The cost grows with the length of the message.
+		let message = [0x42u8; 1024];
+		let signature = pair.sign(&message);
+		let (result, gas_used) = execute(input(
+			SCHEME_ED25519,
+			&pair.public().0,
+			&message,
+			&signature.0,
+		));
+		assert_eq!(result, Ok(bool_output(true)));
+		assert_eq!(gas_used, 2_000 + 32 * 12);
+	}
+
+	#[test]
+	fn test_invalid_input() {
+		let (result, gas_used) = execute(vec![SCHEME_ED25519; 96]);
+		assert_eq!(
+			result,
+			Err(PrecompileFailure::Error {
+				exit_status: ExitError::Other("input must contain at least 97 bytes".into())
+			})
+		);
+		assert_eq!(gas_used, 0);
+
+		let (result, _) = execute(input(2, &[0u8; 32], b"", &[0u8; 64]));
+		assert_eq!(
+			result,
+			Err(PrecompileFailure::Error {
+				exit_status: ExitError::Other("unknown signature scheme".into())
+			})
+		);
+	}
+}
@@ -31,6 +31,12 @@ benchmarks! {
 		assert!(result.is_err());
 		assert_eq!(result.unwrap_err(), sp_runtime::DispatchError::BadOrigin);
 	}
+
+	set_paused {
+	}: _(RawOrigin::Root, true)
+	verify {
+		assert!(Paused::<T>::get());
+	}
 }
 
 // impl_benchmark_test_suite!(Pallet, crate::tests::new_test_ext(), crate::mock::Test);
@@ -60,6 +60,7 @@ extern crate alloc;
 #[cfg(feature = "runtime-benchmarks")]
 pub mod benchmarking;
 
+pub mod migrations;
 #[cfg(test)]
 mod mock;
 pub mod runner;
@@ -74,7 +75,7 @@ pub use evm::{
 };
 use hash_db::Hasher;
 use impl_trait_for_tuples::impl_for_tuples;
-use scale_codec::{Decode, Encode, MaxEncodedLen};
+use scale_codec::{Decode, Encode, FullCodec, MaxEncodedLen};
 use scale_info::TypeInfo;
 // Substrate
 use frame_support::{
@@ -95,7 +96,9 @@ use frame_support::{
 use frame_system::RawOrigin;
 use sp_core::{H160, H256, U256};
 use sp_runtime::{
-	traits::{BadOrigin, NumberFor, Saturating, UniqueSaturatedInto, Zero},
+	traits::{
+		BadOrigin, Bounded, Convert, ConvertInto, NumberFor, Saturating, UniqueSaturatedInto, Zero,
+	},
 	AccountId32, DispatchErrorWithPostInfo,
 };
 // Frontier
@@ -120,7 +123,10 @@ pub mod pallet {
 	use frame_support::pallet_prelude::*;
 	use frame_system::pallet_prelude::*;
 
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
 	#[pallet::pallet]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	#[pallet::without_storage_info]
 	pub struct Pallet<T>(PhantomData<T>);
 
@@ -134,6 +140,9 @@ pub mod pallet {
 		type FeeCalculator: FeeCalculator;
 
 		/// Maps Ethereum gas to Substrate weight.
+		///
+		/// [`FixedGasWeightMapping`] truncates weight to gas, while
+		/// [`RoundingUpGasWeightMapping`] rounds it up so that gas is never under-reported.
 		type GasWeightMapping: GasWeightMapping;
 
 		/// Weight corresponding to a gas unit.
@@ -151,6 +160,10 @@ pub mod pallet {
 		#[pallet::no_default_bounds]
 		type WithdrawOrigin: EnsureAddressOrigin<Self::RuntimeOrigin, Success = AccountIdOf<Self>>;
 
+		/// Existence requirement of the withdrawing account on `withdraw`. Use `KeepAlive` to
+		/// prevent full withdrawals from reaping the account.
+		type WithdrawExistenceRequirement: Get<ExistenceRequirement>;
+
 		/// Mapping from address to account id.
 		#[pallet::no_default_bounds]
 		type AddressMapping: AddressMapping<AccountIdOf<Self>>;
@@ -170,6 +183,7 @@ pub mod pallet {
 		/// Chain ID of EVM.
 		type ChainId: Get<u64>;
 		/// The block gas limit. Can be a simple constant, or an adjustment algorithm in another pallet.
+		/// Its weight, as given by `GasWeightMapping`, must fit in the maximum block weight.
 		type BlockGasLimit: Get<U256>;
 
 		/// EVM execution runner.
@@ -195,9 +209,47 @@ pub mod pallet {
 		/// Define the quick clear limit of storage clearing when a contract suicides. Set to 0 to disable it.
 		type SuicideQuickClearLimit: Get<u32>;
 
+		/// Maximum number of storage entries of suicided contracts cleared in `on_idle` per block.
+		/// Contracts left over by the quick clear are eventually removed, along with their
+		/// provider account. Set to 0 to disable it.
+		type SuicideIdleClearLimit: Get<u32>;
+
 		/// Gas limit storage growth ratio.
 		type GasLimitStorageGrowthRatio: Get<u64>;
 
+		/// The maximum balance an account may reach through an EVM value transfer. Transfers that
+		/// would push the recipient above it fail. `None` disables the cap.
+		type MaxAccountBalance: Get<Option<U256>>;
+
+		/// Whether an EVM execution may be started while another one is in progress, e.g. by a
+		/// precompile dispatching a call that enters the EVM again. `ReentrancyPolicy::Allow`
+		/// keeps the behavior of runtimes built without the deprecated `forbid-evm-reentrancy`
+		/// feature, `ReentrancyPolicy::Forbid` the one of runtimes built with it.
+		type Reentrancy: Get<ReentrancyPolicy>;
+
+		/// Whether `SELFDESTRUCT` follows the Cancun semantics of EIP-6780: only a contract
+		/// created in the same transaction is removed, any other contract merely sends its
+		/// balance to the target and keeps its code and storage.
+		type RestrictSelfdestruct: Get<bool>;
+
+		/// Whether, under EIP-161, the storage of the accounts an execution touched and left empty
+		/// is cleared once the execution is done, paid with the gas left. Disabled, such accounts
+		/// are kept as they are.
+		type RemoveTouchedEmptyAccounts: Get<bool>;
+
+		/// Randomness of the current block, returned by `PREVRANDAO` (formerly `DIFFICULTY`).
+		/// `None`, the default, keeps the value of these opcodes at zero.
+		type PrevRandao: Get<Option<H256>>;
+
+		/// Maximum size in bytes of the input of the `call`, `create` and `create2` extrinsics,
+		/// which otherwise is only bounded by the block length. Also enforced by the runner when
+		/// it validates a transactional execution.
+		type MaxCallDataSize: Get<u32>;
+
+		/// Number of blocks before the current one whose hash `BLOCKHASH` returns, 256 on
+		/// Ethereum. `None`, the default, returns every hash the `BlockHashMapping` knows of.
+		type BlockHashWindow: Get<Option<u32>>;
+
 		/// Get the timestamp for the current block.
 		#[pallet::no_default]
 		type Timestamp: Time;
@@ -206,6 +258,10 @@ pub mod pallet {
 		type WeightInfo: WeightInfo;
 
 		/// EVM config used in the module.
+		///
+		/// EIP-3860 is controlled by its `max_initcode_size`: when set, create transactions are
+		/// charged the init code word cost and init code larger than the limit is rejected with
+		/// [`Error::CreateContractSizeLimit`]. Set it to `None` to disable both.
 		fn config() -> &'static EvmConfig {
 			&SHANGHAI_CONFIG
 		}
@@ -214,7 +270,11 @@ pub mod pallet {
 	pub mod config_preludes {
 		use super::*;
 		use core::str::FromStr;
-		use frame_support::{derive_impl, parameter_types, ConsensusEngineId};
+		use frame_support::{
+			derive_impl, parameter_types,
+			traits::{ConstBool, ConstU32},
+			ConsensusEngineId,
+		};
 		use sp_runtime::traits::BlakeTwo256;
 
 		pub struct TestDefaultConfig;
@@ -237,12 +297,17 @@ pub mod pallet {
 			pub const GasLimitStorageGrowthRatio: u64 = BLOCK_GAS_LIMIT.saturating_div(MAX_STORAGE_GROWTH);
 			pub WeightPerGas: Weight = Weight::from_parts(20_000, 0);
 			pub SuicideQuickClearLimit: u32 = 0;
+			pub SuicideIdleClearLimit: u32 = 100;
+			pub const WithdrawExistenceRequirement: ExistenceRequirement =
+				ExistenceRequirement::AllowDeath;
+			pub const Reentrancy: ReentrancyPolicy = ReentrancyPolicy::Allow;
 		}
 
 		#[register_default_impl(TestDefaultConfig)]
 		impl DefaultConfig for TestDefaultConfig {
 			type CallOrigin = EnsureAddressRoot<Self::AccountId>;
 			type WithdrawOrigin = EnsureAddressNever<Self::AccountId>;
+			type WithdrawExistenceRequirement = WithdrawExistenceRequirement;
 			type AddressMapping = HashedAddressMapping<BlakeTwo256>;
 			type FeeCalculator = FixedGasPrice;
 			type GasWeightMapping = FixedGasWeightMapping<Self>;
@@ -258,7 +323,15 @@ pub mod pallet {
 			type FindAuthor = FindAuthorTruncated;
 			type GasLimitPovSizeRatio = GasLimitPovSizeRatio;
 			type GasLimitStorageGrowthRatio = GasLimitStorageGrowthRatio;
+			type MaxAccountBalance = ();
+			type Reentrancy = Reentrancy;
+			type RestrictSelfdestruct = ConstBool<false>;
+			type RemoveTouchedEmptyAccounts = ConstBool<false>;
+			type PrevRandao = ();
+			type MaxCallDataSize = ConstU32<{ u32::MAX }>;
+			type BlockHashWindow = ();
 			type SuicideQuickClearLimit = SuicideQuickClearLimit;
+			type SuicideIdleClearLimit = SuicideIdleClearLimit;
 			type WeightInfo = ();
 		}
 
@@ -286,6 +359,33 @@ pub mod pallet {
 		}
 	}
 
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_idle(_: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+			Self::clear_suicided(remaining_weight)
+		}
+
+		fn integrity_test() {
+			// A block filled up to `BlockGasLimit` must not exceed the block weight, otherwise
+			// the advertised gas limit can never be reached.
+			let block_gas_limit = T::BlockGasLimit::get().min(u64::MAX.into()).low_u64();
+			let block_gas_weight = T::GasWeightMapping::gas_to_weight(block_gas_limit, false);
+			let max_block = <T as frame_system::Config>::BlockWeights::get().max_block;
+			assert!(
+				block_gas_weight.all_lte(max_block),
+				"BlockGasLimit must fit the block weight: {:?} > {:?}",
+				block_gas_weight,
+				max_block,
+			);
+
+			// Balances are reported to the EVM as `u128`, so larger ones couldn't be represented.
+			assert!(
+				TryInto::<u128>::try_into(<BalanceOf<T> as Bounded>::max_value()).is_ok(),
+				"The currency balance type must fit in u128",
+			);
+		}
+	}
+
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
 		/// Withdraw balance from EVM into currency/balances pallet.
@@ -303,7 +403,7 @@ pub mod pallet {
 				&address_account_id,
 				&destination,
 				value,
-				ExistenceRequirement::AllowDeath,
+				T::WithdrawExistenceRequirement::get(),
 			)?;
 
 			Ok(())
@@ -328,6 +428,10 @@ pub mod pallet {
 			access_list: Vec<(H160, Vec<H256>)>,
 		) -> DispatchResultWithPostInfo {
 			T::CallOrigin::ensure_address_origin(&source, origin)?;
+			ensure!(
+				input.len() <= T::MaxCallDataSize::get() as usize,
+				Error::<T>::CallDataTooLarge
+			);
 
 			let is_transactional = true;
 			let validate = true;
@@ -339,12 +443,14 @@ pub mod pallet {
 				gas_limit,
 				Some(max_fee_per_gas),
 				max_priority_fee_per_gas,
+				None,
 				nonce,
 				access_list,
 				is_transactional,
 				validate,
 				None,
 				None,
+				None,
 				T::config(),
 			) {
 				Ok(info) => info,
@@ -404,6 +510,10 @@ pub mod pallet {
 			access_list: Vec<(H160, Vec<H256>)>,
 		) -> DispatchResultWithPostInfo {
 			T::CallOrigin::ensure_address_origin(&source, origin)?;
+			ensure!(
+				init.len() <= T::MaxCallDataSize::get() as usize,
+				Error::<T>::CallDataTooLarge
+			);
 
 			let is_transactional = true;
 			let validate = true;
@@ -414,12 +524,14 @@ pub mod pallet {
 				gas_limit,
 				Some(max_fee_per_gas),
 				max_priority_fee_per_gas,
+				None,
 				nonce,
 				access_list,
 				is_transactional,
 				validate,
 				None,
 				None,
+				None,
 				T::config(),
 			) {
 				Ok(info) => info,
@@ -491,6 +603,10 @@ pub mod pallet {
 			access_list: Vec<(H160, Vec<H256>)>,
 		) -> DispatchResultWithPostInfo {
 			T::CallOrigin::ensure_address_origin(&source, origin)?;
+			ensure!(
+				init.len() <= T::MaxCallDataSize::get() as usize,
+				Error::<T>::CallDataTooLarge
+			);
 
 			let is_transactional = true;
 			let validate = true;
@@ -502,12 +618,14 @@ pub mod pallet {
 				gas_limit,
 				Some(max_fee_per_gas),
 				max_priority_fee_per_gas,
+				None,
 				nonce,
 				access_list,
 				is_transactional,
 				validate,
 				None,
 				None,
+				None,
 				T::config(),
 			) {
 				Ok(info) => info,
@@ -559,6 +677,16 @@ pub mod pallet {
 				pays_fee: Pays::No,
 			})
 		}
+
+		/// Pause or resume all EVM execution. While paused, calls and creates are rejected.
+		#[pallet::call_index(4)]
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::set_paused())]
+		pub fn set_paused(origin: OriginFor<T>, paused: bool) -> DispatchResult {
+			ensure_root(origin)?;
+			<Paused<T>>::put(paused);
+			Self::deposit_event(Event::PausedSet { paused });
+			Ok(())
+		}
 	}
 
 	#[pallet::event]
@@ -574,6 +702,8 @@ pub mod pallet {
 		Executed { address: H160 },
 		/// A contract has been executed with errors. States are reverted with only gas fees applied.
 		ExecutedFailed { address: H160 },
+		/// EVM execution has been paused or resumed.
+		PausedSet { paused: bool },
 	}
 
 	#[pallet::error]
@@ -604,6 +734,18 @@ pub mod pallet {
 		TransactionMustComeFromEOA,
 		/// Undefined error.
 		Undefined,
+		/// Max priority fee per gas is higher than max fee per gas.
+		PriorityFeeTooHigh,
+		/// Init code exceeds the EIP-3860 size limit.
+		CreateContractSizeLimit,
+		/// The input of the call or create exceeds `MaxCallDataSize`.
+		CallDataTooLarge,
+		/// EVM execution is paused.
+		Paused,
+		/// The sender or the recipient of the transaction is blocked.
+		BlockedAddress,
+		/// The access list of the transaction has too many entries.
+		AccessListTooLarge,
 	}
 
 	impl<T> From<TransactionValidationError> for Error<T> {
@@ -615,10 +757,19 @@ pub mod pallet {
 				TransactionValidationError::TxNonceTooLow => Error::<T>::InvalidNonce,
 				TransactionValidationError::TxNonceTooHigh => Error::<T>::InvalidNonce,
 				TransactionValidationError::GasPriceTooLow => Error::<T>::GasPriceTooLow,
-				TransactionValidationError::PriorityFeeTooHigh => Error::<T>::GasPriceTooLow,
+				TransactionValidationError::PriorityFeeTooHigh => Error::<T>::PriorityFeeTooHigh,
 				TransactionValidationError::InvalidFeeInput => Error::<T>::GasPriceTooLow,
 				TransactionValidationError::InvalidChainId => Error::<T>::InvalidChainId,
 				TransactionValidationError::InvalidSignature => Error::<T>::InvalidSignature,
+				TransactionValidationError::CreateContractSizeLimit => {
+					Error::<T>::CreateContractSizeLimit
+				}
+				TransactionValidationError::BlockedAddress => Error::<T>::BlockedAddress,
+				TransactionValidationError::AccessListTooLarge => Error::<T>::AccessListTooLarge,
+				TransactionValidationError::BalanceTooLowForFee
+				| TransactionValidationError::BalanceTooLowForValue => Error::<T>::BalanceLow,
+				TransactionValidationError::Paused => Error::<T>::Paused,
+				TransactionValidationError::SenderNotEoa => Error::<T>::TransactionMustComeFromEOA,
 				TransactionValidationError::UnknownError => Error::<T>::Undefined,
 			}
 		}
@@ -627,6 +778,9 @@ pub mod pallet {
 	#[pallet::genesis_config]
 	#[derive(frame_support::DefaultNoBound)]
 	pub struct GenesisConfig<T> {
+		/// Accounts to create. Accounts with code get a sufficient reference from
+		/// `AccountProvider::create_account`, which keeps them alive without a balance. Other
+		/// accounts with a nonce or storage need a balance of at least the existential deposit.
 		pub accounts: BTreeMap<H160, GenesisAccount>,
 		#[serde(skip)]
 		pub _marker: PhantomData<T>,
@@ -641,6 +795,27 @@ pub mod pallet {
 			const MAX_ACCOUNT_NONCE: usize = 100;
 
 			for (address, account) in &self.accounts {
+				if let Some(limit) = T::config().create_contract_limit {
+					assert!(
+						account.code.len() <= limit,
+						"Genesis code of {:?} is {} bytes, over the {} bytes code size limit",
+						address,
+						account.code.len(),
+						limit,
+					);
+				}
+
+				// Without code, nothing but its balance references the account, so its nonce
+				// would be left on an account `frame_system` doesn't consider alive.
+				let balance: BalanceOf<T> = account.balance.unique_saturated_into();
+				let kept_alive = !account.code.is_empty()
+					|| (!balance.is_zero() && balance >= T::Currency::minimum_balance());
+				assert!(
+					kept_alive || (account.nonce.is_zero() && account.storage.is_empty()),
+					"Genesis account {:?} has neither code nor a balance of at least the existential deposit",
+					address,
+				);
+
 				let account_id = T::AddressMapping::into_account_id(*address);
 
 				// ASSUME: in one single EVM transaction, the nonce will not increase more than
@@ -652,10 +827,7 @@ pub mod pallet {
 					T::AccountProvider::inc_account_nonce(&account_id);
 				}
 
-				let _ = T::Currency::deposit_creating(
-					&account_id,
-					account.balance.unique_saturated_into(),
-				);
+				let _ = T::Currency::deposit_creating(&account_id, balance);
 
 				Pallet::<T>::create_account(*address, account.code.clone());
 
@@ -679,6 +851,14 @@ pub mod pallet {
 
 	#[pallet::storage]
 	pub type Suicided<T: Config> = StorageMap<_, Blake2_128Concat, H160, (), OptionQuery>;
+
+	/// Whether EVM execution is paused, see [`Pallet::set_paused`].
+	#[pallet::storage]
+	pub type Paused<T: Config> = StorageValue<_, bool, ValueQuery>;
+
+	/// EVM nonces of the accounts, when using [`DedicatedEvmAccountProvider`].
+	#[pallet::storage]
+	pub type AccountNonces<T: Config> = StorageMap<_, Blake2_128Concat, H160, U256, ValueQuery>;
 }
 
 /// Utility alias for easy access to the [`AccountProvider::AccountId`] type from a given config.
@@ -687,6 +867,15 @@ pub type AccountIdOf<T> = <<T as Config>::AccountProvider as AccountProvider>::A
 /// Type alias for currency balance.
 pub type BalanceOf<T> = <<T as Config>::Currency as Currency<AccountIdOf<T>>>::Balance;
 
+/// Convert a currency balance into the `U256` reported to the EVM. Balances above `u128::MAX`
+/// are clamped to it, which `integrity_test` rules out for the configured currency.
+fn evm_balance<B: TryInto<u128>>(balance: B) -> U256 {
+	U256::from(balance.try_into().unwrap_or_else(|_| {
+		log::warn!(target: "evm", "Balance exceeds u128::MAX, reporting u128::MAX to the EVM");
+		u128::MAX
+	}))
+}
+
 /// Type alias for negative imbalance during fees
 type NegativeImbalanceOf<C, T> = <C as Currency<AccountIdOf<T>>>::NegativeImbalance;
 
@@ -715,6 +904,28 @@ impl CodeMetadata {
 	}
 }
 
+/// Policy applied when an EVM execution is started while another one is in progress.
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub enum ReentrancyPolicy {
+	/// Reject nested executions with [`Error::Reentrancy`].
+	Forbid,
+	/// Allow nested executions at any depth.
+	Allow,
+	/// Allow at most the given number of executions nested in the outermost one.
+	LimitDepth(u32),
+}
+
+impl ReentrancyPolicy {
+	/// Whether a new execution may start while `depth` executions are in progress.
+	pub fn allows(&self, depth: u32) -> bool {
+		match self {
+			Self::Forbid => depth == 0,
+			Self::Allow => true,
+			Self::LimitDepth(limit) => depth <= *limit,
+		}
+	}
+}
+
 pub trait EnsureAddressOrigin<OuterOrigin> {
 	/// Success return type.
 	type Success;
@@ -800,6 +1011,28 @@ where
 	}
 }
 
+/// Ensure that the origin is the account registered for the address in the `Lookup` storage map,
+/// e.g. an on-chain `H160 -> AccountId` registry. Unregistered addresses are always rejected.
+pub struct EnsureAddressMapped<AccountId, Lookup>(core::marker::PhantomData<(AccountId, Lookup)>);
+
+impl<OuterOrigin, AccountId, Lookup> EnsureAddressOrigin<OuterOrigin>
+	for EnsureAddressMapped<AccountId, Lookup>
+where
+	OuterOrigin: Into<Result<RawOrigin<AccountId>, OuterOrigin>> + From<RawOrigin<AccountId>>,
+	AccountId: FullCodec + PartialEq,
+	Lookup: frame_support::storage::StorageMap<H160, AccountId, Query = Option<AccountId>>,
+{
+	type Success = AccountId;
+
+	fn try_address_origin(address: &H160, origin: OuterOrigin) -> Result<AccountId, OuterOrigin> {
+		let registered = Lookup::get(address);
+		origin.into().and_then(|o| match o {
+			RawOrigin::Signed(who) if registered.as_ref() == Some(&who) => Ok(who),
+			r => Err(OuterOrigin::from(r)),
+		})
+	}
+}
+
 /// Ensure that the address is AccountId20.
 pub struct EnsureAccountId20;
 
@@ -906,6 +1139,23 @@ where
 	}
 }
 
+/// `FixedGasWeightMapping` that rounds weight up to the next whole unit of gas, instead of
+/// truncating it.
+pub struct RoundingUpGasWeightMapping<T>(core::marker::PhantomData<T>);
+impl<T> GasWeightMapping for RoundingUpGasWeightMapping<T>
+where
+	T: FixedGasWeightMappingAssociatedTypes,
+{
+	fn gas_to_weight(gas: u64, without_base_weight: bool) -> Weight {
+		FixedGasWeightMapping::<T>::gas_to_weight(gas, without_base_weight)
+	}
+	fn weight_to_gas(weight: Weight) -> u64 {
+		weight
+			.ref_time()
+			.div_ceil(T::WeightPerGas::get().ref_time())
+	}
+}
+
 static SHANGHAI_CONFIG: EvmConfig = EvmConfig::shanghai();
 
 impl<T: Config> Pallet<T> {
@@ -963,6 +1213,63 @@ impl<T: Config> Pallet<T> {
 		}
 	}
 
+	/// Clear the storage of suicided contracts, at most `SuicideIdleClearLimit` entries within
+	/// `remaining_weight`. Once all the storage of a contract is cleared, it is removed from
+	/// `Suicided` and its provider account is removed.
+	fn clear_suicided(remaining_weight: Weight) -> Weight {
+		let db_weight = T::DbWeight::get();
+		// Reading the next suicided contract, then removing it and its provider account.
+		let contract_weight = db_weight.reads_writes(2, 2);
+		let entry_weight = db_weight.writes(1);
+
+		let mut limit = T::SuicideIdleClearLimit::get();
+		let mut used_weight = Weight::zero();
+		while limit > 0 {
+			let Some(available_weight) = remaining_weight
+				.checked_sub(&used_weight)
+				.and_then(|weight| weight.checked_sub(&contract_weight))
+			else {
+				break;
+			};
+			let entries = available_weight
+				.ref_time()
+				.checked_div(entry_weight.ref_time())
+				.unwrap_or(u64::MAX)
+				.min(limit.into()) as u32;
+			if entries == 0 {
+				break;
+			}
+
+			let Some(address) = <Suicided<T>>::iter_keys().next() else {
+				used_weight.saturating_accrue(db_weight.reads(1));
+				break;
+			};
+
+			#[allow(deprecated)]
+			let res = <AccountStorages<T>>::remove_prefix(address, Some(entries));
+			let removed = match res {
+				KillStorageResult::AllRemoved(removed) => {
+					<Suicided<T>>::remove(address);
+
+					let account_id = T::AddressMapping::into_account_id(address);
+					T::AccountProvider::remove_account(&account_id);
+					removed
+				}
+				KillStorageResult::SomeRemaining(removed) => removed,
+			};
+
+			used_weight.saturating_accrue(contract_weight);
+			used_weight.saturating_accrue(entry_weight.saturating_mul(removed.into()));
+			limit = limit.saturating_sub(removed);
+
+			if let KillStorageResult::SomeRemaining(_) = res {
+				break;
+			}
+		}
+
+		used_weight
+	}
+
 	/// Create an account.
 	pub fn create_account(address: H160, code: Vec<u8>) {
 		if <Suicided<T>>::contains_key(address) {
@@ -989,6 +1296,16 @@ impl<T: Config> Pallet<T> {
 		<AccountCodes<T>>::insert(address, code);
 	}
 
+	/// Get the size of the account code without loading it: from its metadata if present,
+	/// otherwise from the length prefix of the stored code.
+	pub fn account_code_size(address: H160) -> u64 {
+		if let Some(meta) = <AccountCodesMetadata<T>>::get(address) {
+			return meta.size;
+		}
+
+		<AccountCodes<T>>::decode_len(address).unwrap_or(0) as u64
+	}
+
 	/// Get the account metadata (hash and size) from storage if it exists,
 	/// or compute it from code and store it if it doesn't exist.
 	pub fn account_code_metadata(address: H160) -> CodeMetadata {
@@ -1026,18 +1343,62 @@ impl<T: Config> Pallet<T> {
 		(
 			Account {
 				nonce: U256::from(UniqueSaturatedInto::<u128>::unique_saturated_into(nonce)),
-				balance: U256::from(UniqueSaturatedInto::<u128>::unique_saturated_into(balance)),
+				balance: evm_balance(balance),
+			},
+			T::DbWeight::get().reads(2),
+		)
+	}
+
+	/// Get the account basic in EVM format to validate a transaction from `address`. The balance is
+	/// the one the fee can be withdrawn from, as reported by `OnChargeTransaction`.
+	pub fn account_basic_for_fees(address: &H160) -> (Account, frame_support::weights::Weight) {
+		let account_id = T::AddressMapping::into_account_id(*address);
+		let nonce = T::AccountProvider::account_nonce(&account_id);
+		let balance = T::OnChargeTransaction::fee_paying_balance(address);
+
+		(
+			Account {
+				nonce: U256::from(UniqueSaturatedInto::<u128>::unique_saturated_into(nonce)),
+				balance,
 			},
 			T::DbWeight::get().reads(2),
 		)
 	}
 
+	/// Get the account basic in EVM format for each of the given addresses, in the same order.
+	pub fn accounts_basic(addresses: &[H160]) -> (Vec<Account>, frame_support::weights::Weight) {
+		addresses.iter().fold(
+			(Vec::with_capacity(addresses.len()), Weight::zero()),
+			|(mut accounts, weight), address| {
+				let (account, read_weight) = Self::account_basic(address);
+				accounts.push(account);
+				(accounts, weight.saturating_add(read_weight))
+			},
+		)
+	}
+
 	/// Get the author using the FindAuthor trait.
 	pub fn find_author() -> H160 {
+		Self::try_find_author().unwrap_or_default()
+	}
+
+	/// Get the author using the FindAuthor trait, or `None` if the block has no known author.
+	pub fn try_find_author() -> Option<H160> {
 		let digest = <frame_system::Pallet<T>>::digest();
 		let pre_runtime_digests = digest.logs.iter().filter_map(|d| d.as_pre_runtime());
 
-		T::FindAuthor::find_author(pre_runtime_digests).unwrap_or_default()
+		T::FindAuthor::find_author(pre_runtime_digests)
+	}
+
+	/// Check whether `address` is one of the configured precompiles.
+	///
+	/// The lookup is given an unbounded gas budget; a precompile set that still runs out of
+	/// gas answering is treated as not containing the address.
+	pub fn is_precompile(address: H160) -> bool {
+		match T::PrecompilesValue::get().is_precompile(address, u64::MAX) {
+			IsPrecompileResult::Answer { is_precompile, .. } => is_precompile,
+			IsPrecompileResult::OutOfGas => false,
+		}
 	}
 }
 
@@ -1064,15 +1425,53 @@ pub trait OnChargeEVMTransaction<T: Config> {
 
 	/// Introduced in EIP1559 to handle the priority tip.
 	fn pay_priority_fee(tip: Self::LiquidityInfo);
+
+	/// Balance of `who` the fee of a transaction can be withdrawn from, checked when validating
+	/// the transaction. Defaults to the balance reported by `Pallet::account_basic`.
+	fn fee_paying_balance(who: &H160) -> U256 {
+		Pallet::<T>::account_basic(who).0.balance
+	}
+}
+
+/// Handles the part of a fee refund which couldn't be deposited back to the account that paid
+/// the fee, e.g. because it would stay below the existential deposit.
+pub trait OnRefundDust<Imbalance> {
+	/// Take `dust`, or hand it back to be paid along with the tip of the transaction.
+	fn on_refund_dust(dust: Imbalance) -> Option<Imbalance>;
+}
+
+/// Pays refund dust along with the tip, to the block author.
+pub struct DustToTip;
+
+impl<Imbalance> OnRefundDust<Imbalance> for DustToTip {
+	fn on_refund_dust(dust: Imbalance) -> Option<Imbalance> {
+		Some(dust)
+	}
+}
+
+/// Hands refund dust to the `OnUnbalanced` handler `H`, e.g. a treasury.
+pub struct DustTo<H>(core::marker::PhantomData<H>);
+
+impl<Imbalance, H: OnUnbalanced<Imbalance>> OnRefundDust<Imbalance> for DustTo<H> {
+	fn on_refund_dust(dust: Imbalance) -> Option<Imbalance> {
+		H::on_unbalanced(dust);
+		None
+	}
 }
 
 /// Implements the transaction payment for a pallet implementing the `Currency`
 /// trait (eg. the pallet_balances) using an unbalance handler (implementing
 /// `OnUnbalanced`).
 /// Similar to `CurrencyAdapter` of `pallet_transaction_payment`
-pub struct EVMCurrencyAdapter<C, OU>(core::marker::PhantomData<(C, OU)>);
-
-impl<T, C, OU> OnChargeEVMTransaction<T> for EVMCurrencyAdapter<C, OU>
+///
+/// Refunds which can't be deposited back, e.g. because the account was reaped, are handed to
+/// `RD`, which by default pays them to the block author with the tip. Tips paid in a block
+/// without a known author are handed to `NA`, which defaults to the base fee handler `OU`.
+pub struct EVMCurrencyAdapter<C, OU, RD = DustToTip, NA = OU>(
+	core::marker::PhantomData<(C, OU, RD, NA)>,
+);
+
+impl<T, C, OU, RD, NA> OnChargeEVMTransaction<T> for EVMCurrencyAdapter<C, OU, RD, NA>
 where
 	T: Config,
 	C: Currency<AccountIdOf<T>>,
@@ -1081,6 +1480,8 @@ where
 	C::NegativeImbalance:
 		Imbalance<<C as Currency<AccountIdOf<T>>>::Balance, Opposite = C::PositiveImbalance>,
 	OU: OnUnbalanced<NegativeImbalanceOf<C, T>>,
+	RD: OnRefundDust<NegativeImbalanceOf<C, T>>,
+	NA: OnUnbalanced<NegativeImbalanceOf<C, T>>,
 	U256: UniqueSaturatedInto<<C as Currency<AccountIdOf<T>>>::Balance>,
 {
 	// Kept type as Option to satisfy bound of Default
@@ -1116,7 +1517,7 @@ where
 				.saturating_sub(corrected_fee.unique_saturated_into());
 			// refund to the account that paid the fees. If this fails, the
 			// account might have dropped below the existential balance. In
-			// that case we don't refund anything.
+			// that case the refund is handed to `RD`.
 			let refund_imbalance = C::deposit_into_existing(&account_id, refund_amount)
 				.unwrap_or_else(|_| C::PositiveImbalance::zero());
 
@@ -1138,6 +1539,13 @@ where
 				refund_imbalance
 			};
 
+			// Hand the part of the refund that couldn't be deposited to `RD`.
+			let (dust, paid) = paid.split(refund_amount.saturating_sub(refund_imbalance.peek()));
+			let paid = match RD::on_refund_dust(dust) {
+				Some(dust) => paid.merge(dust),
+				None => paid,
+			};
+
 			// merge the imbalance caused by paying the fees and refunding parts of it again.
 			let adjusted_paid = paid
 				.offset(refund_imbalance)
@@ -1155,8 +1563,14 @@ where
 	fn pay_priority_fee(tip: Self::LiquidityInfo) {
 		// Default Ethereum behaviour: issue the tip to the block author.
 		if let Some(tip) = tip {
-			let account_id = T::AddressMapping::into_account_id(<Pallet<T>>::find_author());
-			let _ = C::deposit_into_existing(&account_id, tip.peek());
+			match <Pallet<T>>::try_find_author() {
+				Some(author) => {
+					let account_id = T::AddressMapping::into_account_id(author);
+					let _ = C::deposit_into_existing(&account_id, tip.peek());
+				}
+				// Without an author the tip would otherwise go to the zero address.
+				None => NA::on_unbalanced(tip),
+			}
 		}
 	}
 }
@@ -1166,13 +1580,48 @@ where
 ///
 /// Equivalent of `EVMCurrencyAdapter` but for fungible traits. Similar to `FungibleAdapter` of
 /// `pallet_transaction_payment`
-pub struct EVMFungibleAdapter<F, OU>(core::marker::PhantomData<(F, OU)>);
+///
+/// Refunds which can't be deposited back, e.g. because they are below the existential deposit,
+/// are handed to `RD`, which by default pays them to the block author with the tip. Tips paid in
+/// a block without a known author are handed to `NA`, which defaults to the base fee handler `OU`.
+///
+/// Fees are withdrawn with the preservation and fortitude given by the [`FeeWithdrawalPolicy`]
+/// `WP`. The default `()` keeps the account alive and leaves frozen balance untouched.
+pub struct EVMFungibleAdapter<F, OU, RD = DustToTip, NA = OU, WP = ()>(
+	core::marker::PhantomData<(F, OU, RD, NA, WP)>,
+);
+
+/// Decides how `EVMFungibleAdapter` withdraws the fee of a transaction from its sender.
+pub trait FeeWithdrawalPolicy<AccountId> {
+	/// Preservation and fortitude to withdraw the fee of `who` with. `Fortitude::Force` lets the
+	/// fee be paid from balance that is otherwise frozen.
+	fn fee_withdrawal(who: &AccountId) -> (Preservation, Fortitude);
+}
+
+/// Keep the account alive and don't touch frozen balance.
+impl<AccountId> FeeWithdrawalPolicy<AccountId> for () {
+	fn fee_withdrawal(_who: &AccountId) -> (Preservation, Fortitude) {
+		(Preservation::Preserve, Fortitude::Polite)
+	}
+}
+
+/// Keep the account alive, but let fees be paid from frozen balance.
+pub struct ForceFeeWithdrawal;
+
+impl<AccountId> FeeWithdrawalPolicy<AccountId> for ForceFeeWithdrawal {
+	fn fee_withdrawal(_who: &AccountId) -> (Preservation, Fortitude) {
+		(Preservation::Preserve, Fortitude::Force)
+	}
+}
 
-impl<T, F, OU> OnChargeEVMTransaction<T> for EVMFungibleAdapter<F, OU>
+impl<T, F, OU, RD, NA, WP> OnChargeEVMTransaction<T> for EVMFungibleAdapter<F, OU, RD, NA, WP>
 where
 	T: Config,
 	F: Balanced<AccountIdOf<T>>,
 	OU: OnUnbalanced<Credit<AccountIdOf<T>, F>>,
+	RD: OnRefundDust<Credit<AccountIdOf<T>, F>>,
+	NA: OnUnbalanced<Credit<AccountIdOf<T>, F>>,
+	WP: FeeWithdrawalPolicy<AccountIdOf<T>>,
 	U256: UniqueSaturatedInto<<F as Inspect<AccountIdOf<T>>>::Balance>,
 {
 	// Kept type as Option to satisfy bound of Default
@@ -1183,12 +1632,13 @@ where
 			return Ok(None);
 		}
 		let account_id = T::AddressMapping::into_account_id(*who);
+		let (preservation, fortitude) = WP::fee_withdrawal(&account_id);
 		let imbalance = F::withdraw(
 			&account_id,
 			fee.unique_saturated_into(),
 			Precision::Exact,
-			Preservation::Preserve,
-			Fortitude::Polite,
+			preservation,
+			fortitude,
 		)
 		.map_err(|_| Error::<T>::BalanceLow)?;
 		Ok(Some(imbalance))
@@ -1211,6 +1661,13 @@ where
 			let refund_imbalance = F::deposit(&account_id, refund_amount, Precision::BestEffort)
 				.unwrap_or_else(|_| Debt::<AccountIdOf<T>, F>::zero());
 
+			// Hand the part of the refund that couldn't be deposited to `RD`.
+			let (dust, paid) = paid.split(refund_amount.saturating_sub(refund_imbalance.peek()));
+			let paid = match RD::on_refund_dust(dust) {
+				Some(dust) => paid.merge(dust),
+				None => paid,
+			};
+
 			// merge the imbalance caused by paying the fees and refunding parts of it again.
 			let adjusted_paid = paid
 				.offset(refund_imbalance)
@@ -1228,10 +1685,22 @@ where
 	fn pay_priority_fee(tip: Self::LiquidityInfo) {
 		// Default Ethereum behaviour: issue the tip to the block author.
 		if let Some(tip) = tip {
-			let account_id = T::AddressMapping::into_account_id(<Pallet<T>>::find_author());
-			let _ = F::deposit(&account_id, tip.peek(), Precision::BestEffort);
+			match <Pallet<T>>::try_find_author() {
+				Some(author) => {
+					let account_id = T::AddressMapping::into_account_id(author);
+					let _ = F::deposit(&account_id, tip.peek(), Precision::BestEffort);
+				}
+				// Without an author the tip would otherwise go to the zero address.
+				None => NA::on_unbalanced(tip),
+			}
 		}
 	}
+
+	fn fee_paying_balance(who: &H160) -> U256 {
+		let account_id = T::AddressMapping::into_account_id(*who);
+		let (preservation, fortitude) = WP::fee_withdrawal(&account_id);
+		evm_balance(F::reducible_balance(&account_id, preservation, fortitude))
+	}
 }
 
 /// Implementation for () does not specify what to do with imbalance
@@ -1265,6 +1734,10 @@ where
 	fn pay_priority_fee(tip: Self::LiquidityInfo) {
 		<EVMFungibleAdapter<T::Currency, ()> as OnChargeEVMTransaction<T>>::pay_priority_fee(tip);
 	}
+
+	fn fee_paying_balance(who: &H160) -> U256 {
+		<EVMFungibleAdapter<T::Currency, ()> as OnChargeEVMTransaction<T>>::fee_paying_balance(who)
+	}
 }
 
 pub trait OnCreate<T> {
@@ -1309,3 +1782,40 @@ impl<T: frame_system::Config> AccountProvider for FrameSystemAccountProvider<T>
 		let _ = frame_system::Pallet::<T>::dec_sufficients(who);
 	}
 }
+
+/// EVM account provider keeping EVM nonces in [`AccountNonces`], apart from the
+/// [`frame_system`] account nonces used by extrinsics.
+///
+/// `M` converts an account back to its EVM address, and must be the inverse of
+/// [`Config::AddressMapping`]. Accounts existence is still tracked through [`frame_system`]
+/// sufficient references. Chains switching from [`FrameSystemAccountProvider`] seed the nonces
+/// with [`migrations::MigrateToV1`].
+pub struct DedicatedEvmAccountProvider<T, M = ConvertInto>(core::marker::PhantomData<(T, M)>);
+
+impl<T, M> AccountProvider for DedicatedEvmAccountProvider<T, M>
+where
+	T: Config,
+	M: Convert<T::AccountId, H160>,
+{
+	type AccountId = T::AccountId;
+	type Nonce = T::Nonce;
+
+	fn account_nonce(who: &Self::AccountId) -> Self::Nonce {
+		let nonce = AccountNonces::<T>::get(M::convert(who.clone()));
+		UniqueSaturatedInto::<u128>::unique_saturated_into(nonce).unique_saturated_into()
+	}
+
+	fn inc_account_nonce(who: &Self::AccountId) {
+		AccountNonces::<T>::mutate(M::convert(who.clone()), |nonce| {
+			*nonce = nonce.saturating_add(U256::one())
+		});
+	}
+
+	fn create_account(who: &Self::AccountId) {
+		let _ = frame_system::Pallet::<T>::inc_sufficients(who);
+	}
+
+	fn remove_account(who: &Self::AccountId) {
+		let _ = frame_system::Pallet::<T>::dec_sufficients(who);
+	}
+}
@@ -0,0 +1,92 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Storage migrations of the EVM pallet.
+
+use frame_support::{
+	migrations::{MigrationId, SteppedMigration, SteppedMigrationError},
+	traits::{Get, GetStorageVersion, StorageVersion},
+	weights::WeightMeter,
+};
+use sp_core::{H160, U256};
+use sp_runtime::traits::{Convert, UniqueSaturatedInto, Zero};
+
+use crate::{AccountNonces, Config, Pallet};
+
+/// Migrate the pallet to storage version 1, seeding [`AccountNonces`] with the current
+/// [`frame_system`] account nonces.
+///
+/// To be included by chains switching to [`crate::DedicatedEvmAccountProvider`], with the same
+/// account to address conversion `M`. Every account is visited, so this is a multi-block
+/// migration to be run by `pallet_migrations`, copying as many nonces per block as the weight
+/// it is given allows. It does nothing if the pallet is already at version 1.
+pub struct MigrateToV1<T, M>(core::marker::PhantomData<(T, M)>);
+
+impl<T, M> SteppedMigration for MigrateToV1<T, M>
+where
+	T: Config,
+	M: Convert<T::AccountId, H160>,
+{
+	/// The last account visited.
+	type Cursor = T::AccountId;
+	type Identifier = MigrationId<16>;
+
+	fn id() -> Self::Identifier {
+		MigrationId {
+			pallet_id: *b"pallet-evm-nonce",
+			version_from: 0,
+			version_to: 1,
+		}
+	}
+
+	fn step(
+		mut cursor: Option<Self::Cursor>,
+		meter: &mut WeightMeter,
+	) -> Result<Option<Self::Cursor>, SteppedMigrationError> {
+		// Reading the storage version, then reading an account and writing its nonce.
+		let version_weight = T::DbWeight::get().reads(1);
+		let account_weight = T::DbWeight::get().reads_writes(1, 1);
+		let required = version_weight.saturating_add(account_weight);
+		if meter.remaining().any_lt(required) {
+			return Err(SteppedMigrationError::InsufficientWeight { required });
+		}
+		meter.consume(version_weight);
+		if Pallet::<T>::on_chain_storage_version() != StorageVersion::new(0) {
+			return Ok(None);
+		}
+
+		let mut accounts = match cursor.as_ref() {
+			Some(last) => frame_system::Account::<T>::iter_from(
+				frame_system::Account::<T>::hashed_key_for(last),
+			),
+			None => frame_system::Account::<T>::iter(),
+		};
+		while meter.try_consume(account_weight).is_ok() {
+			let Some((who, info)) = accounts.next() else {
+				// The weight of this last write is covered by the one consumed for the account.
+				StorageVersion::new(1).put::<Pallet<T>>();
+				return Ok(None);
+			};
+			if !info.nonce.is_zero() {
+				let nonce: u128 = info.nonce.unique_saturated_into();
+				AccountNonces::<T>::insert(M::convert(who.clone()), U256::from(nonce));
+			}
+			cursor = Some(who);
+		}
+		Ok(cursor)
+	}
+}
@@ -17,12 +17,20 @@
 
 //! Test mock for unit tests and benchmarking
 
-use frame_support::{derive_impl, parameter_types, weights::Weight};
-use sp_core::{H160, U256};
+use frame_support::{
+	derive_impl, parameter_types,
+	traits::{
+		tokens::{Fortitude, Preservation},
+		ExistenceRequirement, FindAuthor,
+	},
+	weights::{RuntimeDbWeight, Weight},
+	ConsensusEngineId,
+};
+use sp_core::{H160, H256, U256};
 
 use crate::{
-	FeeCalculator, IsPrecompileResult, Precompile, PrecompileHandle, PrecompileResult,
-	PrecompileSet,
+	FeeCalculator, IsPrecompileResult, Precompile, PrecompileHandle, PrecompileOutput,
+	PrecompileResult, PrecompileSet,
 };
 
 frame_support::construct_runtime! {
@@ -36,6 +44,7 @@ frame_support::construct_runtime! {
 
 parameter_types! {
 	pub const BlockHashCount: u64 = 250;
+	pub static DbWeight: RuntimeDbWeight = RuntimeDbWeight { read: 0, write: 0 };
 	pub BlockWeights: frame_system::limits::BlockWeights =
 		frame_system::limits::BlockWeights::simple_max(Weight::from_parts(1024, 0));
 }
@@ -45,11 +54,12 @@ impl frame_system::Config for Test {
 	type Nonce = u64;
 	type Block = frame_system::mocking::MockBlock<Self>;
 	type BlockHashCount = BlockHashCount;
+	type DbWeight = DbWeight;
 	type AccountData = pallet_balances::AccountData<u64>;
 }
 
 parameter_types! {
-	pub const ExistentialDeposit: u64 = 0;
+	pub static ExistentialDeposit: u64 = 0;
 }
 #[derive_impl(pallet_balances::config_preludes::TestDefaultConfig)]
 impl pallet_balances::Config for Test {
@@ -62,20 +72,75 @@ impl pallet_timestamp::Config for Test {}
 
 parameter_types! {
 	pub MockPrecompiles: MockPrecompileSet = MockPrecompileSet;
+	pub static BlockGasLimit: U256 = U256::from(150_000_000);
+	// Low enough for `BlockGasLimit` to fit the default block weight.
+	pub WeightPerGas: Weight = Weight::from_parts(6_000, 0);
+	pub static WithdrawExistenceRequirement: ExistenceRequirement =
+		ExistenceRequirement::AllowDeath;
+	pub static Reentrancy: crate::ReentrancyPolicy = crate::ReentrancyPolicy::Forbid;
+	pub static MaxAccountBalance: Option<U256> = None;
+	pub static RestrictSelfdestruct: bool = false;
+	pub static RemoveTouchedEmptyAccounts: bool = false;
+	pub static PrevRandao: Option<H256> = None;
+	pub static MaxCallDataSize: u32 = u32::MAX;
+	pub static ForceFees: bool = false;
+	pub static BlockHashWindow: Option<u32> = None;
+	pub static BlockAuthor: Option<H160> = Some(H160([
+		0x12, 0x34, 0x50, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+	]));
 }
 
 #[derive_impl(crate::config_preludes::TestDefaultConfig)]
 impl crate::Config for Test {
 	type AccountProvider = crate::FrameSystemAccountProvider<Self>;
 	type FeeCalculator = FixedGasPrice;
+	type WithdrawOrigin = crate::EnsureAddressTruncated;
+	type WithdrawExistenceRequirement = WithdrawExistenceRequirement;
 	type BlockHashMapping = crate::SubstrateBlockHashMapping<Self>;
+	type BlockGasLimit = BlockGasLimit;
+	type WeightPerGas = WeightPerGas;
+	type FindAuthor = MockFindAuthor;
+	type MaxAccountBalance = MaxAccountBalance;
+	type Reentrancy = Reentrancy;
+	type RestrictSelfdestruct = RestrictSelfdestruct;
+	type RemoveTouchedEmptyAccounts = RemoveTouchedEmptyAccounts;
+	type PrevRandao = PrevRandao;
+	type MaxCallDataSize = MaxCallDataSize;
+	type BlockHashWindow = BlockHashWindow;
 	type Currency = Balances;
+	type OnChargeTransaction =
+		crate::EVMFungibleAdapter<Balances, (), crate::DustToTip, (), MockFeeWithdrawal>;
 	type PrecompilesType = MockPrecompileSet;
 	type PrecompilesValue = MockPrecompiles;
 	type Runner = crate::runner::stack::Runner<Self>;
 	type Timestamp = Timestamp;
 }
 
+/// Returns `BlockAuthor`, so tests can simulate blocks without an author digest.
+pub struct MockFindAuthor;
+impl FindAuthor<H160> for MockFindAuthor {
+	fn find_author<'a, I>(_digests: I) -> Option<H160>
+	where
+		I: 'a + IntoIterator<Item = (ConsensusEngineId, &'a [u8])>,
+	{
+		BlockAuthor::get()
+	}
+}
+
+/// Lets fees be paid from frozen balance while `ForceFees` is set.
+pub struct MockFeeWithdrawal;
+impl<AccountId> crate::FeeWithdrawalPolicy<AccountId> for MockFeeWithdrawal {
+	fn fee_withdrawal(who: &AccountId) -> (Preservation, Fortitude) {
+		if ForceFees::get() {
+			<crate::ForceFeeWithdrawal as crate::FeeWithdrawalPolicy<AccountId>>::fee_withdrawal(
+				who,
+			)
+		} else {
+			<() as crate::FeeWithdrawalPolicy<AccountId>>::fee_withdrawal(who)
+		}
+	}
+}
+
 pub struct FixedGasPrice;
 impl FeeCalculator for FixedGasPrice {
 	fn min_gas_price() -> (U256, Weight) {
@@ -84,7 +149,24 @@ impl FeeCalculator for FixedGasPrice {
 	}
 }
 
-/// Example PrecompileSet with only Identity precompile.
+/// Proof size reported by [`MockStateReader`].
+pub const MOCK_STATE_READ_PROOF_SIZE: u64 = 10_000;
+
+/// Stands in for a precompile reading a large value from the runtime state.
+pub struct MockStateReader;
+
+impl Precompile for MockStateReader {
+	fn execute(handle: &mut impl PrecompileHandle) -> PrecompileResult {
+		handle.record_external_cost(None, Some(MOCK_STATE_READ_PROOF_SIZE), None)?;
+		Ok(PrecompileOutput {
+			exit_status: crate::ExitSucceed::Returned,
+			output: Vec::new(),
+		})
+	}
+}
+
+/// Example PrecompileSet with the Identity precompile at address 1 and
+/// [`MockStateReader`] at address 2.
 pub struct MockPrecompileSet;
 
 impl PrecompileSet for MockPrecompileSet {
@@ -96,6 +178,9 @@ impl PrecompileSet for MockPrecompileSet {
 		if address == H160::from_low_u64_be(1) {
 			return Some(pallet_evm_precompile_simple::Identity::execute(handle));
 		}
+		if address == H160::from_low_u64_be(2) {
+			return Some(MockStateReader::execute(handle));
+		}
 
 		None
 	}
@@ -105,7 +190,8 @@ impl PrecompileSet for MockPrecompileSet {
 	/// `execute` already performs a check internally.
 	fn is_precompile(&self, address: H160, _gas: u64) -> IsPrecompileResult {
 		IsPrecompileResult::Answer {
-			is_precompile: address == H160::from_low_u64_be(1),
+			is_precompile: address == H160::from_low_u64_be(1)
+				|| address == H160::from_low_u64_be(2),
 			extra_cost: 0,
 		}
 	}
@@ -29,6 +29,18 @@ pub struct RunnerError<E: Into<sp_runtime::DispatchError>> {
 	pub weight: Weight,
 }
 
+/// Runs EVM operations.
+///
+/// `call`, `create` and `create2` accept a `gas_price_override` which, when set, is used as the
+/// effective gas price of the execution instead of the one derived from `max_fee_per_gas` and
+/// `max_priority_fee_per_gas`. They also accept a `gas_floor`, the minimum gas charged for the
+/// execution, capped by its gas limit. This lets callers price the transaction data on top of its
+/// execution, e.g. for the EIP-7623 calldata floor.
+///
+/// Execution failures are not errors: a reverted or failed `create` still returns its
+/// [`CreateInfo`](crate::CreateInfo), with the `exit_reason`, the address the contract would
+/// have been deployed at and the revert data of the constructor. Only transaction validation
+/// failures are reported as `RunnerError`.
 pub trait Runner<T: Config> {
 	type Error: Into<sp_runtime::DispatchError>;
 
@@ -56,12 +68,14 @@ pub trait Runner<T: Config> {
 		gas_limit: u64,
 		max_fee_per_gas: Option<U256>,
 		max_priority_fee_per_gas: Option<U256>,
+		gas_price_override: Option<U256>,
 		nonce: Option<U256>,
 		access_list: Vec<(H160, Vec<H256>)>,
 		is_transactional: bool,
 		validate: bool,
 		weight_limit: Option<Weight>,
 		proof_size_base_cost: Option<u64>,
+		gas_floor: Option<u64>,
 		config: &evm::Config,
 	) -> Result<CallInfo, RunnerError<Self::Error>>;
 
@@ -72,12 +86,14 @@ pub trait Runner<T: Config> {
 		gas_limit: u64,
 		max_fee_per_gas: Option<U256>,
 		max_priority_fee_per_gas: Option<U256>,
+		gas_price_override: Option<U256>,
 		nonce: Option<U256>,
 		access_list: Vec<(H160, Vec<H256>)>,
 		is_transactional: bool,
 		validate: bool,
 		weight_limit: Option<Weight>,
 		proof_size_base_cost: Option<u64>,
+		gas_floor: Option<u64>,
 		config: &evm::Config,
 	) -> Result<CreateInfo, RunnerError<Self::Error>>;
 
@@ -89,12 +105,14 @@ pub trait Runner<T: Config> {
 		gas_limit: u64,
 		max_fee_per_gas: Option<U256>,
 		max_priority_fee_per_gas: Option<U256>,
+		gas_price_override: Option<U256>,
 		nonce: Option<U256>,
 		access_list: Vec<(H160, Vec<H256>)>,
 		is_transactional: bool,
 		validate: bool,
 		weight_limit: Option<Weight>,
 		proof_size_base_cost: Option<u64>,
+		gas_floor: Option<u64>,
 		config: &evm::Config,
 	) -> Result<CreateInfo, RunnerError<Self::Error>>;
 }
@@ -31,6 +31,7 @@ use evm::{
 };
 // Substrate
 use frame_support::{
+	storage::KillStorageResult,
 	traits::{
 		tokens::{currency::Currency, ExistenceRequirement},
 		Get, Time,
@@ -51,11 +52,11 @@ use super::meter::StorageMeter;
 use crate::{
 	runner::Runner as RunnerT, AccountCodes, AccountCodesMetadata, AccountProvider,
 	AccountStorages, AddressMapping, BalanceOf, BlockHashMapping, Config, Error, Event,
-	FeeCalculator, OnChargeEVMTransaction, OnCreate, Pallet, RunnerError,
+	FeeCalculator, GasWeightMapping, OnChargeEVMTransaction, OnCreate, Pallet, Paused, RunnerError,
 };
 
-#[cfg(feature = "forbid-evm-reentrancy")]
-environmental::environmental!(IN_EVM: bool);
+// Number of EVM executions in progress, checked against `Config::Reentrancy`.
+environmental::environmental!(EVM_DEPTH: u32);
 
 #[derive(Default)]
 pub struct Runner<T: Config> {
@@ -74,11 +75,13 @@ where
 		gas_limit: u64,
 		max_fee_per_gas: Option<U256>,
 		max_priority_fee_per_gas: Option<U256>,
+		gas_price_override: Option<U256>,
 		config: &'config evm::Config,
 		precompiles: &'precompiles T::PrecompilesType,
 		is_transactional: bool,
 		weight_limit: Option<Weight>,
 		proof_size_base_cost: Option<u64>,
+		gas_floor: Option<u64>,
 		f: F,
 	) -> Result<ExecutionInfoV2<R>, RunnerError<Error<T>>>
 	where
@@ -92,44 +95,35 @@ where
 		) -> (ExitReason, R),
 		R: Default,
 	{
-		let (base_fee, weight) = T::FeeCalculator::min_gas_price();
+		let (base_fee, mut weight) = T::FeeCalculator::min_gas_price();
 
-		#[cfg(not(feature = "forbid-evm-reentrancy"))]
-		let res = Self::execute_inner(
-			source,
-			value,
-			gas_limit,
-			max_fee_per_gas,
-			max_priority_fee_per_gas,
-			config,
-			precompiles,
-			is_transactional,
-			f,
-			base_fee,
-			weight,
-			weight_limit,
-			proof_size_base_cost,
-		);
+		// Account for reading `Paused` below.
+		weight = weight.saturating_add(T::DbWeight::get().reads(1));
+		if <Paused<T>>::get() {
+			return Err(RunnerError {
+				error: Error::<T>::Paused,
+				weight,
+			});
+		}
 
-		#[cfg(feature = "forbid-evm-reentrancy")]
-		let res = IN_EVM::using_once(&mut false, || {
-			IN_EVM::with(|in_evm| {
-				if *in_evm {
+		let res = EVM_DEPTH::using_once(&mut 0, || {
+			EVM_DEPTH::with(|depth| {
+				if !T::Reentrancy::get().allows(*depth) {
 					return Err(RunnerError {
 						error: Error::<T>::Reentrancy,
 						weight,
 					});
 				}
-				*in_evm = true;
+				*depth += 1;
 				Ok(())
 			})
 			// This should always return `Some`, but let's play it safe.
 			.unwrap_or(Ok(()))?;
 
-			// Ensure that we always release the lock whenever we finish processing
+			// Ensure that we always leave the execution whenever we finish processing
 			sp_core::defer! {
-				IN_EVM::with(|in_evm| {
-					*in_evm = false;
+				EVM_DEPTH::with(|depth| {
+					*depth -= 1;
 				});
 			}
 
@@ -139,6 +133,7 @@ where
 				gas_limit,
 				max_fee_per_gas,
 				max_priority_fee_per_gas,
+				gas_price_override,
 				config,
 				precompiles,
 				is_transactional,
@@ -147,6 +142,7 @@ where
 				weight,
 				weight_limit,
 				proof_size_base_cost,
+				gas_floor,
 			)
 		});
 
@@ -160,6 +156,7 @@ where
 		mut gas_limit: u64,
 		max_fee_per_gas: Option<U256>,
 		max_priority_fee_per_gas: Option<U256>,
+		gas_price_override: Option<U256>,
 		config: &'config evm::Config,
 		precompiles: &'precompiles T::PrecompilesType,
 		is_transactional: bool,
@@ -168,6 +165,7 @@ where
 		weight: Weight,
 		weight_limit: Option<Weight>,
 		proof_size_base_cost: Option<u64>,
+		gas_floor: Option<u64>,
 	) -> Result<ExecutionInfoV2<R>, RunnerError<Error<T>>>
 	where
 		F: FnOnce(
@@ -180,6 +178,8 @@ where
 		) -> (ExitReason, R),
 		R: Default,
 	{
+		let gas_floor = gas_floor.unwrap_or_default().min(gas_limit);
+
 		// Used to record the external costs in the evm through the StackState implementation
 		let maybe_weight_info =
 			WeightInfo::new_from_weight_limit(weight_limit, proof_size_base_cost).map_err(
@@ -221,7 +221,11 @@ where
 			});
 		}
 
-		let total_fee_per_gas = if is_transactional {
+		let total_fee_per_gas = if let Some(gas_price) = gas_price_override {
+			// The caller forces the effective gas price, e.g. to simulate an execution at a
+			// given price without changing the transaction.
+			gas_price
+		} else if is_transactional {
 			match (max_fee_per_gas, max_priority_fee_per_gas) {
 				// Zero max_fee_per_gas for validated transactional calls exist in XCM -> EVM
 				// because fees are already withdrawn in the xcm-executor.
@@ -283,6 +287,19 @@ where
 
 		let (reason, retv) = f(&mut executor);
 
+		let used_gas = executor.used_gas();
+		let touched_gas = if config.empty_considered_exists || !T::RemoveTouchedEmptyAccounts::get()
+		{
+			0
+		} else {
+			let state = executor.state_mut();
+			Self::remove_touched_empty_accounts(
+				&state.substate.touches,
+				&mut state.weight_info,
+				gas_limit.saturating_sub(used_gas),
+			)
+		};
+
 		// Compute the storage gas cost based on the storage growth.
 		let storage_gas = match &executor.state().storage_meter {
 			Some(storage_meter) => storage_meter.storage_to_gas(storage_growth_ratio),
@@ -298,11 +315,13 @@ where
 		};
 
 		// Post execution.
-		let used_gas = executor.used_gas();
-		let effective_gas = U256::from(core::cmp::max(
-			core::cmp::max(used_gas, pov_gas),
-			storage_gas,
-		));
+		let effective_gas = U256::from(
+			used_gas
+				.saturating_add(touched_gas)
+				.max(pov_gas)
+				.max(storage_gas)
+				.max(gas_floor),
+		);
 
 		let actual_fee = effective_gas.saturating_mul(total_fee_per_gas);
 		let actual_base_fee = effective_gas.saturating_mul(base_fee);
@@ -394,6 +413,79 @@ where
 			logs: state.substate.logs,
 		})
 	}
+
+	/// EIP-161: clear the storage of the touched accounts the execution left empty. Checking an
+	/// account and each entry removed are paid with the gas left by the execution, and their
+	/// proof size is recorded. The storage it doesn't cover is kept, to be cleared by a later
+	/// touch. Empty accounts have no code, so they were never given a provider account and none
+	/// is removed. Returns the gas used.
+	fn remove_touched_empty_accounts(
+		touches: &BTreeSet<H160>,
+		weight_info: &mut Option<WeightInfo>,
+		gas_left: u64,
+	) -> u64 {
+		let db_weight = T::DbWeight::get();
+		// Reading the account basic, the code length and the first storage entry.
+		let check_gas = T::GasWeightMapping::weight_to_gas(db_weight.reads(3));
+		let check_proof_size = IS_EMPTY_CHECK_PROOF_SIZE.saturating_add(ACCOUNT_STORAGE_PROOF_SIZE);
+		let entry_gas = T::GasWeightMapping::weight_to_gas(db_weight.writes(1));
+
+		let mut used_gas = 0u64;
+		for address in touches {
+			if used_gas.saturating_add(check_gas) > gas_left {
+				break;
+			}
+			if let Some(weight_info) = weight_info {
+				if weight_info
+					.try_record_proof_size_or_fail(check_proof_size)
+					.is_err()
+				{
+					break;
+				}
+			}
+			used_gas = used_gas.saturating_add(check_gas);
+
+			if !Pallet::<T>::is_account_empty(address)
+				|| !<AccountStorages<T>>::contains_prefix(address)
+			{
+				continue;
+			}
+
+			let mut entries = gas_left
+				.saturating_sub(used_gas)
+				.checked_div(entry_gas)
+				.unwrap_or(u64::MAX);
+			if let Some(WeightInfo {
+				proof_size_limit: Some(limit),
+				proof_size_usage: Some(usage),
+				..
+			}) = weight_info
+			{
+				entries = entries.min(limit.saturating_sub(*usage) / WRITE_PROOF_SIZE);
+			}
+			let entries = entries.min(u32::MAX.into()) as u32;
+			if entries == 0 {
+				break;
+			}
+
+			log::debug!(
+				target: "evm",
+				"Clearing the storage of empty touched account at {:?}",
+				address
+			);
+			#[allow(deprecated)]
+			let removed = match <AccountStorages<T>>::remove_prefix(address, Some(entries)) {
+				KillStorageResult::AllRemoved(removed)
+				| KillStorageResult::SomeRemaining(removed) => u64::from(removed),
+			};
+			used_gas = used_gas.saturating_add(entry_gas.saturating_mul(removed));
+			if let Some(weight_info) = weight_info {
+				// Within the limit, checked above.
+				let _ = weight_info.try_record_proof_size_or_fail(WRITE_PROOF_SIZE * removed);
+			}
+		}
+		used_gas
+	}
 }
 
 impl<T: Config> RunnerT<T> for Runner<T>
@@ -418,9 +510,16 @@ where
 		evm_config: &evm::Config,
 	) -> Result<(), RunnerError<Self::Error>> {
 		let (base_fee, mut weight) = T::FeeCalculator::min_gas_price();
-		let (source_account, inner_weight) = Pallet::<T>::account_basic(&source);
+		let (source_account, inner_weight) = Pallet::<T>::account_basic_for_fees(&source);
 		weight = weight.saturating_add(inner_weight);
 
+		if is_transactional && input.len() > T::MaxCallDataSize::get() as usize {
+			return Err(RunnerError {
+				error: Error::<T>::CallDataTooLarge,
+				weight,
+			});
+		}
+
 		let _ = fp_evm::CheckEvmTransaction::<Self::Error>::new(
 			fp_evm::CheckEvmTransactionConfig {
 				evm_config,
@@ -459,12 +558,14 @@ where
 		gas_limit: u64,
 		max_fee_per_gas: Option<U256>,
 		max_priority_fee_per_gas: Option<U256>,
+		gas_price_override: Option<U256>,
 		nonce: Option<U256>,
 		access_list: Vec<(H160, Vec<H256>)>,
 		is_transactional: bool,
 		validate: bool,
 		weight_limit: Option<Weight>,
 		proof_size_base_cost: Option<u64>,
+		gas_floor: Option<u64>,
 		config: &evm::Config,
 	) -> Result<CallInfo, RunnerError<Self::Error>> {
 		if validate {
@@ -491,11 +592,13 @@ where
 			gas_limit,
 			max_fee_per_gas,
 			max_priority_fee_per_gas,
+			gas_price_override,
 			config,
 			&precompiles,
 			is_transactional,
 			weight_limit,
 			proof_size_base_cost,
+			gas_floor,
 			|executor| executor.transact_call(source, target, value, input, gas_limit, access_list),
 		)
 	}
@@ -507,12 +610,14 @@ where
 		gas_limit: u64,
 		max_fee_per_gas: Option<U256>,
 		max_priority_fee_per_gas: Option<U256>,
+		gas_price_override: Option<U256>,
 		nonce: Option<U256>,
 		access_list: Vec<(H160, Vec<H256>)>,
 		is_transactional: bool,
 		validate: bool,
 		weight_limit: Option<Weight>,
 		proof_size_base_cost: Option<u64>,
+		gas_floor: Option<u64>,
 		config: &evm::Config,
 	) -> Result<CreateInfo, RunnerError<Self::Error>> {
 		if validate {
@@ -539,19 +644,22 @@ where
 			gas_limit,
 			max_fee_per_gas,
 			max_priority_fee_per_gas,
+			gas_price_override,
 			config,
 			&precompiles,
 			is_transactional,
 			weight_limit,
 			proof_size_base_cost,
+			gas_floor,
 			|executor| {
 				let address = executor.create_address(evm::CreateScheme::Legacy { caller: source });
 				T::OnCreate::on_create(source, address);
-				let (reason, _) =
+				let (reason, output) =
 					executor.transact_create(source, value, init, gas_limit, access_list);
-				(reason, address)
+				(reason, (address, output))
 			},
 		)
+		.map(Into::into)
 	}
 
 	fn create2(
@@ -562,12 +670,14 @@ where
 		gas_limit: u64,
 		max_fee_per_gas: Option<U256>,
 		max_priority_fee_per_gas: Option<U256>,
+		gas_price_override: Option<U256>,
 		nonce: Option<U256>,
 		access_list: Vec<(H160, Vec<H256>)>,
 		is_transactional: bool,
 		validate: bool,
 		weight_limit: Option<Weight>,
 		proof_size_base_cost: Option<u64>,
+		gas_floor: Option<u64>,
 		config: &evm::Config,
 	) -> Result<CreateInfo, RunnerError<Self::Error>> {
 		if validate {
@@ -595,11 +705,13 @@ where
 			gas_limit,
 			max_fee_per_gas,
 			max_priority_fee_per_gas,
+			gas_price_override,
 			config,
 			&precompiles,
 			is_transactional,
 			weight_limit,
 			proof_size_base_cost,
+			gas_floor,
 			|executor| {
 				let address = executor.create_address(evm::CreateScheme::Create2 {
 					caller: source,
@@ -607,17 +719,20 @@ where
 					salt,
 				});
 				T::OnCreate::on_create(source, address);
-				let (reason, _) =
+				let (reason, output) =
 					executor.transact_create2(source, value, init, salt, gas_limit, access_list);
-				(reason, address)
+				(reason, (address, output))
 			},
 		)
+		.map(Into::into)
 	}
 }
 
 struct SubstrateStackSubstate<'config> {
 	metadata: StackSubstateMetadata<'config>,
 	deletes: BTreeSet<H160>,
+	creates: BTreeSet<H160>,
+	touches: BTreeSet<H160>,
 	logs: Vec<Log>,
 	parent: Option<Box<SubstrateStackSubstate<'config>>>,
 }
@@ -636,6 +751,8 @@ impl<'config> SubstrateStackSubstate<'config> {
 			metadata: self.metadata.spit_child(gas_limit, is_static),
 			parent: None,
 			deletes: BTreeSet::new(),
+			creates: BTreeSet::new(),
+			touches: BTreeSet::new(),
 			logs: Vec::new(),
 		};
 		mem::swap(&mut entering, self);
@@ -652,6 +769,8 @@ impl<'config> SubstrateStackSubstate<'config> {
 		self.metadata.swallow_commit(exited.metadata)?;
 		self.logs.append(&mut exited.logs);
 		self.deletes.append(&mut exited.deletes);
+		self.creates.append(&mut exited.creates);
+		self.touches.append(&mut exited.touches);
 
 		sp_io::storage::commit_transaction();
 		Ok(())
@@ -691,6 +810,26 @@ impl<'config> SubstrateStackSubstate<'config> {
 		self.deletes.insert(address);
 	}
 
+	pub fn created(&self, address: H160) -> bool {
+		if self.creates.contains(&address) {
+			return true;
+		}
+
+		if let Some(parent) = self.parent.as_ref() {
+			return parent.created(address);
+		}
+
+		false
+	}
+
+	pub fn set_created(&mut self, address: H160) {
+		self.creates.insert(address);
+	}
+
+	pub fn set_touched(&mut self, address: H160) {
+		self.touches.insert(address);
+	}
+
 	pub fn log(&mut self, address: H160, topics: Vec<H256>, data: Vec<u8>) {
 		self.logs.push(Log {
 			address,
@@ -743,6 +882,8 @@ impl<'vicinity, 'config, T: Config> SubstrateStackState<'vicinity, 'config, T> {
 			substate: SubstrateStackSubstate {
 				metadata,
 				deletes: BTreeSet::new(),
+				creates: BTreeSet::new(),
+				touches: BTreeSet::new(),
 				logs: Vec::new(),
 				parent: None,
 			},
@@ -779,7 +920,14 @@ where
 	}
 
 	fn block_hash(&self, number: U256) -> H256 {
-		if number > U256::from(u32::MAX) {
+		// Only the blocks before the current one have a hash, and within `BlockHashWindow` of it
+		// if set, whatever the `BlockHashMapping` retains. A call made on the state of a past
+		// block thus sees the hashes that block did.
+		let current = self.block_number();
+		if number >= current
+			|| number > U256::from(u32::MAX)
+			|| T::BlockHashWindow::get().is_some_and(|window| current - number > U256::from(window))
+		{
 			H256::default()
 		} else {
 			T::BlockHashMapping::block_hash(number.as_u32())
@@ -801,11 +949,13 @@ where
 	}
 
 	fn block_difficulty(&self) -> U256 {
-		U256::zero()
+		T::PrevRandao::get()
+			.map(|randomness| U256::from_big_endian(randomness.as_bytes()))
+			.unwrap_or_default()
 	}
 
 	fn block_randomness(&self) -> Option<H256> {
-		None
+		T::PrevRandao::get()
 	}
 
 	fn block_gas_limit(&self) -> U256 {
@@ -929,6 +1079,9 @@ where
 	}
 
 	fn reset_storage(&mut self, address: H160) {
+		// The executor resets the storage of every new contract before running its init code.
+		self.substate.set_created(address);
+
 		#[allow(deprecated)]
 		let _ = <AccountStorages<T>>::remove_prefix(address, None);
 	}
@@ -938,6 +1091,12 @@ where
 	}
 
 	fn set_deleted(&mut self, address: H160) {
+		// Under EIP-6780 a contract that wasn't created in this transaction survives its
+		// SELFDESTRUCT; the executor has already moved its balance to the target.
+		if T::RestrictSelfdestruct::get() && !self.substate.created(address) {
+			return;
+		}
+
 		self.substate.set_deleted(address)
 	}
 
@@ -954,6 +1113,16 @@ where
 	fn transfer(&mut self, transfer: Transfer) -> Result<(), ExitError> {
 		let source = T::AddressMapping::into_account_id(transfer.source);
 		let target = T::AddressMapping::into_account_id(transfer.target);
+		if let Some(max_balance) = T::MaxAccountBalance::get() {
+			let target_balance: U256 = <T::Currency as Currency<_>>::total_balance(&target).into();
+			if transfer.source != transfer.target
+				&& target_balance.saturating_add(transfer.value) > max_balance
+			{
+				return Err(ExitError::Other(
+					"recipient balance would exceed MaxAccountBalance".into(),
+				));
+			}
+		}
 		T::Currency::transfer(
 			&source,
 			&target,
@@ -974,12 +1143,13 @@ where
 		// issuance to be reduced. We do not need to replicate this.
 	}
 
-	fn touch(&mut self, _address: H160) {
-		// Do nothing on touch in Substrate.
-		//
+	fn touch(&mut self, address: H160) {
 		// EVM pallet considers all accounts to exist, and distinguish
-		// only empty and non-empty accounts. This avoids many of the
-		// subtle issues in EIP-161.
+		// only empty and non-empty accounts. Touched accounts are only
+		// recorded, so the runner can clear the empty ones once the
+		// execution is done if EIP-161 and `RemoveTouchedEmptyAccounts`
+		// are enabled, see `Runner::remove_touched_empty_accounts`.
+		self.substate.set_touched(address);
 	}
 
 	fn is_cold(&self, address: H160) -> bool {
@@ -993,7 +1163,7 @@ where
 	}
 
 	fn code_size(&self, address: H160) -> U256 {
-		U256::from(<Pallet<T>>::account_code_metadata(address).size)
+		U256::from(<Pallet<T>>::account_code_size(address))
 	}
 
 	fn code_hash(&self, address: H160) -> H256 {
@@ -1036,7 +1206,7 @@ where
 							let pre_size = remaining_proof_size.min(size_limit);
 							weight_info.try_record_proof_size_or_fail(pre_size)?;
 
-							let actual_size = Pallet::<T>::account_code_metadata(address).size;
+							let actual_size = Pallet::<T>::account_code_size(address);
 							if actual_size > pre_size {
 								return Err(ExitError::OutOfGas);
 							}
@@ -1130,7 +1300,7 @@ where
 						let pre_size = remaining_proof_size.min(size_limit);
 						weight_info.try_record_proof_size_or_fail(pre_size)?;
 
-						let actual_size = Pallet::<T>::account_code_metadata(address).size;
+						let actual_size = Pallet::<T>::account_code_size(address);
 						if actual_size > pre_size {
 							return Err(ExitError::OutOfGas);
 						}
@@ -1240,11 +1410,13 @@ where
 	}
 }
 
-#[cfg(feature = "forbid-evm-reentrancy")]
 #[cfg(test)]
 mod tests {
 	use super::*;
-	use crate::mock::{MockPrecompileSet, Test};
+	use crate::{
+		mock::{MockPrecompileSet, Reentrancy, Test},
+		ReentrancyPolicy,
+	};
 	use evm::ExitSucceed;
 
 	macro_rules! assert_matches {
@@ -1275,6 +1447,7 @@ mod tests {
 			100_000,
 			None,
 			None,
+			None,
 			&config,
 			&MockPrecompileSet,
 			false,
@@ -1287,6 +1460,7 @@ mod tests {
 					100_000,
 					None,
 					None,
+					None,
 					&config,
 					&MockPrecompileSet,
 					false,
@@ -1319,6 +1493,7 @@ mod tests {
 			100_000,
 			None,
 			None,
+			None,
 			&config,
 			&MockPrecompileSet,
 			false,
@@ -1328,4 +1503,54 @@ mod tests {
 		);
 		assert!(res.is_ok());
 	}
+
+	/// Enters the EVM, then `nested` more times, each one from within the previous execution.
+	fn enter(nested: u32) -> Result<(), Error<Test>> {
+		let config = evm::Config::istanbul();
+		let mut inner = Ok(());
+		let res = Runner::<Test>::execute(
+			H160::default(),
+			U256::default(),
+			100_000,
+			None,
+			None,
+			None,
+			&config,
+			&MockPrecompileSet,
+			false,
+			None,
+			None,
+			|_| {
+				if nested > 0 {
+					inner = enter(nested - 1);
+				}
+				(ExitReason::Succeed(ExitSucceed::Stopped), ())
+			},
+		);
+		res.map(|_| ()).map_err(|e| e.error).and(inner)
+	}
+
+	#[test]
+	fn forbid_reentrancy_policy_rejects_nested_execution() {
+		Reentrancy::set(ReentrancyPolicy::Forbid);
+		assert!(enter(0).is_ok());
+		assert_matches!(enter(1), Err(Error::<Test>::Reentrancy));
+	}
+
+	#[test]
+	fn allow_reentrancy_policy_accepts_nested_execution() {
+		Reentrancy::set(ReentrancyPolicy::Allow);
+		assert!(enter(5).is_ok());
+	}
+
+	#[test]
+	fn limit_depth_reentrancy_policy_bounds_nested_execution() {
+		Reentrancy::set(ReentrancyPolicy::LimitDepth(2));
+		assert!(enter(2).is_ok());
+		assert_matches!(enter(3), Err(Error::<Test>::Reentrancy));
+
+		Reentrancy::set(ReentrancyPolicy::LimitDepth(0));
+		assert!(enter(0).is_ok());
+		assert_matches!(enter(1), Err(Error::<Test>::Reentrancy));
+	}
 }
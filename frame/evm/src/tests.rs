@@ -18,8 +18,9 @@ use super::*;
 use crate::mock::*;
 
 use frame_support::{
-	assert_ok,
+	assert_err, assert_ok,
 	traits::{LockIdentifier, LockableCurrency, WithdrawReasons},
+	weights::RuntimeDbWeight,
 };
 use sp_runtime::BuildStorage;
 use std::{collections::BTreeMap, str::FromStr};
@@ -97,11 +98,13 @@ mod proof_size_test {
 			Some(FixedGasPrice::min_gas_price().0),
 			None,
 			None,
+			None,
 			Vec::new(),
 			true, // transactional
 			true, // must be validated
 			weight_limit,
 			Some(0),
+			None,
 			&<Test as Config>::config().clone(),
 		)
 	}
@@ -118,11 +121,13 @@ mod proof_size_test {
 			Some(FixedGasPrice::min_gas_price().0),
 			None,
 			None,
+			None,
 			Vec::new(),
 			true, // non-transactional
 			true, // must be validated
 			weight_limit,
 			Some(0),
+			None,
 			&<Test as Config>::config().clone(),
 		)
 	}
@@ -220,11 +225,13 @@ mod proof_size_test {
 				Some(FixedGasPrice::min_gas_price().0),
 				None,
 				None,
+				None,
 				Vec::new(),
 				true, // transactional
 				true, // must be validated
 				Some(weight_limit),
 				Some(0),
+				None,
 				&<Test as Config>::config().clone(),
 			)
 			.expect("call succeeds");
@@ -275,11 +282,13 @@ mod proof_size_test {
 				Some(FixedGasPrice::min_gas_price().0),
 				None,
 				None,
+				None,
 				Vec::new(),
 				true, // transactional
 				true, // must be validated
 				Some(weight_limit),
 				Some(0),
+				None,
 				&<Test as Config>::config().clone(),
 			)
 			.expect("call succeeds");
@@ -329,11 +338,13 @@ mod proof_size_test {
 				Some(FixedGasPrice::min_gas_price().0),
 				None,
 				None,
+				None,
 				Vec::new(),
 				true, // transactional
 				true, // must be validated
 				Some(weight_limit),
 				Some(0),
+				None,
 				&<Test as Config>::config().clone(),
 			)
 			.expect("call succeeds");
@@ -378,11 +389,13 @@ mod proof_size_test {
 				Some(FixedGasPrice::min_gas_price().0),
 				None,
 				None,
+				None,
 				Vec::new(),
 				true, // transactional
 				true, // must be validated
 				Some(weight_limit),
 				Some(0),
+				None,
 				&<Test as Config>::config().clone(),
 			)
 			.expect("call succeeds");
@@ -431,11 +444,13 @@ mod proof_size_test {
 				Some(FixedGasPrice::min_gas_price().0),
 				None,
 				None,
+				None,
 				Vec::new(),
 				true, // transactional
 				true, // must be validated
 				Some(weight_limit),
 				Some(0),
+				None,
 				&<Test as Config>::config().clone(),
 			)
 			.expect("call succeeds");
@@ -499,11 +514,13 @@ mod proof_size_test {
 				Some(FixedGasPrice::min_gas_price().0),
 				None,
 				None,
+				None,
 				Vec::new(),
 				true, // transactional
 				true, // must be validated
 				Some(weight_limit),
 				Some(0),
+				None,
 				&<Test as Config>::config().clone(),
 			)
 			.expect("call succeeds");
@@ -557,11 +574,13 @@ mod proof_size_test {
 				Some(FixedGasPrice::min_gas_price().0),
 				None,
 				None,
+				None,
 				Vec::new(),
 				true, // transactional
 				true, // must be validated
 				Some(weight_limit),
 				Some(0),
+				None,
 				&config,
 			)
 			.expect("call succeeds");
@@ -598,11 +617,13 @@ mod proof_size_test {
 				Some(FixedGasPrice::min_gas_price().0),
 				None,
 				None,
+				None,
 				Vec::new(),
 				true, // transactional
 				true, // must be validated
 				Some(weight_limit),
 				Some(0),
+				None,
 				&config,
 			)
 			.expect("call succeeds");
@@ -619,6 +640,45 @@ mod proof_size_test {
 			assert_eq!(used_gas.effective, U256::from(actual_proof_size * ratio));
 		});
 	}
+
+	#[test]
+	fn precompile_proof_size_is_accounted() {
+		new_test_ext().execute_with(|| {
+			let gas_limit: u64 = 1_000_000;
+			let weight_limit = FixedGasWeightMapping::<Test>::gas_to_weight(gas_limit, true);
+			let call_proof_size = |precompile: H160| {
+				<Test as Config>::Runner::call(
+					H160::default(),
+					precompile,
+					Vec::new(),
+					U256::zero(),
+					gas_limit,
+					Some(FixedGasPrice::min_gas_price().0),
+					None,
+					None,
+					None,
+					Vec::new(),
+					true, // transactional
+					true, // must be validated
+					Some(weight_limit),
+					Some(0),
+					None,
+					&<Test as Config>::config().clone(),
+				)
+				.expect("call succeeds")
+				.weight_info
+				.expect("weight info")
+				.proof_size_usage
+				.expect("proof size usage")
+			};
+
+			// Identity reads no state, the state reader reports its proof size to the handle.
+			let identity = call_proof_size(H160::from_low_u64_be(1));
+			let state_reader = call_proof_size(H160::from_low_u64_be(2));
+
+			assert_eq!(state_reader, identity + MOCK_STATE_READ_PROOF_SIZE);
+		});
+	}
 }
 
 mod storage_growth_test {
@@ -646,6 +706,7 @@ mod storage_growth_test {
 			Some(FixedGasPrice::min_gas_price().0),
 			None,
 			None,
+			None,
 			Vec::new(),
 			true, // transactional
 			true, // must be validated
@@ -653,6 +714,7 @@ mod storage_growth_test {
 				gas_limit, true,
 			)),
 			Some(0),
+			None,
 			<Test as Config>::config(),
 		)
 	}
@@ -673,11 +735,13 @@ mod storage_growth_test {
 			Some(FixedGasPrice::min_gas_price().0),
 			None,
 			None,
+			None,
 			Vec::new(),
 			true, // transactional
 			true, // must be validated
 			None,
 			Some(0),
+			None,
 			<Test as Config>::config(),
 		)
 	}
@@ -895,6 +959,105 @@ pub fn new_test_ext() -> sp_io::TestExternalities {
 	t.into()
 }
 
+/// Deploy `runtime_code` from the root account, returning the address of the contract.
+fn deploy(runtime_code: &[u8]) -> H160 {
+	// PUSH1 len, PUSH1 12, PUSH1 0, CODECOPY, PUSH1 len, PUSH1 0, RETURN
+	let len = u8::try_from(runtime_code.len()).expect("runtime code fits PUSH1");
+	let mut init = vec![
+		0x60, len, 0x60, 0x0c, 0x60, 0x00, 0x39, 0x60, len, 0x60, 0x00, 0xf3,
+	];
+	init.extend_from_slice(runtime_code);
+
+	let contract = <Test as Config>::Runner::create(
+		H160::default(),
+		init,
+		U256::zero(),
+		1_000_000,
+		None,
+		None,
+		None,
+		None,
+		Vec::new(),
+		false, // non-transactional
+		true,  // must be validated
+		None,
+		None,
+		None,
+		&<Test as Config>::config().clone(),
+	)
+	.expect("create succeeds")
+	.value;
+	assert_eq!(<AccountCodes<Test>>::get(contract), runtime_code.to_vec());
+	contract
+}
+
+#[test]
+fn call_and_create_input_is_limited_to_max_call_data_size() {
+	new_test_ext().execute_with(|| {
+		MaxCallDataSize::set(4);
+		let call = |input: Vec<u8>| {
+			EVM::call(
+				RuntimeOrigin::root(),
+				H160::default(),
+				H160::from_str("1000000000000000000000000000000000000001").unwrap(),
+				input,
+				U256::default(),
+				1000000,
+				U256::from(1_000_000_000),
+				None,
+				None,
+				Vec::new(),
+			)
+		};
+		// Zeroes are STOP opcodes, so the init code deploys an empty contract.
+		let create = |init: Vec<u8>| {
+			EVM::create(
+				RuntimeOrigin::root(),
+				H160::default(),
+				init,
+				U256::default(),
+				1000000,
+				U256::from(1_000_000_000),
+				None,
+				None,
+				Vec::new(),
+			)
+		};
+
+		assert_ok!(call(vec![0; 4]));
+		assert_err!(call(vec![0; 5]), Error::<Test>::CallDataTooLarge);
+		assert_ok!(create(vec![0; 4]));
+		assert_err!(create(vec![0; 5]), Error::<Test>::CallDataTooLarge);
+
+		// The runner enforces it as well, for transactional executions only.
+		let runner_call = |is_transactional: bool| {
+			<Test as Config>::Runner::call(
+				H160::default(),
+				H160::from_str("1000000000000000000000000000000000000001").unwrap(),
+				vec![0; 5],
+				U256::default(),
+				1000000,
+				Some(U256::from(1_000_000_000)),
+				None,
+				None,
+				None,
+				Vec::new(),
+				is_transactional,
+				true, // must be validated
+				None,
+				None,
+				None,
+				&<Test as Config>::config().clone(),
+			)
+		};
+		assert_eq!(
+			runner_call(true).unwrap_err().error,
+			Error::<Test>::CallDataTooLarge
+		);
+		assert!(runner_call(false).is_ok());
+	});
+}
+
 #[test]
 fn fail_call_return_ok() {
 	new_test_ext().execute_with(|| {
@@ -1019,6 +1182,43 @@ fn find_author() {
 	});
 }
 
+#[test]
+fn is_precompile() {
+	new_test_ext().execute_with(|| {
+		assert!(EVM::is_precompile(H160::from_low_u64_be(1)));
+		assert!(!EVM::is_precompile(H160::from_low_u64_be(3)));
+		assert!(!EVM::is_precompile(
+			H160::from_str("1000000000000000000000000000000000000001").unwrap()
+		));
+	});
+}
+
+#[test]
+fn accounts_basic_preserves_order() {
+	new_test_ext().execute_with(|| {
+		let funded = H160::from_str("1000000000000000000000000000000000000001").unwrap();
+		let empty = H160::from_str("7700000000000000000000000000000000000001").unwrap();
+		let author = H160::from_str("1234500000000000000000000000000000000000").unwrap();
+		let addresses = [empty, funded, author, empty];
+
+		let (accounts, weight) = EVM::accounts_basic(&addresses);
+		let expected: Vec<_> = addresses
+			.iter()
+			.map(|address| EVM::account_basic(address).0)
+			.collect();
+		assert_eq!(accounts, expected);
+		assert_eq!(accounts[0].balance, U256::zero());
+		assert_eq!(accounts[1].balance, U256::from(1_000_000));
+		assert_eq!(accounts[2].balance, U256::from(12_345));
+		assert_eq!(
+			weight,
+			<Test as frame_system::Config>::DbWeight::get().reads(2 * addresses.len() as u64)
+		);
+
+		assert_eq!(EVM::accounts_basic(&[]).0, Vec::new());
+	});
+}
+
 #[test]
 fn reducible_balance() {
 	new_test_ext().execute_with(|| {
@@ -1271,11 +1471,13 @@ fn runner_non_transactional_calls_with_non_balance_accounts_is_ok_without_gas_pr
 			None,
 			None,
 			None,
+			None,
 			Vec::new(),
 			false, // non-transactional
 			true,  // must be validated
 			None,
 			None,
+			None,
 			&<Test as Config>::config().clone(),
 		)
 		.expect("Non transactional call succeeds");
@@ -1307,17 +1509,55 @@ fn runner_non_transactional_calls_with_non_balance_accounts_is_err_with_gas_pric
 			Some(U256::from(1_000_000_000)),
 			None,
 			None,
+			None,
 			Vec::new(),
 			false, // non-transactional
 			true,  // must be validated
 			None,
 			None,
+			None,
 			&<Test as Config>::config().clone(),
 		);
 		assert!(res.is_err());
 	});
 }
 
+#[test]
+fn runner_call_charges_gas_price_override() {
+	new_test_ext().execute_with(|| {
+		let call_at_price = |gas_price: u64| {
+			let before = EVM::account_basic(&H160::default()).0.balance;
+			let info = <Test as Config>::Runner::call(
+				H160::default(),
+				H160::from_str("1000000000000000000000000000000000000001").unwrap(),
+				Vec::new(),
+				U256::zero(),
+				1000000,
+				Some(FixedGasPrice::min_gas_price().0),
+				None,
+				Some(U256::from(gas_price)),
+				None,
+				Vec::new(),
+				true,  // transactional
+				false, // not validated
+				None,
+				None,
+				None,
+				&<Test as Config>::config().clone(),
+			)
+			.expect("call succeeds");
+			let after = EVM::account_basic(&H160::default()).0.balance;
+			(before - after, info.used_gas.effective)
+		};
+
+		// The same call is charged at the forced price, regardless of the fee inputs.
+		let (low_fee, used_gas) = call_at_price(1_000_000_000);
+		let (high_fee, _) = call_at_price(2_000_000_000);
+		assert_eq!(low_fee, used_gas * 1_000_000_000);
+		assert_eq!(high_fee, used_gas * 2_000_000_000);
+	});
+}
+
 #[test]
 fn runner_transactional_call_with_zero_gas_price_fails() {
 	// Transactional calls are rejected when `max_fee_per_gas == None`.
@@ -1331,11 +1571,13 @@ fn runner_transactional_call_with_zero_gas_price_fails() {
 			None,
 			None,
 			None,
+			None,
 			Vec::new(),
 			true, // transactional
 			true, // must be validated
 			None,
 			None,
+			None,
 			&<Test as Config>::config().clone(),
 		);
 		assert!(res.is_err());
@@ -1355,11 +1597,13 @@ fn runner_max_fee_per_gas_gte_max_priority_fee_per_gas() {
 			Some(U256::from(1_000_000_000)),
 			Some(U256::from(2_000_000_000)),
 			None,
+			None,
 			Vec::new(),
 			true, // transactional
 			true, // must be validated
 			None,
 			None,
+			None,
 			&<Test as Config>::config().clone(),
 		);
 		assert!(res.is_err());
@@ -1372,17 +1616,87 @@ fn runner_max_fee_per_gas_gte_max_priority_fee_per_gas() {
 			Some(U256::from(1_000_000_000)),
 			Some(U256::from(2_000_000_000)),
 			None,
+			None,
 			Vec::new(),
 			false, // non-transactional
 			true,  // must be validated
 			None,
 			None,
+			None,
 			&<Test as Config>::config().clone(),
 		);
 		assert!(res.is_err());
 	});
 }
 
+#[test]
+fn runner_rejects_priority_fee_above_max_fee() {
+	new_test_ext().execute_with(|| {
+		let res = <Test as Config>::Runner::call(
+			H160::default(),
+			H160::from_str("1000000000000000000000000000000000000001").unwrap(),
+			Vec::new(),
+			U256::zero(),
+			1000000,
+			Some(U256::from(1_000_000_000)),
+			Some(U256::from(1_000_000_001)),
+			None,
+			None,
+			Vec::new(),
+			true, // transactional
+			true, // must be validated
+			None,
+			None,
+			None,
+			&<Test as Config>::config().clone(),
+		);
+		assert!(matches!(
+			res,
+			Err(RunnerError {
+				error: Error::PriorityFeeTooHigh,
+				..
+			})
+		));
+	});
+}
+
+#[test]
+fn transfer_above_max_account_balance_fails() {
+	new_test_ext().execute_with(|| {
+		let target = H160::from_str("1000000000000000000000000000000000000001").unwrap();
+		MaxAccountBalance::set(Some(U256::from(1_000_005)));
+
+		let call = |value: u32| {
+			<Test as Config>::Runner::call(
+				H160::default(),
+				target,
+				Vec::new(),
+				U256::from(value),
+				1000000,
+				None,
+				None,
+				None,
+				None,
+				Vec::new(),
+				true, // transactional
+				true, // must be validated
+				None,
+				None,
+				None,
+				&<Test as Config>::config().clone(),
+			)
+			.expect("call is valid")
+			.exit_reason
+		};
+
+		assert!(matches!(call(6), ExitReason::Error(ExitError::Other(_))));
+		assert_eq!(EVM::account_basic(&target).0.balance, U256::from(1_000_000));
+
+		assert!(matches!(call(5), ExitReason::Succeed(_)));
+		assert_eq!(EVM::account_basic(&target).0.balance, U256::from(1_000_005));
+	});
+}
+
 #[test]
 fn eip3607_transaction_from_contract() {
 	new_test_ext().execute_with(|| {
@@ -1397,11 +1711,13 @@ fn eip3607_transaction_from_contract() {
 			None,
 			None,
 			None,
+			None,
 			Vec::new(),
 			true,  // transactional
 			false, // not sure be validated
 			None,
 			None,
+			None,
 			&<Test as Config>::config().clone(),
 		) {
 			Err(RunnerError {
@@ -1422,11 +1738,13 @@ fn eip3607_transaction_from_contract() {
 			None,
 			None,
 			None,
+			None,
 			Vec::new(),
 			false, // non-transactional
 			true,  // must be validated
 			None,
 			None,
+			None,
 			&<Test as Config>::config().clone(),
 		)
 		.is_ok());
@@ -1469,3 +1787,1187 @@ fn metadata_empty_dont_code_gets_cached() {
 		assert!(<AccountCodesMetadata<Test>>::get(address).is_none());
 	});
 }
+
+#[test]
+fn code_size_does_not_load_code() {
+	new_test_ext().execute_with(|| {
+		let address = H160::repeat_byte(0xaa);
+
+		// Store only the length prefix of a 1000 byte code: loading the code would fail to
+		// decode it, only the length prefix can be read.
+		frame_support::storage::unhashed::put_raw(
+			&<AccountCodes<Test>>::hashed_key_for(address),
+			&scale_codec::Compact(1000u32).encode(),
+		);
+		assert!(<AccountCodes<Test>>::get(address).is_empty());
+
+		assert_eq!(crate::Pallet::<Test>::account_code_size(address), 1000);
+		// The metadata isn't computed as a side effect.
+		assert!(<AccountCodesMetadata<Test>>::get(address).is_none());
+
+		// Cached metadata takes precedence.
+		crate::Pallet::<Test>::create_account(address, b"Exemple".to_vec());
+		assert_eq!(crate::Pallet::<Test>::account_code_size(address), 7);
+	});
+}
+
+#[test]
+fn create_enforces_init_code_size_limit() {
+	new_test_ext().execute_with(|| {
+		let create = |init_code_len: usize| {
+			<Test as Config>::Runner::create(
+				H160::default(),
+				vec![0u8; init_code_len],
+				U256::zero(),
+				1_000_000,
+				Some(FixedGasPrice::min_gas_price().0),
+				None,
+				None,
+				None,
+				Vec::new(),
+				true, // transactional
+				true, // must be validated
+				None,
+				None,
+				None,
+				&<Test as Config>::config().clone(),
+			)
+		};
+
+		// Init code at the EIP-3860 limit is accepted.
+		assert!(create(49152).is_ok());
+
+		// One byte over the limit is rejected.
+		match create(49153) {
+			Err(RunnerError {
+				error: Error::CreateContractSizeLimit,
+				..
+			}) => (),
+			_ => panic!("Should have failed"),
+		}
+	});
+}
+
+#[test]
+fn integrity_test_accepts_block_gas_limit_within_block_weight() {
+	new_test_ext().execute_with(|| {
+		<EVM as frame_support::traits::Hooks<u64>>::integrity_test();
+	});
+}
+
+#[test]
+#[should_panic(expected = "BlockGasLimit must fit the block weight")]
+fn integrity_test_rejects_block_gas_limit_exceeding_block_weight() {
+	new_test_ext().execute_with(|| {
+		let max_block = <Test as frame_system::Config>::BlockWeights::get().max_block;
+		let max_block_gas = <Test as Config>::GasWeightMapping::weight_to_gas(max_block);
+		BlockGasLimit::set(U256::from(max_block_gas + 1));
+
+		<EVM as frame_support::traits::Hooks<u64>>::integrity_test();
+	});
+}
+
+#[test]
+fn evm_balance_is_clamped_at_u128_max() {
+	assert_eq!(crate::evm_balance(u64::MAX), U256::from(u64::MAX));
+	assert_eq!(crate::evm_balance(u128::MAX), U256::from(u128::MAX));
+	assert_eq!(
+		crate::evm_balance(U256::from(u128::MAX) - 1),
+		U256::from(u128::MAX) - 1
+	);
+	assert_eq!(
+		crate::evm_balance(U256::from(u128::MAX) + 1),
+		U256::from(u128::MAX)
+	);
+	assert_eq!(crate::evm_balance(U256::MAX), U256::from(u128::MAX));
+}
+
+#[test]
+fn ensure_address_mapped_checks_registered_account() {
+	#[frame_support::storage_alias]
+	type AddressRegistry = StorageMap<
+		EvmTest,
+		frame_support::Blake2_128Concat,
+		H160,
+		<Test as frame_system::Config>::AccountId,
+	>;
+	type EnsureMapped =
+		EnsureAddressMapped<<Test as frame_system::Config>::AccountId, AddressRegistry>;
+
+	new_test_ext().execute_with(|| {
+		let address = H160::from_str("1000000000000000000000000000000000000001").unwrap();
+		let owner = <Test as Config>::AddressMapping::into_account_id(H160::repeat_byte(0xaa));
+		let other = <Test as Config>::AddressMapping::into_account_id(H160::repeat_byte(0xbb));
+
+		// Unregistered addresses are rejected.
+		assert_eq!(
+			EnsureMapped::ensure_address_origin(&address, RuntimeOrigin::signed(owner.clone())),
+			Err(BadOrigin)
+		);
+
+		AddressRegistry::insert(address, owner.clone());
+		assert_eq!(
+			EnsureMapped::ensure_address_origin(&address, RuntimeOrigin::signed(owner.clone())),
+			Ok(owner)
+		);
+		assert_eq!(
+			EnsureMapped::ensure_address_origin(&address, RuntimeOrigin::signed(other)),
+			Err(BadOrigin)
+		);
+		assert_eq!(
+			EnsureMapped::ensure_address_origin(&address, RuntimeOrigin::root()),
+			Err(BadOrigin)
+		);
+	});
+}
+
+fn withdraw_full_balance(requirement: ExistenceRequirement) -> sp_runtime::DispatchResult {
+	ExistentialDeposit::set(1);
+	WithdrawExistenceRequirement::set(requirement);
+
+	let address = H160::repeat_byte(0x11);
+	let mut destination = [0u8; 32];
+	destination[..20].copy_from_slice(address.as_bytes());
+	let destination = AccountId32::from(destination);
+	let source = <Test as Config>::AddressMapping::into_account_id(address);
+	let _ = <Test as Config>::Currency::deposit_creating(&source, 1_000);
+
+	EVM::withdraw(RuntimeOrigin::signed(destination), address, 1_000)
+}
+
+#[test]
+fn withdraw_full_balance_reaps_account_when_death_is_allowed() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(withdraw_full_balance(ExistenceRequirement::AllowDeath));
+
+		let source = <Test as Config>::AddressMapping::into_account_id(H160::repeat_byte(0x11));
+		assert_eq!(<Test as Config>::Currency::free_balance(&source), 0);
+		assert!(!frame_system::Account::<Test>::contains_key(&source));
+	});
+}
+
+#[test]
+fn withdraw_full_balance_is_rejected_when_kept_alive() {
+	new_test_ext().execute_with(|| {
+		assert!(withdraw_full_balance(ExistenceRequirement::KeepAlive).is_err());
+
+		let source = <Test as Config>::AddressMapping::into_account_id(H160::repeat_byte(0x11));
+		assert_eq!(<Test as Config>::Currency::free_balance(&source), 1_000);
+	});
+}
+
+#[test]
+#[should_panic(expected = "over the 24576 bytes code size limit")]
+fn genesis_code_over_size_limit_is_rejected() {
+	let mut accounts = BTreeMap::new();
+	accounts.insert(
+		H160::repeat_byte(0x11),
+		GenesisAccount {
+			nonce: U256::zero(),
+			balance: U256::zero(),
+			storage: Default::default(),
+			code: vec![0; <Test as Config>::config().create_contract_limit.unwrap() + 1],
+		},
+	);
+
+	let _ = crate::GenesisConfig::<Test> {
+		accounts,
+		..Default::default()
+	}
+	.build_storage();
+}
+
+#[test]
+fn genesis_contract_is_kept_alive_by_a_sufficient_reference() {
+	let mut accounts = BTreeMap::new();
+	accounts.insert(
+		H160::repeat_byte(0x11),
+		GenesisAccount {
+			nonce: U256::from(1),
+			balance: U256::zero(),
+			storage: Default::default(),
+			code: vec![0x00],
+		},
+	);
+
+	let storage = crate::GenesisConfig::<Test> {
+		accounts,
+		..Default::default()
+	}
+	.build_storage()
+	.unwrap();
+
+	sp_io::TestExternalities::from(storage).execute_with(|| {
+		let contract = H160::repeat_byte(0x11);
+		let account_id = <Test as Config>::AddressMapping::into_account_id(contract);
+		assert_eq!(frame_system::Pallet::<Test>::sufficients(&account_id), 1);
+		assert_eq!(frame_system::Pallet::<Test>::providers(&account_id), 0);
+		assert!(frame_system::Pallet::<Test>::account_exists(&account_id));
+		assert_eq!(EVM::account_basic(&contract).0.nonce, U256::from(1));
+	});
+}
+
+#[test]
+#[should_panic(expected = "has neither code nor a balance of at least the existential deposit")]
+fn genesis_account_without_code_or_balance_is_rejected() {
+	let mut accounts = BTreeMap::new();
+	accounts.insert(
+		H160::repeat_byte(0x11),
+		GenesisAccount {
+			nonce: U256::from(1),
+			balance: U256::zero(),
+			storage: Default::default(),
+			code: vec![],
+		},
+	);
+
+	let _ = crate::GenesisConfig::<Test> {
+		accounts,
+		..Default::default()
+	}
+	.build_storage();
+}
+
+#[test]
+fn block_timestamp_opcode_returns_seconds() {
+	new_test_ext().execute_with(|| {
+		// `pallet_timestamp` works in milliseconds.
+		Timestamp::set_timestamp(1_700_000_000_999);
+
+		// TIMESTAMP, PUSH1 0, MSTORE, PUSH1 32, PUSH1 0, RETURN
+		let contract = H160::repeat_byte(0x42);
+		EVM::create_account(
+			contract,
+			vec![0x42, 0x60, 0x00, 0x52, 0x60, 0x20, 0x60, 0x00, 0xf3],
+		);
+
+		let info = <Test as Config>::Runner::call(
+			H160::default(),
+			contract,
+			Vec::new(),
+			U256::zero(),
+			1000000,
+			None,
+			None,
+			None,
+			None,
+			Vec::new(),
+			false, // non-transactional
+			true,  // must be validated
+			None,
+			None,
+			None,
+			&<Test as Config>::config().clone(),
+		)
+		.expect("call succeeds");
+
+		assert_eq!(
+			U256::from_big_endian(&info.value),
+			U256::from(1_700_000_000)
+		);
+	});
+}
+
+#[test]
+fn basefee_opcode_returns_the_fee_calculator_base_fee() {
+	new_test_ext().execute_with(|| {
+		// BASEFEE, PUSH1 0, MSTORE, PUSH1 32, PUSH1 0, RETURN
+		let runtime_code = [0x48, 0x60, 0x00, 0x52, 0x60, 0x20, 0x60, 0x00, 0xf3];
+		let contract = deploy(&runtime_code);
+
+		let info = <Test as Config>::Runner::call(
+			H160::default(),
+			contract,
+			Vec::new(),
+			U256::zero(),
+			1_000_000,
+			None,
+			None,
+			None,
+			None,
+			Vec::new(),
+			false, // non-transactional
+			true,  // must be validated
+			None,
+			None,
+			None,
+			&<Test as Config>::config().clone(),
+		)
+		.expect("call succeeds");
+
+		assert_eq!(
+			U256::from_big_endian(&info.value),
+			FixedGasPrice::min_gas_price().0
+		);
+	});
+}
+
+#[test]
+fn runner_create_with_reverting_constructor_returns_create_info() {
+	new_test_ext().execute_with(|| {
+		let source = H160::default();
+		let nonce = EVM::account_basic(&source).0.nonce;
+
+		// PUSH1 0xaa, PUSH1 0, MSTORE8, PUSH1 1, PUSH1 0, REVERT
+		let info = <Test as Config>::Runner::create(
+			source,
+			vec![0x60, 0xaa, 0x60, 0x00, 0x53, 0x60, 0x01, 0x60, 0x00, 0xfd],
+			U256::zero(),
+			1_000_000,
+			Some(FixedGasPrice::min_gas_price().0),
+			None,
+			None,
+			None,
+			Vec::new(),
+			true, // transactional
+			true, // must be validated
+			None,
+			None,
+			None,
+			<Test as Config>::config(),
+		)
+		.expect("a reverted create is not a runner error");
+
+		assert_eq!(info.exit_reason, ExitReason::Revert(ExitRevert::Reverted));
+		// The revert data of the constructor is returned.
+		assert_eq!(info.output, vec![0xaa]);
+		// The attempted address is reported, but nothing is deployed there.
+		assert!(!info.value.is_zero());
+		assert!(AccountCodes::<Test>::get(info.value).is_empty());
+		assert_eq!(EVM::account_basic(&source).0.nonce, nonce + 1);
+	});
+}
+
+#[test]
+fn paused_evm_rejects_calls_and_creates() {
+	new_test_ext().execute_with(|| {
+		let call = || {
+			EVM::call(
+				RuntimeOrigin::root(),
+				H160::default(),
+				H160::from_str("1000000000000000000000000000000000000001").unwrap(),
+				Vec::new(),
+				U256::default(),
+				1000000,
+				U256::from(1_000_000_000),
+				None,
+				None,
+				Vec::new(),
+			)
+		};
+		let create = || {
+			<Test as Config>::Runner::create(
+				H160::default(),
+				vec![0x00],
+				U256::zero(),
+				1_000_000,
+				None,
+				None,
+				None,
+				None,
+				Vec::new(),
+				false, // non-transactional
+				false, // no need to validate
+				None,
+				None,
+				None,
+				&<Test as Config>::config().clone(),
+			)
+		};
+
+		let who = <Test as Config>::AddressMapping::into_account_id(H160::default());
+		assert_err!(EVM::set_paused(RuntimeOrigin::signed(who), true), BadOrigin);
+		assert_ok!(EVM::set_paused(RuntimeOrigin::root(), true));
+		assert!(Paused::<Test>::get());
+
+		assert_err!(call().map_err(|e| e.error), Error::<Test>::Paused);
+		assert!(matches!(
+			create(),
+			Err(RunnerError {
+				error: Error::<Test>::Paused,
+				..
+			})
+		));
+
+		assert_ok!(EVM::set_paused(RuntimeOrigin::root(), false));
+		assert_ok!(call());
+		assert!(create().is_ok());
+	});
+}
+
+/// Converts the mock accounts back to addresses for [`DedicatedEvmAccountProvider`]. The mock
+/// address mapping is hashed, any injective conversion does for these tests.
+pub struct AccountToAddress;
+impl Convert<AccountId32, H160> for AccountToAddress {
+	fn convert(who: AccountId32) -> H160 {
+		H160::from_slice(&AsRef::<[u8]>::as_ref(&who)[..20])
+	}
+}
+
+#[test]
+fn dedicated_account_provider_nonces_are_independent_of_system_nonces() {
+	type Provider = DedicatedEvmAccountProvider<Test, AccountToAddress>;
+
+	new_test_ext().execute_with(|| {
+		let who = <Test as Config>::AddressMapping::into_account_id(H160::repeat_byte(0x11));
+
+		Provider::inc_account_nonce(&who);
+		Provider::inc_account_nonce(&who);
+		assert_eq!(Provider::account_nonce(&who), 2);
+		assert_eq!(
+			AccountNonces::<Test>::get(AccountToAddress::convert(who.clone())),
+			U256::from(2)
+		);
+		assert_eq!(frame_system::Pallet::<Test>::account_nonce(&who), 0);
+
+		frame_system::Pallet::<Test>::inc_account_nonce(&who);
+		assert_eq!(Provider::account_nonce(&who), 2);
+		assert_eq!(frame_system::Pallet::<Test>::account_nonce(&who), 1);
+	});
+}
+
+#[test]
+fn dedicated_account_provider_migration_seeds_system_nonces() {
+	use frame_support::{
+		migrations::{SteppedMigration, SteppedMigrationError},
+		traits::{GetStorageVersion, StorageVersion},
+		weights::WeightMeter,
+	};
+	type Provider = DedicatedEvmAccountProvider<Test, AccountToAddress>;
+	type Migration = crate::migrations::MigrateToV1<Test, AccountToAddress>;
+
+	new_test_ext().execute_with(|| {
+		StorageVersion::new(0).put::<EVM>();
+		DbWeight::set(RuntimeDbWeight { read: 1, write: 1 });
+		let who = <Test as Config>::AddressMapping::into_account_id(H160::repeat_byte(0x11));
+		for _ in 0..5 {
+			frame_system::Pallet::<Test>::inc_account_nonce(&who);
+		}
+		assert_eq!(Provider::account_nonce(&who), 0);
+
+		// Reading the storage version, and at least one account.
+		assert_eq!(
+			Migration::step(None, &mut WeightMeter::with_limit(Weight::from_parts(2, 0))),
+			Err(SteppedMigrationError::InsufficientWeight {
+				required: Weight::from_parts(3, 0)
+			})
+		);
+
+		// Two accounts per step.
+		let mut cursor = None;
+		let mut steps = 0;
+		loop {
+			let mut meter = WeightMeter::with_limit(Weight::from_parts(1 + 2 * 2, 0));
+			cursor = Migration::step(cursor, &mut meter).unwrap();
+			steps += 1;
+			if cursor.is_none() {
+				break;
+			}
+		}
+		assert!(steps > 1);
+		assert_eq!(Provider::account_nonce(&who), 5);
+		assert_eq!(EVM::on_chain_storage_version(), 1);
+
+		// Genesis accounts keep their nonce as well.
+		let genesis = <Test as Config>::AddressMapping::into_account_id(
+			H160::from_str("1000000000000000000000000000000000000001").unwrap(),
+		);
+		assert_eq!(Provider::account_nonce(&genesis), 1);
+
+		// The migration only runs once.
+		Provider::inc_account_nonce(&who);
+		let mut meter = WeightMeter::with_limit(Weight::from_parts(u64::MAX, 0));
+		assert_eq!(Migration::step(None, &mut meter), Ok(None));
+		assert_eq!(Provider::account_nonce(&who), 6);
+	});
+}
+
+#[test]
+fn unrefundable_fee_is_handed_to_refund_dust_handler() {
+	frame_support::parameter_types! {
+		pub static RefundDust: u64 = 0;
+		pub static BaseFee: u64 = 0;
+	}
+	pub struct DustHandler;
+	impl OnUnbalanced<NegativeImbalanceOf<Balances, Test>> for DustHandler {
+		fn on_nonzero_unbalanced(amount: NegativeImbalanceOf<Balances, Test>) {
+			RefundDust::set(amount.peek());
+		}
+	}
+	pub struct BaseFeeHandler;
+	impl OnUnbalanced<NegativeImbalanceOf<Balances, Test>> for BaseFeeHandler {
+		fn on_nonzero_unbalanced(amount: NegativeImbalanceOf<Balances, Test>) {
+			BaseFee::set(amount.peek());
+		}
+	}
+	type Adapter = EVMCurrencyAdapter<Balances, BaseFeeHandler, DustTo<DustHandler>>;
+	type DefaultAdapter = EVMCurrencyAdapter<Balances, BaseFeeHandler>;
+
+	new_test_ext().execute_with(|| {
+		ExistentialDeposit::set(100);
+		let evm_addr = H160::from_str("1000000000000000000000000000000000000003").unwrap();
+		let substrate_addr = <Test as Config>::AddressMapping::into_account_id(evm_addr);
+		let _ = <Test as Config>::Currency::deposit_creating(&substrate_addr, 150);
+
+		// Withdrawing the fee leaves the account below the existential deposit, so it is reaped.
+		let paid =
+			<Adapter as OnChargeEVMTransaction<Test>>::withdraw_fee(&evm_addr, U256::from(100))
+				.unwrap();
+		assert!(!frame_system::Account::<Test>::contains_key(
+			&substrate_addr
+		));
+
+		// The refund of 70 can't be deposited back and goes to the dust handler.
+		let tip = <Adapter as OnChargeEVMTransaction<Test>>::correct_and_deposit_fee(
+			&evm_addr,
+			U256::from(30),
+			U256::from(10),
+			paid,
+		);
+		assert_eq!(RefundDust::get(), 70);
+		assert_eq!(BaseFee::get(), 10);
+		assert_eq!(tip.map(|tip| tip.peek()), Some(20));
+		assert_eq!(Balances::free_balance(&substrate_addr), 0);
+
+		// By default, the refund is paid to the block author along with the tip.
+		RefundDust::set(0);
+		let _ = <Test as Config>::Currency::deposit_creating(&substrate_addr, 150);
+		let paid = <DefaultAdapter as OnChargeEVMTransaction<Test>>::withdraw_fee(
+			&evm_addr,
+			U256::from(100),
+		)
+		.unwrap();
+		let tip = <DefaultAdapter as OnChargeEVMTransaction<Test>>::correct_and_deposit_fee(
+			&evm_addr,
+			U256::from(30),
+			U256::from(10),
+			paid,
+		);
+		assert_eq!(RefundDust::get(), 0);
+		assert_eq!(BaseFee::get(), 10);
+		assert_eq!(tip.map(|tip| tip.peek()), Some(90));
+	});
+}
+
+#[test]
+fn tip_without_author_is_handed_to_fallback_handler() {
+	frame_support::parameter_types! {
+		pub static TipFallback: u64 = 0;
+	}
+	pub struct FallbackHandler;
+	impl OnUnbalanced<NegativeImbalanceOf<Balances, Test>> for FallbackHandler {
+		fn on_nonzero_unbalanced(amount: NegativeImbalanceOf<Balances, Test>) {
+			TipFallback::set(amount.peek());
+		}
+	}
+	impl OnUnbalanced<Credit<AccountIdOf<Test>, Balances>> for FallbackHandler {
+		fn on_nonzero_unbalanced(amount: Credit<AccountIdOf<Test>, Balances>) {
+			TipFallback::set(amount.peek());
+		}
+	}
+
+	fn pay_tip<Adapter: OnChargeEVMTransaction<Test>>() {
+		let evm_addr = H160::from_str("1000000000000000000000000000000000000003").unwrap();
+		let substrate_addr = <Test as Config>::AddressMapping::into_account_id(evm_addr);
+		let _ = <Test as Config>::Currency::deposit_creating(&substrate_addr, 150);
+
+		let paid =
+			<Adapter as OnChargeEVMTransaction<Test>>::withdraw_fee(&evm_addr, U256::from(100))
+				.unwrap();
+		let tip = <Adapter as OnChargeEVMTransaction<Test>>::correct_and_deposit_fee(
+			&evm_addr,
+			U256::from(30),
+			U256::from(10),
+			paid,
+		);
+		<Adapter as OnChargeEVMTransaction<Test>>::pay_priority_fee(tip);
+	}
+
+	new_test_ext().execute_with(|| {
+		// Simulate a block imported without an author digest.
+		BlockAuthor::set(None);
+		let zero_account = <Test as Config>::AddressMapping::into_account_id(H160::default());
+		let before = Balances::free_balance(&zero_account);
+
+		pay_tip::<EVMCurrencyAdapter<Balances, (), DustToTip, FallbackHandler>>();
+		assert_eq!(TipFallback::get(), 20);
+
+		TipFallback::set(0);
+		pay_tip::<EVMFungibleAdapter<Balances, (), DustToTip, FallbackHandler>>();
+		assert_eq!(TipFallback::get(), 20);
+
+		assert_eq!(Balances::free_balance(&zero_account), before);
+	});
+}
+
+#[test]
+fn blockhash_in_a_call_on_the_state_of_a_past_block() {
+	new_test_ext().execute_with(|| {
+		// PUSH1 0, CALLDATALOAD, BLOCKHASH, PUSH1 0, MSTORE, PUSH1 32, PUSH1 0, RETURN
+		let contract = H160::from_low_u64_be(0x40);
+		EVM::create_account(
+			contract,
+			vec![
+				0x60, 0x00, 0x35, 0x40, 0x60, 0x00, 0x52, 0x60, 0x20, 0x60, 0x00, 0xf3,
+			],
+		);
+		// Called like `eth_call` does through the runtime API.
+		let blockhash = |number: u64| {
+			let info = <Test as Config>::Runner::call(
+				H160::default(),
+				contract,
+				H256::from_low_u64_be(number).as_bytes().to_vec(),
+				U256::zero(),
+				1_000_000,
+				None,
+				None,
+				None,
+				None,
+				Vec::new(),
+				false, // non-transactional
+				true,  // must be validated
+				None,
+				None,
+				None,
+				&<Test as Config>::config().clone(),
+			)
+			.expect("call succeeds");
+			H256::from_slice(&info.value)
+		};
+		let hash = |number: u64| H256::from_low_u64_be(number + 1);
+
+		// Import blocks 1 to 300, leaving the state a call at block 300 runs on.
+		for number in 1..=300 {
+			System::initialize(&number, &hash(number - 1), &Default::default());
+			System::finalize();
+		}
+
+		// The block the call runs on has no hash yet, nor have later blocks.
+		assert_eq!(blockhash(299), hash(299));
+		assert_eq!(blockhash(300), H256::zero());
+		assert_eq!(blockhash(301), H256::zero());
+
+		// Without a window, every hash the mapping retains is visible, here the last
+		// `BlockHashCount` ones.
+		assert_eq!(blockhash(50), hash(50));
+		assert_eq!(blockhash(49), H256::zero());
+
+		BlockHashWindow::set(Some(200));
+		assert_eq!(blockhash(100), hash(100));
+		assert_eq!(blockhash(99), H256::zero());
+	});
+}
+
+#[test]
+fn prevrandao_opcode_returns_the_configured_randomness() {
+	new_test_ext().execute_with(|| {
+		// PREVRANDAO, PUSH1 0, MSTORE, PUSH1 32, PUSH1 0, RETURN
+		let contract = H160::from_low_u64_be(0x44);
+		EVM::create_account(
+			contract,
+			vec![0x44, 0x60, 0x00, 0x52, 0x60, 0x20, 0x60, 0x00, 0xf3],
+		);
+		let prevrandao = || {
+			<Test as Config>::Runner::call(
+				H160::default(),
+				contract,
+				Vec::new(),
+				U256::zero(),
+				1_000_000,
+				None,
+				None,
+				None,
+				None,
+				Vec::new(),
+				false, // non-transactional
+				true,  // must be validated
+				None,
+				None,
+				None,
+				&<Test as Config>::config().clone(),
+			)
+			.expect("call succeeds")
+			.value
+		};
+
+		assert_eq!(prevrandao(), vec![0u8; 32]);
+
+		let randomness = H256::repeat_byte(0x42);
+		PrevRandao::set(Some(randomness));
+		assert_eq!(prevrandao(), randomness.as_bytes().to_vec());
+	});
+}
+
+#[test]
+fn dispatched_call_sees_source_as_origin_and_caller() {
+	new_test_ext().execute_with(|| {
+		// ORIGIN, PUSH1 0, SSTORE, CALLER, PUSH1 1, SSTORE, STOP
+		let runtime_code = [0x32, 0x60, 0x00, 0x55, 0x33, 0x60, 0x01, 0x55, 0x00];
+		let contract = deploy(&runtime_code);
+
+		let source = H160::repeat_byte(0xaa);
+		let _ = <Test as Config>::Currency::deposit_creating(
+			&<Test as Config>::AddressMapping::into_account_id(source),
+			1_000_000_000_000_000,
+		);
+		assert_ok!(EVM::call(
+			RuntimeOrigin::root(),
+			source,
+			contract,
+			Vec::new(),
+			U256::zero(),
+			100_000,
+			U256::from(1_000_000_000),
+			None,
+			None,
+			Vec::new(),
+		));
+
+		assert_eq!(
+			<AccountStorages<Test>>::get(contract, H256::from_low_u64_be(0)),
+			H256::from(source)
+		);
+		assert_eq!(
+			<AccountStorages<Test>>::get(contract, H256::from_low_u64_be(1)),
+			H256::from(source)
+		);
+	});
+}
+
+#[test]
+fn weight_to_gas_truncates_or_rounds_up() {
+	let per_gas = <Test as Config>::WeightPerGas::get().ref_time();
+	let weight = |ref_time: u64| Weight::from_parts(ref_time, 0);
+
+	for (ref_time, truncated, rounded_up) in [
+		(0, 0, 0),
+		(1, 0, 1),
+		(per_gas - 1, 0, 1),
+		(per_gas, 1, 1),
+		(per_gas + 1, 1, 2),
+		(21_000 * per_gas - 1, 20_999, 21_000),
+	] {
+		assert_eq!(
+			FixedGasWeightMapping::<Test>::weight_to_gas(weight(ref_time)),
+			truncated
+		);
+		assert_eq!(
+			RoundingUpGasWeightMapping::<Test>::weight_to_gas(weight(ref_time)),
+			rounded_up
+		);
+	}
+
+	// Both map gas to the same weight.
+	assert_eq!(
+		RoundingUpGasWeightMapping::<Test>::gas_to_weight(21_000, true),
+		FixedGasWeightMapping::<Test>::gas_to_weight(21_000, true)
+	);
+}
+
+/// Withdraw a fee of 100 from an account holding 150, 100 of which are frozen by a lock.
+fn withdraw_fee_from_frozen_balance<WP: FeeWithdrawalPolicy<AccountIdOf<Test>>>(
+) -> Result<(), Error<Test>> {
+	let evm_addr = H160::from_str("1000000000000000000000000000000000000003").unwrap();
+	let substrate_addr = <Test as Config>::AddressMapping::into_account_id(evm_addr);
+	let _ = <Test as Config>::Currency::deposit_creating(&substrate_addr, 150);
+	Balances::set_lock(*b"te/stlok", &substrate_addr, 100, WithdrawReasons::all());
+
+	<EVMFungibleAdapter<Balances, (), DustToTip, (), WP> as OnChargeEVMTransaction<Test>>::withdraw_fee(
+		&evm_addr,
+		U256::from(100),
+	)
+	.map(|_| ())
+}
+
+#[test]
+fn fungible_fee_is_not_withdrawn_from_frozen_balance_by_default() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(
+			withdraw_fee_from_frozen_balance::<()>(),
+			Err(Error::<Test>::BalanceLow)
+		);
+	});
+}
+
+#[test]
+fn fungible_fee_is_withdrawn_from_frozen_balance_when_forced() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(
+			withdraw_fee_from_frozen_balance::<ForceFeeWithdrawal>(),
+			Ok(())
+		);
+		let substrate_addr = <Test as Config>::AddressMapping::into_account_id(
+			H160::from_str("1000000000000000000000000000000000000003").unwrap(),
+		);
+		assert_eq!(Balances::free_balance(&substrate_addr), 50);
+	});
+}
+
+/// Call an empty account with a fee of 21_000 gas at 1 gwei from an account holding
+/// 30_000 gwei, 20_000 gwei of which are frozen by a lock.
+fn call_from_frozen_balance() -> Result<(), Error<Test>> {
+	let evm_addr = H160::from_str("1000000000000000000000000000000000000004").unwrap();
+	let substrate_addr = <Test as Config>::AddressMapping::into_account_id(evm_addr);
+	let _ = <Test as Config>::Currency::deposit_creating(&substrate_addr, 30_000_000_000_000);
+	Balances::set_lock(
+		*b"te/stlok",
+		&substrate_addr,
+		20_000_000_000_000,
+		WithdrawReasons::all(),
+	);
+
+	<Test as Config>::Runner::call(
+		evm_addr,
+		H160::from_low_u64_be(0xe0),
+		Vec::new(),
+		U256::zero(),
+		21_000,
+		Some(U256::from(1_000_000_000)),
+		None,
+		None,
+		None,
+		Vec::new(),
+		true, // transactional
+		true, // must be validated
+		None,
+		None,
+		None,
+		&<Test as Config>::config().clone(),
+	)
+	.map(|_| ())
+	.map_err(|e| e.error)
+}
+
+#[test]
+fn transaction_is_not_validated_against_frozen_balance_by_default() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(call_from_frozen_balance(), Err(Error::<Test>::BalanceLow));
+	});
+}
+
+#[test]
+fn transaction_is_validated_against_frozen_balance_when_fees_are_forced() {
+	new_test_ext().execute_with(|| {
+		ForceFees::set(true);
+		assert_eq!(call_from_frozen_balance(), Ok(()));
+		let substrate_addr = <Test as Config>::AddressMapping::into_account_id(
+			H160::from_str("1000000000000000000000000000000000000004").unwrap(),
+		);
+		assert_eq!(
+			Balances::free_balance(&substrate_addr),
+			30_000_000_000_000 - 21_000_000_000_000
+		);
+	});
+}
+
+/// `CALLER SELFDESTRUCT`: sends the contract balance to the caller and destroys the contract.
+const SELFDESTRUCT_TO_CALLER: [u8; 2] = [0x33, 0xff];
+
+/// Deploy `SELFDESTRUCT_TO_CALLER` with a balance of 500 and call it in a later transaction.
+fn call_existing_selfdestructing_contract() -> (H160, H160) {
+	let contract = H160::from_low_u64_be(0x5d);
+	let caller = H160::from_low_u64_be(0xca);
+	EVM::create_account(contract, SELFDESTRUCT_TO_CALLER.to_vec());
+	let _ = <Test as Config>::Currency::deposit_creating(
+		&<Test as Config>::AddressMapping::into_account_id(contract),
+		500,
+	);
+
+	let info = <Test as Config>::Runner::call(
+		caller,
+		contract,
+		Vec::new(),
+		U256::zero(),
+		1_000_000,
+		None,
+		None,
+		None,
+		None,
+		Vec::new(),
+		false, // non-transactional
+		false, // no need to validate
+		None,
+		None,
+		None,
+		&<Test as Config>::config().clone(),
+	)
+	.expect("call succeeds");
+	assert_eq!(info.exit_reason, ExitReason::Succeed(ExitSucceed::Suicided));
+
+	(contract, caller)
+}
+
+#[test]
+fn selfdestruct_removes_existing_contract_before_cancun() {
+	new_test_ext().execute_with(|| {
+		let (contract, caller) = call_existing_selfdestructing_contract();
+
+		assert!(<AccountCodes<Test>>::get(contract).is_empty());
+		assert!(<Suicided<Test>>::contains_key(contract));
+		assert_eq!(EVM::account_basic(&caller).0.balance, U256::from(500));
+	});
+}
+
+#[test]
+fn selfdestruct_keeps_existing_contract_under_eip6780() {
+	new_test_ext().execute_with(|| {
+		RestrictSelfdestruct::set(true);
+		let (contract, caller) = call_existing_selfdestructing_contract();
+
+		// Only the balance is sent to the target.
+		assert_eq!(
+			<AccountCodes<Test>>::get(contract),
+			SELFDESTRUCT_TO_CALLER.to_vec()
+		);
+		assert!(!<Suicided<Test>>::contains_key(contract));
+		assert_eq!(EVM::account_basic(&caller).0.balance, U256::from(500));
+		assert_eq!(EVM::account_basic(&contract).0.balance, U256::zero());
+	});
+}
+
+#[test]
+fn selfdestruct_removes_contract_created_in_the_same_transaction_under_eip6780() {
+	new_test_ext().execute_with(|| {
+		RestrictSelfdestruct::set(true);
+		crate::config_preludes::SuicideQuickClearLimit::set(&10);
+
+		// PUSH1 1, PUSH1 0, SSTORE, CALLER, SELFDESTRUCT
+		let init = vec![0x60, 0x01, 0x60, 0x00, 0x55, 0x33, 0xff];
+		let info = <Test as Config>::Runner::create(
+			H160::default(),
+			init,
+			U256::zero(),
+			1_000_000,
+			None,
+			None,
+			None,
+			None,
+			Vec::new(),
+			false, // non-transactional
+			false, // no need to validate
+			None,
+			None,
+			None,
+			&<Test as Config>::config().clone(),
+		)
+		.expect("create succeeds");
+		assert_eq!(info.exit_reason, ExitReason::Succeed(ExitSucceed::Suicided));
+
+		// The storage written by the init code was cleared with the account.
+		assert_eq!(
+			<AccountStorages<Test>>::get(info.value, H256::zero()),
+			H256::zero()
+		);
+		assert_eq!(<AccountStorages<Test>>::iter_prefix(info.value).count(), 0);
+	});
+}
+
+#[test]
+fn create_collision_with_existing_account_is_rejected() {
+	new_test_ext().execute_with(|| {
+		// PUSH1 0, PUSH1 0, RETURN: deploys an empty contract.
+		let init = vec![0x60, 0x00, 0x60, 0x00, 0xf3];
+		let init_code_hash = H256::from(sp_io::hashing::keccak_256(&init));
+		let create2 = |salt: H256| {
+			<Test as Config>::Runner::create2(
+				H160::default(),
+				init.clone(),
+				salt,
+				U256::zero(),
+				1_000_000,
+				None,
+				None,
+				None,
+				None,
+				Vec::new(),
+				false, // non-transactional
+				false, // no need to validate
+				None,
+				None,
+				None,
+				&<Test as Config>::config().clone(),
+			)
+			.expect("create2 runs")
+		};
+
+		// The target address already has code.
+		let salt = H256::repeat_byte(0x01);
+		let address = fp_evm::create2_address(H160::default(), salt, init_code_hash);
+		EVM::create_account(address, vec![0x00]);
+		assert_eq!(
+			create2(salt).exit_reason,
+			ExitReason::Error(ExitError::CreateCollision)
+		);
+		assert_eq!(<AccountCodes<Test>>::get(address), vec![0x00]);
+
+		// The target address has a nonzero nonce.
+		let salt = H256::repeat_byte(0x02);
+		let address = fp_evm::create2_address(H160::default(), salt, init_code_hash);
+		frame_system::Pallet::<Test>::inc_account_nonce(
+			<Test as Config>::AddressMapping::into_account_id(address),
+		);
+		assert_eq!(
+			create2(salt).exit_reason,
+			ExitReason::Error(ExitError::CreateCollision)
+		);
+
+		// An unused address can be created at.
+		let info = create2(H256::repeat_byte(0x03));
+		assert_eq!(info.exit_reason, ExitReason::Succeed(ExitSucceed::Returned));
+	});
+}
+
+#[test]
+fn suicided_contracts_are_cleared_on_idle() {
+	let contract = H160::from_low_u64_be(0x5d);
+	let account_id = <Test as Config>::AddressMapping::into_account_id(contract);
+	let on_idle = |n: u64| <EVM as frame_support::traits::Hooks<u64>>::on_idle(n, Weight::MAX);
+
+	let mut ext = new_test_ext();
+	ext.execute_with(|| {
+		crate::config_preludes::SuicideIdleClearLimit::set(&2);
+
+		EVM::create_account(contract, vec![0x00]);
+		for index in 0..3 {
+			<AccountStorages<Test>>::insert(
+				contract,
+				H256::from_low_u64_be(index),
+				H256::repeat_byte(0x01),
+			);
+		}
+		assert_eq!(frame_system::Pallet::<Test>::sufficients(&account_id), 1);
+
+		// Without quick clear, the storage and the provider account are left over.
+		EVM::remove_account(&contract);
+		assert!(<Suicided<Test>>::contains_key(contract));
+		assert_eq!(<AccountStorages<Test>>::iter_prefix(contract).count(), 3);
+		assert_eq!(frame_system::Pallet::<Test>::sufficients(&account_id), 1);
+	});
+	// The entries removed with a limit are counted against the storage committed by the
+	// previous blocks.
+	ext.commit_all().unwrap();
+
+	ext.execute_with(|| {
+		on_idle(1);
+		assert!(<Suicided<Test>>::contains_key(contract));
+		assert_eq!(<AccountStorages<Test>>::iter_prefix(contract).count(), 1);
+	});
+	ext.commit_all().unwrap();
+
+	ext.execute_with(|| {
+		on_idle(2);
+		assert!(!<Suicided<Test>>::contains_key(contract));
+		assert_eq!(<AccountStorages<Test>>::iter_prefix(contract).count(), 0);
+		assert_eq!(frame_system::Pallet::<Test>::sufficients(&account_id), 0);
+	});
+}
+
+/// Call an empty account holding storage, returns it with the standard and effective gas used.
+fn call_empty_account_with_storage(config: &evm::Config) -> (H160, U256, U256) {
+	let empty = H160::from_low_u64_be(0xe0);
+	<AccountStorages<Test>>::insert(empty, H256::zero(), H256::repeat_byte(0x01));
+
+	let info = <Test as Config>::Runner::call(
+		H160::from_low_u64_be(0xca),
+		empty,
+		Vec::new(),
+		U256::zero(),
+		1_000_000,
+		None,
+		None,
+		None,
+		None,
+		Vec::new(),
+		false, // non-transactional
+		false, // no need to validate
+		None,
+		None,
+		None,
+		config,
+	)
+	.expect("call succeeds");
+	assert_eq!(info.exit_reason, ExitReason::Succeed(ExitSucceed::Stopped));
+
+	(empty, info.used_gas.standard, info.used_gas.effective)
+}
+
+#[test]
+fn touched_empty_account_is_removed_under_eip161() {
+	new_test_ext().execute_with(|| {
+		RemoveTouchedEmptyAccounts::set(true);
+
+		let (empty, _, _) = call_empty_account_with_storage(&<Test as Config>::config().clone());
+
+		assert!(EVM::is_account_empty(&empty));
+		assert_eq!(<AccountStorages<Test>>::iter_prefix(empty).count(), 0);
+		let account_id = <Test as Config>::AddressMapping::into_account_id(empty);
+		assert_eq!(frame_system::Pallet::<Test>::sufficients(&account_id), 0);
+		assert!(!<Suicided<Test>>::contains_key(empty));
+	});
+}
+
+#[test]
+fn touched_empty_account_is_kept_unless_enabled() {
+	new_test_ext().execute_with(|| {
+		let (empty, standard, effective) =
+			call_empty_account_with_storage(&<Test as Config>::config().clone());
+
+		assert_eq!(
+			<AccountStorages<Test>>::get(empty, H256::zero()),
+			H256::repeat_byte(0x01)
+		);
+		assert_eq!(effective, standard);
+	});
+}
+
+#[test]
+fn touched_empty_account_removal_is_paid_with_gas() {
+	new_test_ext().execute_with(|| {
+		RemoveTouchedEmptyAccounts::set(true);
+		// Each write costs 100 gas, only the single storage entry is removed.
+		DbWeight::set(RuntimeDbWeight {
+			read: 0,
+			write: <Test as Config>::WeightPerGas::get().ref_time() * 100,
+		});
+
+		let (empty, standard, effective) =
+			call_empty_account_with_storage(&<Test as Config>::config().clone());
+
+		assert_eq!(<AccountStorages<Test>>::iter_prefix(empty).count(), 0);
+		assert_eq!(effective, standard + 100);
+	});
+}
+
+#[test]
+fn touched_empty_account_is_kept_when_its_removal_exceeds_the_gas_left() {
+	new_test_ext().execute_with(|| {
+		RemoveTouchedEmptyAccounts::set(true);
+		DbWeight::set(RuntimeDbWeight {
+			read: 0,
+			write: <Test as Config>::WeightPerGas::get().ref_time() * 1_000_000,
+		});
+
+		let (empty, standard, effective) =
+			call_empty_account_with_storage(&<Test as Config>::config().clone());
+
+		assert_eq!(
+			<AccountStorages<Test>>::get(empty, H256::zero()),
+			H256::repeat_byte(0x01)
+		);
+		assert_eq!(effective, standard);
+	});
+}
+
+#[test]
+fn touched_empty_account_is_kept_before_eip161() {
+	new_test_ext().execute_with(|| {
+		RemoveTouchedEmptyAccounts::set(true);
+
+		let (empty, _, _) = call_empty_account_with_storage(&evm::Config::frontier());
+
+		assert_eq!(
+			<AccountStorages<Test>>::get(empty, H256::zero()),
+			H256::repeat_byte(0x01)
+		);
+	});
+}
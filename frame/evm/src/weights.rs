@@ -49,6 +49,7 @@ use core::marker::PhantomData;
 /// Weight functions needed for pallet_evm.
 pub trait WeightInfo {
 	fn withdraw() -> Weight;
+	fn set_paused() -> Weight;
 }
 
 /// Weights for pallet_evm using the Substrate node and recommended hardware.
@@ -61,6 +62,11 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 		// Minimum execution time: 1_564_000 picoseconds.
 		Weight::from_parts(1_696_000, 0)
 	}
+	/// Storage: `EVM::Paused` (r:0 w:1)
+	/// Proof: `EVM::Paused` (`max_values`: Some(1), `max_size`: Some(1), added: 496, mode: `MaxEncodedLen`)
+	fn set_paused() -> Weight {
+		T::DbWeight::get().reads_writes(1_u64, 1_u64)
+	}
 }
 
 // For backwards compatibility and tests
@@ -72,4 +78,9 @@ impl WeightInfo for () {
 		// Minimum execution time: 1_564_000 picoseconds.
 		Weight::from_parts(1_696_000, 0)
 	}
+	/// Storage: `EVM::Paused` (r:0 w:1)
+	/// Proof: `EVM::Paused` (`max_values`: Some(1), `max_size`: Some(1), added: 496, mode: `MaxEncodedLen`)
+	fn set_paused() -> Weight {
+		RocksDbWeight::get().reads_writes(1_u64, 1_u64)
+	}
 }
@@ -25,7 +25,11 @@ use crate::{
 	solidity::{codec::String, revert::revert},
 	EvmResult,
 };
-use alloc::{collections::btree_map::BTreeMap, vec, vec::Vec};
+use alloc::{
+	collections::{btree_map::BTreeMap, btree_set::BTreeSet},
+	vec,
+	vec::Vec,
+};
 use core::{cell::RefCell, marker::PhantomData, ops::RangeInclusive};
 use fp_evm::{
 	ExitError, IsPrecompileResult, Precompile, PrecompileFailure, PrecompileHandle,
@@ -300,6 +304,10 @@ impl<T: SelectorFilter> PrecompileChecks for CallableByPrecompile<T> {
 	}
 }
 
+/// Dummy code stored at precompile addresses, so that contracts checking the code size of a
+/// precompile see a non-empty account.
+pub const PRECOMPILE_DUMMY_CODE: [u8; 5] = [0x60, 0x00, 0x60, 0x00, 0xfd];
+
 /// The type of EVM address.
 #[derive(PartialEq)]
 #[cfg_attr(feature = "std", derive(Debug))]
@@ -337,7 +345,7 @@ pub fn get_address_type<R: pallet_evm::Config>(
 	// check code matches dummy code
 	handle.record_db_read::<R>(code_len as usize)?;
 	let code = pallet_evm::AccountCodes::<R>::get(address);
-	if code == [0x60, 0x00, 0x60, 0x00, 0xfd] {
+	if code == PRECOMPILE_DUMMY_CODE {
 		return Ok(AddressType::Precompile);
 	}
 
@@ -1160,4 +1168,33 @@ impl<R: pallet_evm::Config, P: PrecompileSetFragment> PrecompileSetBuilder<R, P>
 	pub fn summarize_checks(&self) -> Vec<PrecompileCheckSummary> {
 		self.inner.summarize_checks()
 	}
+
+	/// Check that no two precompiles of the set share an address, and that no contract is
+	/// deployed at one of them. Precompile addresses may only hold [`PRECOMPILE_DUMMY_CODE`].
+	/// This reads `AccountCodes`, so it should be run against the genesis state, e.g. from a
+	/// runtime test. Precompiles registered by prefix are not checked.
+	pub fn check_addresses() -> Result<(), PrecompileAddressCollision> {
+		let mut addresses = BTreeSet::new();
+		for address in Self::used_addresses_h160() {
+			if !addresses.insert(address) {
+				return Err(PrecompileAddressCollision::Precompile(address));
+			}
+
+			let code = pallet_evm::AccountCodes::<R>::get(address);
+			if !code.is_empty() && code != PRECOMPILE_DUMMY_CODE {
+				return Err(PrecompileAddressCollision::Contract(address));
+			}
+		}
+
+		Ok(())
+	}
+}
+
+/// Address collision found by [`PrecompileSetBuilder::check_addresses`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PrecompileAddressCollision {
+	/// Several precompiles are registered at the address.
+	Precompile(H160),
+	/// A contract is deployed at the address of a precompile.
+	Contract(H160),
 }
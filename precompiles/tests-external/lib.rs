@@ -25,7 +25,9 @@ use std::{cell::RefCell, rc::Rc};
 
 // Substrate
 use frame_support::{
-	construct_runtime, derive_impl, parameter_types, traits::Everything, weights::Weight,
+	construct_runtime, derive_impl, parameter_types,
+	traits::{ConstBool, Everything, ExistenceRequirement},
+	weights::Weight,
 };
 use sp_core::{H160, H256, U256};
 use sp_runtime::{
@@ -219,7 +221,7 @@ pub type PCall = MockPrecompileCall;
 const MAX_POV_SIZE: u64 = 5 * 1024 * 1024;
 
 parameter_types! {
-	pub BlockGasLimit: U256 = U256::from(u64::MAX);
+	pub BlockGasLimit: U256 = U256::from(1_000_000_000);
 	pub PrecompilesValue: Precompiles<Runtime> = Precompiles::new();
 	pub const WeightPerGas: Weight = Weight::from_parts(1, 0);
 	pub GasLimitPovSizeRatio: u64 = {
@@ -227,6 +229,9 @@ parameter_types! {
 		block_gas_limit.saturating_div(MAX_POV_SIZE)
 	};
 	pub SuicideQuickClearLimit: u32 = 0;
+	pub SuicideIdleClearLimit: u32 = 0;
+	pub const WithdrawExistenceRequirement: ExistenceRequirement = ExistenceRequirement::AllowDeath;
+	pub const Reentrancy: pallet_evm::ReentrancyPolicy = pallet_evm::ReentrancyPolicy::Forbid;
 }
 
 impl pallet_evm::Config for Runtime {
@@ -237,6 +242,7 @@ impl pallet_evm::Config for Runtime {
 	type BlockHashMapping = pallet_evm::SubstrateBlockHashMapping<Self>;
 	type CallOrigin = EnsureAddressRoot<AccountId>;
 	type WithdrawOrigin = EnsureAddressNever<AccountId>;
+	type WithdrawExistenceRequirement = WithdrawExistenceRequirement;
 	type AddressMapping = AccountId;
 	type Currency = Balances;
 	type RuntimeEvent = RuntimeEvent;
@@ -250,7 +256,15 @@ impl pallet_evm::Config for Runtime {
 	type FindAuthor = ();
 	type GasLimitPovSizeRatio = GasLimitPovSizeRatio;
 	type SuicideQuickClearLimit = SuicideQuickClearLimit;
+	type SuicideIdleClearLimit = SuicideIdleClearLimit;
 	type GasLimitStorageGrowthRatio = ();
+	type MaxAccountBalance = ();
+	type Reentrancy = Reentrancy;
+	type RestrictSelfdestruct = ConstBool<false>;
+	type RemoveTouchedEmptyAccounts = ConstBool<false>;
+	type PrevRandao = ();
+	type MaxCallDataSize = frame_support::traits::ConstU32<{ u32::MAX }>;
+	type BlockHashWindow = ();
 	type Timestamp = Timestamp;
 	type WeightInfo = pallet_evm::weights::SubstrateWeight<Runtime>;
 }
@@ -440,3 +454,52 @@ fn get_address_type_works_for_unknown() {
 		);
 	})
 }
+
+#[test]
+fn check_addresses_accepts_distinct_precompiles() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_eq!(Precompiles::<Runtime>::check_addresses(), Ok(()));
+
+		// The dummy code of precompiles is not a collision.
+		pallet_evm::AccountCodes::<Runtime>::insert(
+			H160::from_low_u64_be(1),
+			PRECOMPILE_DUMMY_CODE.to_vec(),
+		);
+		assert_eq!(Precompiles::<Runtime>::check_addresses(), Ok(()));
+	})
+}
+
+#[test]
+fn check_addresses_detects_precompiles_sharing_an_address() {
+	type CollidingPrecompiles<R> = PrecompileSetBuilder<
+		R,
+		(
+			PrecompileAt<AddressU64<1>, MockPrecompile>,
+			PrecompileAt<AddressU64<2>, MockPrecompile>,
+			PrecompileAt<AddressU64<1>, MockPrecompile, CallableByContract>,
+		),
+	>;
+
+	ExtBuilder::default().build().execute_with(|| {
+		let address = H160::from_low_u64_be(1);
+		assert_eq!(
+			CollidingPrecompiles::<Runtime>::check_addresses(),
+			Err(PrecompileAddressCollision::Precompile(address))
+		);
+	})
+}
+
+#[test]
+fn check_addresses_detects_contract_at_precompile_address() {
+	ExtBuilder::default().build().execute_with(|| {
+		let address = H160::from_low_u64_be(2);
+		pallet_evm::AccountCodes::<Runtime>::insert(
+			address,
+			vec![0x60, 0x00, 0x60, 0x00, 0xfd, 0xff],
+		);
+		assert_eq!(
+			Precompiles::<Runtime>::check_addresses(),
+			Err(PrecompileAddressCollision::Contract(address))
+		);
+	})
+}
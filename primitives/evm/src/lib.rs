@@ -184,10 +184,14 @@ impl WeightInfo {
 #[derive(Clone, Eq, PartialEq, Debug, Encode, Decode, TypeInfo)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct UsedGas {
-	/// The used_gas as returned by the evm gasometer on exit.
+	/// The used_gas as returned by the evm gasometer on exit, with refunds already deducted.
 	pub standard: U256,
-	/// The result of applying a gas ratio to the most used
-	/// external metric during the evm execution.
+	/// The gas the transaction is charged for: the largest of `standard` and the gas equivalent
+	/// of the proof size and storage growth recorded during the evm execution.
+	///
+	/// This is the value reported in receipts. Note that `eth_estimateGas` instead returns the
+	/// lowest gas limit the execution succeeds with, which is higher than the used gas when
+	/// refunds apply, as the refunded gas must still be available during execution.
 	pub effective: U256,
 }
 
@@ -202,7 +206,35 @@ pub struct ExecutionInfoV2<T> {
 }
 
 pub type CallInfo = ExecutionInfoV2<Vec<u8>>;
-pub type CreateInfo = ExecutionInfoV2<H160>;
+
+/// Outcome of a contract creation.
+///
+/// The `value` is the address of the contract, which is reported even when the creation failed,
+/// and `output` holds the revert data of a reverted constructor, so that its reason is not lost.
+#[derive(Clone, Eq, PartialEq, Debug, Encode, Decode, TypeInfo)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CreateInfo {
+	pub exit_reason: ExitReason,
+	pub value: H160,
+	pub output: Vec<u8>,
+	pub used_gas: UsedGas,
+	pub weight_info: Option<WeightInfo>,
+	pub logs: Vec<Log>,
+}
+
+impl From<ExecutionInfoV2<(H160, Vec<u8>)>> for CreateInfo {
+	fn from(info: ExecutionInfoV2<(H160, Vec<u8>)>) -> Self {
+		let (value, output) = info.value;
+		Self {
+			exit_reason: info.exit_reason,
+			value,
+			output,
+			used_gas: info.used_gas,
+			weight_info: info.weight_info,
+			logs: info.logs,
+		}
+	}
+}
 
 #[derive(Clone, Eq, PartialEq, Debug, Encode, Decode, TypeInfo)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -270,10 +302,50 @@ pub fn weight_per_gas(
 	weight_per_gas
 }
 
+/// Compute the address of a contract deployed with `CREATE2`, as specified by EIP-1014:
+/// the last 20 bytes of `keccak256(0xff ++ caller ++ salt ++ init_code_hash)`.
+pub fn create2_address(caller: H160, salt: H256, init_code_hash: H256) -> H160 {
+	let mut preimage = [0u8; 85];
+	preimage[0] = 0xff;
+	preimage[1..21].copy_from_slice(caller.as_bytes());
+	preimage[21..53].copy_from_slice(salt.as_bytes());
+	preimage[53..85].copy_from_slice(init_code_hash.as_bytes());
+	H160::from_slice(&sp_core::keccak_256(&preimage)[12..])
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
 
+	#[test]
+	fn test_create2_address() {
+		use core::str::FromStr;
+
+		// Examples from EIP-1014.
+		let init_code_hash = H256::from(sp_core::keccak_256(&[0x00]));
+		assert_eq!(
+			create2_address(H160::zero(), H256::zero(), init_code_hash),
+			H160::from_str("4d1a2e2bb4f88f0250f26ffff098b0b30b26bf38").unwrap()
+		);
+		assert_eq!(
+			create2_address(
+				H160::from_str("deadbeef00000000000000000000000000000000").unwrap(),
+				H256::zero(),
+				init_code_hash
+			),
+			H160::from_str("b928f69bb1d91cd65274e3c79d8986362984fda3").unwrap()
+		);
+		assert_eq!(
+			create2_address(
+				H160::from_str("deadbeef00000000000000000000000000000000").unwrap(),
+				H256::from_str("000000000000000000000000feed000000000000000000000000000000000000")
+					.unwrap(),
+				init_code_hash
+			),
+			H160::from_str("d04116cdd17bebe565eb2422f2497e06cc1c9833").unwrap()
+		);
+	}
+
 	#[test]
 	fn test_weight_per_gas() {
 		assert_eq!(
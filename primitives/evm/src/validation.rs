@@ -20,6 +20,8 @@
 use alloc::vec::Vec;
 pub use evm::backend::Basic as Account;
 use frame_support::{sp_runtime::traits::UniqueSaturatedInto, weights::Weight};
+use scale_codec::{Decode, Encode};
+use scale_info::TypeInfo;
 use sp_core::{H160, H256, U256};
 
 #[derive(Debug)]
@@ -36,6 +38,27 @@ pub struct CheckEvmTransactionInput {
 	pub access_list: Vec<(H160, Vec<H256>)>,
 }
 
+impl CheckEvmTransactionInput {
+	/// The intrinsic cost of the transaction: its base cost plus the cost of its calldata and
+	/// access list, and of its init code for contract creations.
+	pub fn transaction_cost(&self) -> evm::gasometer::TransactionCost {
+		if self.to.is_some() {
+			evm::gasometer::call_transaction_cost(&self.input, &self.access_list)
+		} else {
+			evm::gasometer::create_transaction_cost(&self.input, &self.access_list)
+		}
+	}
+
+	/// The intrinsic gas of the transaction under `evm_config`, i.e. the minimum gas limit it
+	/// needs before any execution.
+	pub fn intrinsic_gas(&self, evm_config: &evm::Config) -> u64 {
+		let mut gasometer = evm::gasometer::Gasometer::new(u64::MAX, evm_config);
+		// The intrinsic cost of a transaction can't overflow the u64 gas limit.
+		let _ = gasometer.record_transaction(self.transaction_cost());
+		gasometer.total_used_gas()
+	}
+}
+
 #[derive(Debug)]
 pub struct CheckEvmTransactionConfig<'config> {
 	pub evm_config: &'config evm::Config,
@@ -56,31 +79,56 @@ pub struct CheckEvmTransaction<'config, E: From<TransactionValidationError>> {
 
 /// Transaction validation errors
 #[repr(u8)]
-#[derive(num_enum::FromPrimitive, num_enum::IntoPrimitive, Debug)]
+#[derive(
+	Clone,
+	Copy,
+	Eq,
+	PartialEq,
+	Debug,
+	Encode,
+	Decode,
+	TypeInfo,
+	num_enum::FromPrimitive,
+	num_enum::IntoPrimitive
+)]
 pub enum TransactionValidationError {
 	/// The transaction gas limit is too low
-	GasLimitTooLow,
+	GasLimitTooLow = 0,
 	/// The transaction gas limit is too hign
-	GasLimitTooHigh,
+	GasLimitTooHigh = 1,
 	/// The transaction gas price is too low
-	GasPriceTooLow,
+	GasPriceTooLow = 2,
 	/// The transaction priority fee is too high
-	PriorityFeeTooHigh,
-	/// The transaction balance is too low
-	BalanceTooLow,
+	PriorityFeeTooHigh = 3,
+	/// The balance covers the maximum fee and the value separately, but not both
+	BalanceTooLow = 4,
 	/// The transaction nonce is too low
-	TxNonceTooLow,
+	TxNonceTooLow = 5,
 	/// The transaction nonce is too high
-	TxNonceTooHigh,
+	TxNonceTooHigh = 6,
 	/// The transaction fee input is invalid
-	InvalidFeeInput,
+	InvalidFeeInput = 7,
 	/// The chain id is incorrect
-	InvalidChainId,
+	InvalidChainId = 8,
 	/// The transaction signature is invalid
-	InvalidSignature,
+	InvalidSignature = 9,
 	/// Unknown error
 	#[num_enum(default)]
-	UnknownError,
+	UnknownError = 10,
+	/// The transaction init code exceeds the EIP-3860 size limit
+	CreateContractSizeLimit = 11,
+	/// The transaction sender or recipient is blocklisted
+	BlockedAddress = 12,
+	/// The transaction access list has more entries than allowed
+	AccessListTooLarge = 13,
+	/// The balance doesn't cover the maximum fee of the transaction
+	BalanceTooLowForFee = 14,
+	/// The balance doesn't cover the value of the transaction
+	BalanceTooLowForValue = 15,
+	/// EVM execution is paused
+	Paused = 16,
+	/// The transaction sender has code deployed (EIP-3607)
+	SenderNotEoa = 17,
 }
 
 impl<'config, E: From<TransactionValidationError>> CheckEvmTransaction<'config, E> {
@@ -152,6 +200,14 @@ impl<'config, E: From<TransactionValidationError>> CheckEvmTransaction<'config,
 		// the provided `gas_price`.
 		let fee = max_fee_per_gas.saturating_mul(self.transaction.gas_limit);
 		if self.config.is_transactional || fee > U256::zero() {
+			// The fee is withdrawn first, so a balance that can't cover it is reported as such
+			// even if it can't cover the value either.
+			if who.balance < fee {
+				return Err(TransactionValidationError::BalanceTooLowForFee.into());
+			}
+			if who.balance < self.transaction.value {
+				return Err(TransactionValidationError::BalanceTooLowForValue.into());
+			}
 			let total_payment = self.transaction.value.saturating_add(fee);
 			if who.balance < total_payment {
 				return Err(TransactionValidationError::BalanceTooLow.into());
@@ -200,6 +256,15 @@ impl<'config, E: From<TransactionValidationError>> CheckEvmTransaction<'config,
 	}
 
 	pub fn validate_common(&self) -> Result<&Self, E> {
+		// EIP-3860: init code size is capped when the evm config defines a limit.
+		if self.transaction.to.is_none() {
+			if let Some(max_initcode_size) = self.config.evm_config.max_initcode_size {
+				if self.transaction.input.len() > max_initcode_size {
+					return Err(TransactionValidationError::CreateContractSizeLimit.into());
+				}
+			}
+		}
+
 		if self.config.is_transactional {
 			// Try to subtract the proof_size_base_cost from the Weight proof_size limit or fail.
 			// Validate the weight limit can afford recording the proof size cost.
@@ -218,17 +283,7 @@ impl<'config, E: From<TransactionValidationError>> CheckEvmTransaction<'config,
 				self.transaction.gas_limit.unique_saturated_into(),
 				self.config.evm_config,
 			);
-			let transaction_cost = if self.transaction.to.is_some() {
-				evm::gasometer::call_transaction_cost(
-					&self.transaction.input,
-					&self.transaction.access_list,
-				)
-			} else {
-				evm::gasometer::create_transaction_cost(
-					&self.transaction.input,
-					&self.transaction.access_list,
-				)
-			};
+			let transaction_cost = self.transaction.transaction_cost();
 
 			if gasometer.record_transaction(transaction_cost).is_err() {
 				return Err(TransactionValidationError::GasLimitTooLow.into());
@@ -260,10 +315,21 @@ mod tests {
 		InvalidFeeInput,
 		InvalidChainId,
 		InvalidSignature,
+		CreateContractSizeLimit,
+		BlockedAddress,
+		AccessListTooLarge,
+		BalanceTooLowForFee,
+		BalanceTooLowForValue,
+		Paused,
+		SenderNotEoa,
 		UnknownError,
 	}
 
 	static SHANGHAI_CONFIG: evm::Config = evm::Config::shanghai();
+	static SHANGHAI_CONFIG_NO_INITCODE_LIMIT: evm::Config = evm::Config {
+		max_initcode_size: None,
+		..evm::Config::shanghai()
+	};
 
 	impl From<TransactionValidationError> for TestError {
 		fn from(e: TransactionValidationError) -> Self {
@@ -278,17 +344,31 @@ mod tests {
 				TransactionValidationError::InvalidFeeInput => TestError::InvalidFeeInput,
 				TransactionValidationError::InvalidChainId => TestError::InvalidChainId,
 				TransactionValidationError::InvalidSignature => TestError::InvalidSignature,
+				TransactionValidationError::CreateContractSizeLimit => {
+					TestError::CreateContractSizeLimit
+				}
+				TransactionValidationError::BlockedAddress => TestError::BlockedAddress,
+				TransactionValidationError::AccessListTooLarge => TestError::AccessListTooLarge,
+				TransactionValidationError::BalanceTooLowForFee => TestError::BalanceTooLowForFee,
+				TransactionValidationError::BalanceTooLowForValue => {
+					TestError::BalanceTooLowForValue
+				}
+				TransactionValidationError::Paused => TestError::Paused,
+				TransactionValidationError::SenderNotEoa => TestError::SenderNotEoa,
 				TransactionValidationError::UnknownError => TestError::UnknownError,
 			}
 		}
 	}
 
 	struct TestCase {
+		pub evm_config: &'static evm::Config,
 		pub blockchain_gas_limit: U256,
 		pub blockchain_base_fee: U256,
 		pub blockchain_chain_id: u64,
 		pub is_transactional: bool,
 		pub chain_id: Option<u64>,
+		pub to: Option<H160>,
+		pub input: Vec<u8>,
 		pub nonce: U256,
 		pub gas_limit: U256,
 		pub gas_price: Option<U256>,
@@ -302,11 +382,14 @@ mod tests {
 	impl Default for TestCase {
 		fn default() -> Self {
 			TestCase {
+				evm_config: &SHANGHAI_CONFIG,
 				blockchain_gas_limit: U256::max_value(),
 				blockchain_base_fee: U256::from(1_000_000_000u128),
 				blockchain_chain_id: 42u64,
 				is_transactional: true,
 				chain_id: Some(42u64),
+				to: Some(H160::default()),
+				input: vec![],
 				nonce: U256::zero(),
 				gas_limit: U256::from(21_000u64),
 				gas_price: None,
@@ -321,11 +404,14 @@ mod tests {
 
 	fn test_env<'config>(input: TestCase) -> CheckEvmTransaction<'config, TestError> {
 		let TestCase {
+			evm_config,
 			blockchain_gas_limit,
 			blockchain_base_fee,
 			blockchain_chain_id,
 			is_transactional,
 			chain_id,
+			to,
+			input,
 			nonce,
 			gas_limit,
 			gas_price,
@@ -337,7 +423,7 @@ mod tests {
 		} = input;
 		CheckEvmTransaction::<TestError>::new(
 			CheckEvmTransactionConfig {
-				evm_config: &SHANGHAI_CONFIG,
+				evm_config,
 				block_gas_limit: blockchain_gas_limit,
 				base_fee: blockchain_base_fee,
 				chain_id: blockchain_chain_id,
@@ -345,8 +431,8 @@ mod tests {
 			},
 			CheckEvmTransactionInput {
 				chain_id,
-				to: Some(H160::default()),
-				input: vec![],
+				to,
+				input,
 				nonce,
 				gas_limit,
 				gas_price,
@@ -476,6 +562,19 @@ mod tests {
 		})
 	}
 
+	fn transaction_create_with_init_code<'config>(
+		init_code_len: usize,
+		evm_config: &'static evm::Config,
+	) -> CheckEvmTransaction<'config, TestError> {
+		test_env(TestCase {
+			evm_config,
+			to: None,
+			input: vec![0u8; init_code_len],
+			gas_limit: U256::from(1_000_000u64),
+			..Default::default()
+		})
+	}
+
 	// Default (valid) transaction succeeds in pool and in block.
 	#[test]
 	fn validate_in_pool_and_block_succeeds() {
@@ -735,6 +834,39 @@ mod tests {
 		assert_eq!(res.unwrap_err(), TestError::BalanceTooLow);
 	}
 
+	// A balance that doesn't cover the maximum fee fails, whether or not it covers the value.
+	#[test]
+	fn validate_balance_too_low_for_fee_fails() {
+		let who = Account {
+			balance: U256::from(20_999_999_999_999u128),
+			nonce: U256::zero(),
+		};
+		let test = default_transaction(true);
+		let res = test.with_balance_for(&who);
+		assert_eq!(res.unwrap_err(), TestError::BalanceTooLowForFee);
+		let test = test_env(TestCase {
+			value: U256::from(30_000_000_000_000u128),
+			..Default::default()
+		});
+		let res = test.with_balance_for(&who);
+		assert_eq!(res.unwrap_err(), TestError::BalanceTooLowForFee);
+	}
+
+	// A balance that covers the maximum fee but not the value fails.
+	#[test]
+	fn validate_balance_too_low_for_value_fails() {
+		let who = Account {
+			balance: U256::from(21_000_000_000_000u128),
+			nonce: U256::zero(),
+		};
+		let test = test_env(TestCase {
+			value: U256::from(21_000_000_000_001u128),
+			..Default::default()
+		});
+		let res = test.with_balance_for(&who);
+		assert_eq!(res.unwrap_err(), TestError::BalanceTooLowForValue);
+	}
+
 	// Fee not set on transactional fails.
 	#[test]
 	fn validate_non_fee_transactional_fails() {
@@ -848,4 +980,142 @@ mod tests {
 		let res = test.with_base_fee();
 		assert!(res.is_ok());
 	}
+
+	// Init code at the EIP-3860 size limit is accepted.
+	#[test]
+	fn validate_init_code_at_size_limit_succeeds() {
+		let who = Account {
+			balance: U256::from(1_000_000_000_000_000u128),
+			nonce: U256::zero(),
+		};
+		let test = transaction_create_with_init_code(49152, &SHANGHAI_CONFIG);
+		assert!(test.validate_in_pool_for(&who).is_ok());
+		assert!(test.validate_in_block_for(&who).is_ok());
+	}
+
+	// Init code one byte over the EIP-3860 size limit is rejected.
+	#[test]
+	fn validate_init_code_over_size_limit_fails() {
+		let who = Account {
+			balance: U256::from(1_000_000_000_000_000u128),
+			nonce: U256::zero(),
+		};
+		let test = transaction_create_with_init_code(49153, &SHANGHAI_CONFIG);
+		let res = test.validate_in_pool_for(&who);
+		assert!(res.is_err());
+		assert_eq!(res.unwrap_err(), TestError::CreateContractSizeLimit);
+		let res = test.validate_in_block_for(&who);
+		assert!(res.is_err());
+		assert_eq!(res.unwrap_err(), TestError::CreateContractSizeLimit);
+	}
+
+	// Init code size is not capped when the evm config has no limit.
+	#[test]
+	fn validate_init_code_size_limit_disabled_succeeds() {
+		let who = Account {
+			balance: U256::from(1_000_000_000_000_000u128),
+			nonce: U256::zero(),
+		};
+		let test = transaction_create_with_init_code(49153, &SHANGHAI_CONFIG_NO_INITCODE_LIMIT);
+		assert!(test.validate_in_block_for(&who).is_ok());
+	}
+
+	// Init code is charged 2 gas per 32-byte word on top of the create intrinsic cost.
+	#[test]
+	fn validate_init_code_word_cost() {
+		let who = Account {
+			balance: U256::from(1_000_000_000_000_000u128),
+			nonce: U256::zero(),
+		};
+		// 53_000 base + 4 gas per zero byte + 2 gas per word, for a two words init code.
+		let intrinsic_cost = 53_000u64 + 4 * 64 + 2 * 2;
+		let test = |gas_limit: u64, evm_config: &'static evm::Config| {
+			test_env(TestCase {
+				evm_config,
+				to: None,
+				input: vec![0u8; 64],
+				gas_limit: U256::from(gas_limit),
+				..Default::default()
+			})
+		};
+		assert!(test(intrinsic_cost, &SHANGHAI_CONFIG)
+			.validate_in_block_for(&who)
+			.is_ok());
+		assert_eq!(
+			test(intrinsic_cost - 1, &SHANGHAI_CONFIG)
+				.validate_in_block_for(&who)
+				.unwrap_err(),
+			TestError::GasLimitTooLow
+		);
+		// Without EIP-3860 the word cost is not charged.
+		assert!(test(intrinsic_cost - 4, &SHANGHAI_CONFIG_NO_INITCODE_LIMIT)
+			.validate_in_block_for(&who)
+			.is_ok());
+	}
+
+	#[test]
+	fn intrinsic_gas_of_call_and_create_with_access_list() {
+		let transaction = |to: Option<H160>| CheckEvmTransactionInput {
+			chain_id: None,
+			to,
+			input: vec![0u8, 1u8],
+			nonce: U256::zero(),
+			gas_limit: U256::zero(),
+			gas_price: None,
+			max_fee_per_gas: None,
+			max_priority_fee_per_gas: None,
+			value: U256::zero(),
+			access_list: vec![(H160::default(), vec![H256::zero(), H256::repeat_byte(1)])],
+		};
+		// 4 gas per zero byte, 16 per nonzero byte, 2_400 per address and 1_900 per storage key.
+		let data_and_access_list = 4 + 16 + 2_400 + 2 * 1_900;
+		assert_eq!(
+			transaction(Some(H160::default())).intrinsic_gas(&SHANGHAI_CONFIG),
+			21_000 + data_and_access_list
+		);
+		// Creations pay 53_000 and, under EIP-3860, 2 gas per init code word.
+		assert_eq!(
+			transaction(None).intrinsic_gas(&SHANGHAI_CONFIG),
+			53_000 + data_and_access_list + 2
+		);
+		assert_eq!(
+			transaction(None).intrinsic_gas(&SHANGHAI_CONFIG_NO_INITCODE_LIMIT),
+			53_000 + data_and_access_list
+		);
+	}
+
+	#[test]
+	fn validation_error_codes_are_stable() {
+		// The codes are sent as `InvalidTransaction::Custom` and SCALE-encoded by the runtime API,
+		// so nodes and runtimes of different versions must agree on them.
+		let codes = [
+			(TransactionValidationError::GasLimitTooLow, 0u8),
+			(TransactionValidationError::GasLimitTooHigh, 1),
+			(TransactionValidationError::GasPriceTooLow, 2),
+			(TransactionValidationError::PriorityFeeTooHigh, 3),
+			(TransactionValidationError::BalanceTooLow, 4),
+			(TransactionValidationError::TxNonceTooLow, 5),
+			(TransactionValidationError::TxNonceTooHigh, 6),
+			(TransactionValidationError::InvalidFeeInput, 7),
+			(TransactionValidationError::InvalidChainId, 8),
+			(TransactionValidationError::InvalidSignature, 9),
+			(TransactionValidationError::UnknownError, 10),
+			(TransactionValidationError::CreateContractSizeLimit, 11),
+			(TransactionValidationError::BlockedAddress, 12),
+			(TransactionValidationError::AccessListTooLarge, 13),
+			(TransactionValidationError::BalanceTooLowForFee, 14),
+			(TransactionValidationError::BalanceTooLowForValue, 15),
+			(TransactionValidationError::Paused, 16),
+			(TransactionValidationError::SenderNotEoa, 17),
+		];
+		for (error, code) in codes {
+			assert_eq!(u8::from(error), code);
+			assert_eq!(TransactionValidationError::from(code), error);
+			assert_eq!(error.encode(), vec![code]);
+		}
+		assert_eq!(
+			TransactionValidationError::from(u8::MAX),
+			TransactionValidationError::UnknownError
+		);
+	}
 }
@@ -45,6 +45,82 @@ pub struct TransactionStatus {
 	pub logs_bloom: Bloom,
 }
 
+/// Fee market data of a block, read in a single runtime call.
+#[derive(Clone, Eq, PartialEq, Default, RuntimeDebug, Encode, Decode, TypeInfo)]
+pub struct BaseFeeInfo {
+	/// The base fee per gas.
+	pub base_fee: U256,
+	/// The elasticity multiplier, if the runtime is post-eip1559.
+	pub elasticity: Option<Permill>,
+	/// The gas used by the current ethereum block.
+	pub gas_used: U256,
+	/// The gas limit of the current ethereum block.
+	pub gas_limit: U256,
+}
+
+/// Hardfork and gas schedule of the EVM configuration transactions are executed with.
+#[derive(Clone, Eq, PartialEq, Default, RuntimeDebug, Encode, Decode, TypeInfo)]
+pub struct EvmConfigInfo {
+	/// Name of the latest hardfork whose features are enabled, e.g. `shanghai`.
+	pub hardfork_name: Vec<u8>,
+	/// Maximum size of deployed code (EIP-170), if limited.
+	pub max_code_size: Option<u64>,
+	/// Maximum size of init code (EIP-3860), if limited.
+	pub max_initcode_size: Option<u64>,
+	/// Intrinsic gas of a call transaction.
+	pub gas_transaction_call: u64,
+	/// Intrinsic gas of a create transaction.
+	pub gas_transaction_create: u64,
+	/// Gas per zero byte of transaction data.
+	pub gas_transaction_zero_data: u64,
+	/// Gas per non-zero byte of transaction data.
+	pub gas_transaction_non_zero_data: u64,
+	/// Gas per address of the access list.
+	pub gas_access_list_address: u64,
+	/// Gas per storage key of the access list.
+	pub gas_access_list_storage_key: u64,
+	/// Maximum depth of the call stack.
+	pub call_stack_limit: u64,
+}
+
+impl From<&fp_evm::Config> for EvmConfigInfo {
+	fn from(config: &fp_evm::Config) -> Self {
+		let hardfork_name: &[u8] = if config.has_push0 {
+			b"shanghai"
+		} else if config.has_base_fee {
+			b"london"
+		} else if config.increase_state_access_gas {
+			b"berlin"
+		} else if config.has_chain_id {
+			b"istanbul"
+		} else {
+			b"frontier"
+		};
+
+		Self {
+			hardfork_name: hardfork_name.to_vec(),
+			max_code_size: config.create_contract_limit.map(|size| size as u64),
+			max_initcode_size: config.max_initcode_size.map(|size| size as u64),
+			gas_transaction_call: config.gas_transaction_call,
+			gas_transaction_create: config.gas_transaction_create,
+			gas_transaction_zero_data: config.gas_transaction_zero_data,
+			gas_transaction_non_zero_data: config.gas_transaction_non_zero_data,
+			gas_access_list_address: config.gas_access_list_address,
+			gas_access_list_storage_key: config.gas_access_list_storage_key,
+			call_stack_limit: config.call_stack_limit as u64,
+		}
+	}
+}
+
+/// Short description of a precompile, for tooling to label calls to it.
+#[derive(Clone, Eq, PartialEq, Default, RuntimeDebug, Encode, Decode, TypeInfo)]
+pub struct PrecompileMetadata {
+	/// Name of the precompile, e.g. `ECRecover`.
+	pub name: Vec<u8>,
+	/// Whether the precompile is one of the standard Ethereum precompiles.
+	pub is_ethereum_standard: bool,
+}
+
 pub trait RuntimeStorageOverride<B: BlockT, C>: Send + Sync {
 	fn is_enabled() -> bool;
 
@@ -59,6 +135,22 @@ pub trait RuntimeStorageOverride<B: BlockT, C>: Send + Sync {
 	);
 
 	fn into_account_id_bytes(address: Address) -> Vec<u8>;
+
+	/// Read the account nonce and balance directly from the state, without executing the runtime.
+	///
+	/// The balance must be the one `account_basic` of the runtime reports, i.e. the reducible
+	/// balance given the `existential_deposit` of the runtime.
+	///
+	/// Returns `None` if direct reads are not supported by this override or the account does not
+	/// exist in the state.
+	fn account_basic(
+		_client: &C,
+		_block: B::Hash,
+		_address: Address,
+		_existential_deposit: u128,
+	) -> Option<fp_evm::Account> {
+		None
+	}
 }
 
 impl<B: BlockT, C> RuntimeStorageOverride<B, C> for () {
@@ -84,7 +176,7 @@ impl<B: BlockT, C> RuntimeStorageOverride<B, C> for () {
 
 sp_api::decl_runtime_apis! {
 	/// API necessary for Ethereum-compatibility layer.
-	#[api_version(5)]
+	#[api_version(18)]
 	pub trait EthereumRuntimeRPCApi {
 		/// Returns runtime defined pallet_evm::ChainId.
 		fn chain_id() -> u64;
@@ -188,6 +280,7 @@ sp_api::decl_runtime_apis! {
 			estimate: bool,
 			access_list: Option<Vec<(Address, Vec<H256>)>>,
 		) -> Result<fp_evm::ExecutionInfo::<Address>, sp_runtime::DispatchError>;
+		#[changed_in(18)]
 		fn create(
 			from: Address,
 			data: Vec<u8>,
@@ -199,6 +292,17 @@ sp_api::decl_runtime_apis! {
 			estimate: bool,
 			access_list: Option<Vec<(Address, Vec<H256>)>>,
 		) -> Result<fp_evm::ExecutionInfoV2::<Address>, sp_runtime::DispatchError>;
+		fn create(
+			from: Address,
+			data: Vec<u8>,
+			value: U256,
+			gas_limit: U256,
+			max_fee_per_gas: Option<U256>,
+			max_priority_fee_per_gas: Option<U256>,
+			nonce: Option<U256>,
+			estimate: bool,
+			access_list: Option<Vec<(Address, Vec<H256>)>>,
+		) -> Result<fp_evm::CreateInfo, sp_runtime::DispatchError>;
 
 		/// Return the current block. Legacy.
 		#[changed_in(2)]
@@ -244,6 +348,12 @@ sp_api::decl_runtime_apis! {
 		fn extrinsic_filter(
 			xts: Vec<<Block as BlockT>::Extrinsic>,
 		) -> Vec<ethereum::TransactionV2>;
+		/// Receives a `Vec<OpaqueExtrinsic>` and returns the ethereum transaction at `index`
+		/// among the ethereum transactions, without collecting the others.
+		fn extrinsic_at(
+			xts: Vec<<Block as BlockT>::Extrinsic>,
+			index: u32,
+		) -> Option<ethereum::TransactionV2>;
 
 		/// Return the elasticity multiplier.
 		fn elasticity() -> Option<Permill>;
@@ -262,6 +372,51 @@ sp_api::decl_runtime_apis! {
 		/// If your project don't need to have a different behavior to initialize "pending" blocks,
 		/// you can copy your Core_initialize_block implementation.
 		fn initialize_pending_block(header: &<Block as BlockT>::Header);
+
+		/// Return the base fee, elasticity, gas used and gas limit of the current block,
+		/// all read from the same state.
+		fn base_fee_info() -> BaseFeeInfo;
+
+		/// Return the block with the given number together with its receipts and transaction
+		/// statuses, or `None` if the runtime no longer retains that block.
+		fn block_by_number(
+			number: U256,
+		) -> Option<(ethereum::BlockV2, Vec<ethereum::ReceiptV3>, Vec<TransactionStatus>)>;
+
+		/// Return the canonical `EnvelopedEncodable` bytes of the transaction with the given hash,
+		/// if it is part of the current block.
+		fn raw_transaction(tx_hash: H256) -> Option<Vec<u8>>;
+
+		/// Check a signed transaction against the current state the way the transaction pool
+		/// would, returning the specific reason it would be rejected.
+		fn validate_transaction(
+			transaction: ethereum::TransactionV2,
+		) -> Result<(), fp_evm::TransactionValidationError>;
+
+		/// Return whether the given address is a precompile of the runtime.
+		fn is_precompile(address: H160) -> bool;
+
+		/// Returns pallet_evm::Accounts for each of the given addresses, in the same order.
+		fn accounts_basic(addresses: Vec<Address>) -> Vec<fp_evm::Account>;
+
+		/// Return the hardfork and gas schedule of the EVM configuration ethereum transactions
+		/// are executed with.
+		fn evm_config_info() -> EvmConfigInfo;
+
+		/// Return the cumulative gas used by the transactions already applied in the pending
+		/// block.
+		fn pending_gas_used() -> U256;
+
+		/// Return the intrinsic gas of the transaction, i.e. the minimum gas limit it needs
+		/// before any execution.
+		fn intrinsic_gas(transaction: ethereum::TransactionV2) -> u64;
+
+		/// Return the address and a short description of each precompile of the runtime.
+		fn precompiles() -> Vec<(Address, PrecompileMetadata)>;
+
+		/// Return the revert data of the ethereum transaction of the block at the given index,
+		/// if it reverted.
+		fn revert_reason(transaction_index: u32) -> Option<Vec<u8>>;
 	}
 
 	#[api_version(2)]
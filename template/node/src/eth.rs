@@ -50,6 +50,14 @@ pub struct EthConfiguration {
 	#[arg(long, default_value = "2048")]
 	pub fee_history_limit: u64,
 
+	/// Maximum number of filters kept in the EthFilterApi pool.
+	#[arg(long, default_value = "500")]
+	pub max_stored_filters: usize,
+
+	/// Number of blocks a filter is kept in the EthFilterApi pool after it was last polled.
+	#[arg(long, default_value = "100")]
+	pub filter_retain_threshold: u64,
+
 	#[arg(long)]
 	pub enable_dev_signer: bool,
 
@@ -131,6 +139,7 @@ pub async fn spawn_frontier_tasks<B, RA, HF>(
 	backend: Arc<FullBackend<B>>,
 	frontier_backend: Arc<FrontierBackend<B, FullClient<B, RA, HF>>>,
 	filter_pool: Option<FilterPool>,
+	filter_retain_threshold: u64,
 	storage_override: Arc<dyn StorageOverride<B>>,
 	fee_history_cache: FeeHistoryCache,
 	fee_history_cache_limit: FeeHistoryCacheLimit,
@@ -192,12 +201,10 @@ pub async fn spawn_frontier_tasks<B, RA, HF>(
 
 	// Spawn Frontier EthFilterApi maintenance task.
 	if let Some(filter_pool) = filter_pool {
-		// Each filter is allowed to stay in the pool for 100 blocks.
-		const FILTER_RETAIN_THRESHOLD: u64 = 100;
 		task_manager.spawn_essential_handle().spawn(
 			"frontier-filter-pool",
 			Some("frontier"),
-			EthTask::filter_pool_task(client.clone(), filter_pool, FILTER_RETAIN_THRESHOLD),
+			EthTask::filter_pool_task(client.clone(), filter_pool, filter_retain_threshold),
 		);
 	}
 
@@ -51,6 +51,8 @@ pub struct EthDeps<B: BlockT, C, P, A: ChainApi, CT, CIDP> {
 	pub block_data_cache: Arc<EthBlockDataCacheTask<B>>,
 	/// EthFilterApi pool.
 	pub filter_pool: Option<FilterPool>,
+	/// Maximum number of filters kept in the filter pool.
+	pub max_stored_filters: usize,
 	/// Maximum number of logs in a query.
 	pub max_past_logs: u32,
 	/// Fee history cache.
@@ -114,6 +116,7 @@ where
 		storage_override,
 		block_data_cache,
 		filter_pool,
+		max_stored_filters,
 		max_past_logs,
 		fee_history_cache,
 		fee_history_cache_limit,
@@ -157,7 +160,7 @@ where
 				frontier_backend.clone(),
 				graph.clone(),
 				filter_pool,
-				500_usize, // max stored filters
+				max_stored_filters,
 				max_past_logs,
 				block_data_cache.clone(),
 			)
@@ -410,6 +410,7 @@ where
 
 		let is_authority = role.is_authority();
 		let enable_dev_signer = eth_config.enable_dev_signer;
+		let max_stored_filters = eth_config.max_stored_filters;
 		let max_past_logs = eth_config.max_past_logs;
 		let execute_gas_limit_multiplier = eth_config.execute_gas_limit_multiplier;
 		let filter_pool = filter_pool.clone();
@@ -427,6 +428,8 @@ where
 
 		let slot_duration = sc_consensus_aura::slot_duration(&*client)?;
 		let target_gas_price = eth_config.target_gas_price;
+		// The pending block is built one slot ahead, so that time-dependent contract logic
+		// simulated against it observes the timestamp the next authored block would carry.
 		let pending_create_inherent_data_providers = move |_, ()| async move {
 			let current = sp_timestamp::InherentDataProvider::from_system_time();
 			let next_slot = current.timestamp().as_millis() + slot_duration.as_millis();
@@ -456,6 +459,7 @@ where
 				storage_override: storage_override.clone(),
 				block_data_cache: block_data_cache.clone(),
 				filter_pool: filter_pool.clone(),
+				max_stored_filters,
 				max_past_logs,
 				fee_history_cache: fee_history_cache.clone(),
 				fee_history_cache_limit,
@@ -503,6 +507,7 @@ where
 		backend,
 		frontier_backend,
 		filter_pool,
+		eth_config.filter_retain_threshold,
 		storage_override,
 		fee_history_cache,
 		fee_history_cache_limit,
@@ -41,7 +41,10 @@ use frame_support::{
 	derive_impl,
 	genesis_builder_helper::{build_state, get_preset},
 	parameter_types,
-	traits::{ConstBool, ConstU32, ConstU64, ConstU8, FindAuthor, OnFinalize, OnTimestampSet},
+	traits::{
+		ConstBool, ConstU32, ConstU64, ConstU8, ExistenceRequirement, FindAuthor, OnFinalize,
+		OnTimestampSet,
+	},
 	weights::{constants::WEIGHT_REF_TIME_PER_MILLIS, IdentityFee, Weight},
 };
 use pallet_transaction_payment::{ConstFeeMultiplier, FungibleAdapter};
@@ -263,6 +266,7 @@ impl pallet_grandpa::Config for Runtime {
 
 parameter_types! {
 	pub storage EnableManualSeal: bool = false;
+	pub storage ManualSealAuthor: Option<H160> = None;
 }
 
 pub struct ConsensusOnTimestampSet<T>(PhantomData<T>);
@@ -336,6 +340,21 @@ impl<F: FindAuthor<u32>> FindAuthor<H160> for FindAuthorTruncated<F> {
 	}
 }
 
+/// Manual seal produces no Aura digests, so blocks sealed that way are credited to the
+/// configured `ManualSealAuthor` instead of being looked up with `F`.
+pub struct FindAuthorOrManualSeal<F>(PhantomData<F>);
+impl<F: FindAuthor<H160>> FindAuthor<H160> for FindAuthorOrManualSeal<F> {
+	fn find_author<'a, I>(digests: I) -> Option<H160>
+	where
+		I: 'a + IntoIterator<Item = (ConsensusEngineId, &'a [u8])>,
+	{
+		if EnableManualSeal::get() {
+			return ManualSealAuthor::get();
+		}
+		F::find_author(digests)
+	}
+}
+
 const BLOCK_GAS_LIMIT: u64 = 75_000_000;
 const MAX_POV_SIZE: u64 = 5 * 1024 * 1024;
 /// The maximum storage growth per block in bytes.
@@ -348,6 +367,10 @@ parameter_types! {
 	pub PrecompilesValue: FrontierPrecompiles<Runtime> = FrontierPrecompiles::<_>::new();
 	pub WeightPerGas: Weight = Weight::from_parts(weight_per_gas(BLOCK_GAS_LIMIT, NORMAL_DISPATCH_RATIO, WEIGHT_MILLISECS_PER_BLOCK), 0);
 	pub SuicideQuickClearLimit: u32 = 0;
+	pub SuicideIdleClearLimit: u32 = 100;
+	pub const WithdrawExistenceRequirement: ExistenceRequirement = ExistenceRequirement::AllowDeath;
+	pub const Reentrancy: pallet_evm::ReentrancyPolicy = pallet_evm::ReentrancyPolicy::Allow;
+	pub const BlockHashWindow: Option<u32> = Some(256);
 }
 
 impl pallet_evm::Config for Runtime {
@@ -358,6 +381,7 @@ impl pallet_evm::Config for Runtime {
 	type BlockHashMapping = pallet_ethereum::EthereumBlockHashMapping<Self>;
 	type CallOrigin = EnsureAccountId20;
 	type WithdrawOrigin = EnsureAccountId20;
+	type WithdrawExistenceRequirement = WithdrawExistenceRequirement;
 	type AddressMapping = IdentityAddressMapping;
 	type Currency = Balances;
 	type RuntimeEvent = RuntimeEvent;
@@ -368,10 +392,18 @@ impl pallet_evm::Config for Runtime {
 	type Runner = pallet_evm::runner::stack::Runner<Self>;
 	type OnChargeTransaction = ();
 	type OnCreate = ();
-	type FindAuthor = FindAuthorTruncated<Aura>;
+	type FindAuthor = FindAuthorOrManualSeal<FindAuthorTruncated<Aura>>;
 	type GasLimitPovSizeRatio = GasLimitPovSizeRatio;
 	type SuicideQuickClearLimit = SuicideQuickClearLimit;
+	type SuicideIdleClearLimit = SuicideIdleClearLimit;
 	type GasLimitStorageGrowthRatio = GasLimitStorageGrowthRatio;
+	type MaxAccountBalance = ();
+	type Reentrancy = Reentrancy;
+	type RestrictSelfdestruct = ConstBool<false>;
+	type RemoveTouchedEmptyAccounts = ConstBool<false>;
+	type PrevRandao = ();
+	type MaxCallDataSize = ConstU32<{ 128 * 1024 }>;
+	type BlockHashWindow = BlockHashWindow;
 	type Timestamp = Timestamp;
 	type WeightInfo = pallet_evm::weights::SubstrateWeight<Self>;
 }
@@ -385,6 +417,18 @@ impl pallet_ethereum::Config for Runtime {
 	type StateRoot = pallet_ethereum::IntermediateStateRoot<Self::Version>;
 	type PostLogContent = PostBlockAndTxnHashes;
 	type ExtraDataLength = ConstU32<30>;
+	type ExtraData = ();
+	type AllowZeroGasPrice = ConstBool<false>;
+	type MaxTransactionsPerBlock = ();
+	type TransactionBlocklist = ();
+	type BlockTransactionRecipients = ConstBool<true>;
+	type EmitExecutedEvent = ConstBool<true>;
+	type ExtrinsicBaseGas = ();
+	type CalldataFloorPricing = ConstBool<false>;
+	type MaxAccessListSize = ConstU32<1024>;
+	type BlockHashPruningLimit = ConstU32<32>;
+	type MaxStateRootEntries = ConstU32<0>;
+	type WeightInfo = pallet_ethereum::weights::SubstrateWeight<Self>;
 }
 
 parameter_types! {
@@ -433,6 +477,8 @@ pub mod pallet_manual_seal {
 	#[derive(frame_support::DefaultNoBound)]
 	pub struct GenesisConfig<T> {
 		pub enable: bool,
+		/// Author of the blocks sealed while manual seal is enabled.
+		pub author: Option<H160>,
 		#[serde(skip)]
 		pub _config: PhantomData<T>,
 	}
@@ -441,6 +487,7 @@ pub mod pallet_manual_seal {
 	impl<T: Config> BuildGenesisConfig for GenesisConfig<T> {
 		fn build(&self) {
 			EnableManualSeal::set(&self.enable);
+			ManualSealAuthor::set(&self.author);
 		}
 	}
 }
@@ -524,6 +571,16 @@ impl<B: BlockT> fp_rpc::ConvertTransaction<<B as BlockT>::Extrinsic> for Transac
 	}
 }
 
+/// The ethereum transactions among `xts`, in order.
+fn ethereum_transactions(
+	xts: Vec<UncheckedExtrinsic>,
+) -> impl Iterator<Item = EthereumTransaction> {
+	xts.into_iter().filter_map(|xt| match xt.0.function {
+		RuntimeCall::Ethereum(transact { transaction }) => Some(transaction),
+		_ => None,
+	})
+}
+
 impl fp_self_contained::SelfContainedCall for RuntimeCall {
 	type SignedInfo = H160;
 
@@ -590,7 +647,9 @@ mod benches {
 		[pallet_balances, Balances]
 		[pallet_timestamp, Timestamp]
 		[pallet_sudo, Sudo]
+		[pallet_ethereum, Ethereum]
 		[pallet_evm, EVM]
+		[pallet_evm_precompile_benchmarking, PrecompileBench::<Runtime>]
 	);
 }
 
@@ -802,13 +861,10 @@ impl_runtime_apis! {
 		) -> Result<pallet_evm::CallInfo, sp_runtime::DispatchError> {
 			use pallet_evm::GasWeightMapping as _;
 
-			let config = if estimate {
-				let mut config = <Runtime as pallet_evm::Config>::config().clone();
-				config.estimate = true;
-				Some(config)
-			} else {
-				None
-			};
+			// Ethereum transactions are executed with `ExtrinsicBaseGas` on top of the intrinsic
+			// cost, so estimates must include it too.
+			let mut config = pallet_ethereum::Pallet::<Runtime>::evm_config();
+			config.estimate = estimate;
 
 			// Estimated encoded transaction size must be based on the heaviest transaction
 			// type (EIP1559Transaction) to be compatible with all transaction types.
@@ -858,13 +914,15 @@ impl_runtime_apis! {
 				gas_limit.unique_saturated_into(),
 				max_fee_per_gas,
 				max_priority_fee_per_gas,
+				None,
 				nonce,
 				access_list.unwrap_or_default(),
 				false,
 				true,
 				weight_limit,
 				proof_size_base_cost,
-				config.as_ref().unwrap_or(<Runtime as pallet_evm::Config>::config()),
+				None,
+				&config,
 			).map_err(|err| err.error.into())
 		}
 
@@ -881,13 +939,10 @@ impl_runtime_apis! {
 		) -> Result<pallet_evm::CreateInfo, sp_runtime::DispatchError> {
 			use pallet_evm::GasWeightMapping as _;
 
-			let config = if estimate {
-				let mut config = <Runtime as pallet_evm::Config>::config().clone();
-				config.estimate = true;
-				Some(config)
-			} else {
-				None
-			};
+			// Ethereum transactions are executed with `ExtrinsicBaseGas` on top of the intrinsic
+			// cost, so estimates must include it too.
+			let mut config = pallet_ethereum::Pallet::<Runtime>::evm_config();
+			config.estimate = estimate;
 
 
 			let mut estimated_transaction_len = data.len() +
@@ -936,13 +991,15 @@ impl_runtime_apis! {
 				gas_limit.unique_saturated_into(),
 				max_fee_per_gas,
 				max_priority_fee_per_gas,
+				None,
 				nonce,
 				access_list.unwrap_or_default(),
 				false,
 				true,
 				weight_limit,
 				proof_size_base_cost,
-				config.as_ref().unwrap_or(<Runtime as pallet_evm::Config>::config()),
+				None,
+				&config,
 			).map_err(|err| err.error.into())
 		}
 
@@ -973,10 +1030,14 @@ impl_runtime_apis! {
 		fn extrinsic_filter(
 			xts: Vec<<Block as BlockT>::Extrinsic>,
 		) -> Vec<EthereumTransaction> {
-			xts.into_iter().filter_map(|xt| match xt.0.function {
-				RuntimeCall::Ethereum(transact { transaction }) => Some(transaction),
-				_ => None
-			}).collect::<Vec<EthereumTransaction>>()
+			ethereum_transactions(xts).collect::<Vec<EthereumTransaction>>()
+		}
+
+		fn extrinsic_at(
+			xts: Vec<<Block as BlockT>::Extrinsic>,
+			index: u32,
+		) -> Option<EthereumTransaction> {
+			ethereum_transactions(xts).nth(index as usize)
 		}
 
 		fn elasticity() -> Option<Permill> {
@@ -1003,6 +1064,64 @@ impl_runtime_apis! {
 		fn initialize_pending_block(header: &<Block as BlockT>::Header) {
 			Executive::initialize_block(header);
 		}
+
+		fn base_fee_info() -> fp_rpc::BaseFeeInfo {
+			let (base_fee, _) = <Runtime as pallet_evm::Config>::FeeCalculator::min_gas_price();
+			let (gas_used, gas_limit) = pallet_ethereum::CurrentBlock::<Runtime>::get()
+				.map(|block| (block.header.gas_used, block.header.gas_limit))
+				.unwrap_or_default();
+			fp_rpc::BaseFeeInfo {
+				base_fee,
+				elasticity: Some(pallet_base_fee::Elasticity::<Runtime>::get()),
+				gas_used,
+				gas_limit,
+			}
+		}
+
+		fn block_by_number(
+			number: U256,
+		) -> Option<(pallet_ethereum::Block, Vec<pallet_ethereum::Receipt>, Vec<TransactionStatus>)> {
+			pallet_ethereum::Pallet::<Runtime>::block_by_number(number)
+		}
+
+		fn raw_transaction(tx_hash: H256) -> Option<Vec<u8>> {
+			pallet_ethereum::Pallet::<Runtime>::raw_transaction(tx_hash)
+		}
+
+		fn validate_transaction(
+			transaction: EthereumTransaction,
+		) -> Result<(), fp_evm::TransactionValidationError> {
+			pallet_ethereum::Pallet::<Runtime>::validate_transaction(&transaction)
+		}
+
+		fn is_precompile(address: H160) -> bool {
+			pallet_evm::Pallet::<Runtime>::is_precompile(address)
+		}
+
+		fn accounts_basic(addresses: Vec<H160>) -> Vec<EVMAccount> {
+			let (accounts, _) = pallet_evm::Pallet::<Runtime>::accounts_basic(&addresses);
+			accounts
+		}
+
+		fn evm_config_info() -> fp_rpc::EvmConfigInfo {
+			pallet_ethereum::Pallet::<Runtime>::evm_config_info()
+		}
+
+		fn pending_gas_used() -> U256 {
+			pallet_ethereum::Pallet::<Runtime>::pending_gas_used()
+		}
+
+		fn intrinsic_gas(transaction: EthereumTransaction) -> u64 {
+			pallet_ethereum::Pallet::<Runtime>::intrinsic_gas(&transaction)
+		}
+
+		fn precompiles() -> Vec<(H160, fp_rpc::PrecompileMetadata)> {
+			FrontierPrecompiles::<Runtime>::metadata()
+		}
+
+		fn revert_reason(transaction_index: u32) -> Option<Vec<u8>> {
+			pallet_ethereum::RevertReasons::<Runtime>::get(transaction_index).map(Into::into)
+		}
 	}
 
 	impl fp_rpc::ConvertTransactionRuntimeApi<Block> for Runtime {
@@ -1024,6 +1143,7 @@ impl_runtime_apis! {
 
 			use baseline::Pallet as BaselineBench;
 			use frame_system_benchmarking::Pallet as SystemBench;
+			use pallet_evm_precompile_benchmarking::Pallet as PrecompileBench;
 
 			let mut list = Vec::<BenchmarkList>::new();
 			list_benchmarks!(list, extra);
@@ -1040,9 +1160,11 @@ impl_runtime_apis! {
 
 			use baseline::Pallet as BaselineBench;
 			use frame_system_benchmarking::Pallet as SystemBench;
+			use pallet_evm_precompile_benchmarking::Pallet as PrecompileBench;
 
 			impl baseline::Config for Runtime {}
 			impl frame_system_benchmarking::Config for Runtime {}
+			impl pallet_evm_precompile_benchmarking::Config for Runtime {}
 
 			let whitelist: Vec<TrackedStorageKey> = Vec::new();
 
@@ -1056,7 +1178,17 @@ impl_runtime_apis! {
 
 #[cfg(test)]
 mod tests {
-	use super::{Runtime, WeightPerGas};
+	use super::{
+		ethereum_transactions, pallet_manual_seal, transact, AccountId, Balances,
+		FrontierPrecompiles, Runtime, RuntimeCall, RuntimeGenesisConfig, UncheckedExtrinsic,
+		WeightPerGas,
+	};
+	use ethereum::{LegacyTransaction, TransactionAction, TransactionSignature, TransactionV2};
+	use frame_support::traits::fungible::{Balanced, Inspect};
+	use pallet_evm::OnChargeEVMTransaction;
+	use sp_core::{H160, H256, U256};
+	use sp_runtime::BuildStorage;
+
 	#[test]
 	fn configured_base_extrinsic_weight_is_evm_compatible() {
 		let min_ethereum_transaction_weight = WeightPerGas::get() * 21_000;
@@ -1065,4 +1197,98 @@ mod tests {
 			.base_extrinsic;
 		assert!(base_extrinsic.ref_time() <= min_ethereum_transaction_weight.ref_time());
 	}
+
+	#[test]
+	fn ethereum_transactions_skips_other_extrinsics() {
+		let ethereum_xt = |nonce: u64| {
+			let transaction = TransactionV2::Legacy(LegacyTransaction {
+				nonce: U256::from(nonce),
+				gas_price: U256::zero(),
+				gas_limit: U256::from(21_000),
+				action: TransactionAction::Create,
+				value: U256::zero(),
+				input: Vec::new(),
+				signature: TransactionSignature::new(
+					27,
+					H256::repeat_byte(1),
+					H256::repeat_byte(1),
+				)
+				.unwrap(),
+			});
+			(
+				UncheckedExtrinsic::new_unsigned(
+					transact {
+						transaction: transaction.clone(),
+					}
+					.into(),
+				),
+				transaction,
+			)
+		};
+		let remark =
+			UncheckedExtrinsic::new_unsigned(RuntimeCall::System(frame_system::Call::remark {
+				remark: Vec::new(),
+			}));
+		let (xt0, tx0) = ethereum_xt(0);
+		let (xt1, tx1) = ethereum_xt(1);
+		let xts = vec![remark.clone(), xt0, remark, xt1];
+
+		assert_eq!(
+			ethereum_transactions(xts.clone()).collect::<Vec<_>>(),
+			vec![tx0, tx1.clone()]
+		);
+		assert_eq!(ethereum_transactions(xts.clone()).nth(1), Some(tx1));
+		assert_eq!(ethereum_transactions(xts).nth(2), None);
+	}
+
+	#[test]
+	fn tips_go_to_the_manual_seal_author() {
+		let author = H160::repeat_byte(0xaa);
+		let mut ext: sp_io::TestExternalities = RuntimeGenesisConfig {
+			manual_seal: pallet_manual_seal::GenesisConfig {
+				enable: true,
+				author: Some(author),
+				..Default::default()
+			},
+			..Default::default()
+		}
+		.build_storage()
+		.unwrap()
+		.into();
+
+		ext.execute_with(|| {
+			assert_eq!(
+				pallet_evm::Pallet::<Runtime>::try_find_author(),
+				Some(author)
+			);
+
+			let tip = <Balances as Balanced<AccountId>>::issue(100);
+			<<Runtime as pallet_evm::Config>::OnChargeTransaction as OnChargeEVMTransaction<
+				Runtime,
+			>>::pay_priority_fee(Some(tip));
+
+			assert_eq!(Balances::balance(&AccountId::from(author)), 100);
+		});
+	}
+
+	#[test]
+	fn precompiles_metadata_describes_the_used_addresses() {
+		let metadata = FrontierPrecompiles::<Runtime>::metadata();
+		assert_eq!(
+			metadata
+				.iter()
+				.map(|(address, _)| *address)
+				.collect::<Vec<_>>(),
+			FrontierPrecompiles::<Runtime>::used_addresses().to_vec()
+		);
+		assert_eq!(
+			metadata
+				.iter()
+				.filter(|(_, metadata)| metadata.is_ethereum_standard)
+				.map(|(address, _)| address.to_low_u64_be())
+				.collect::<Vec<_>>(),
+			vec![1, 2, 3, 4, 5]
+		);
+		assert_eq!(metadata[0].1.name, b"ECRecover".to_vec());
+	}
 }
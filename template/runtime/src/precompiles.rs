@@ -1,4 +1,6 @@
+use alloc::{vec, vec::Vec};
 use core::marker::PhantomData;
+use fp_rpc::PrecompileMetadata;
 use pallet_evm::{
 	IsPrecompileResult, Precompile, PrecompileHandle, PrecompileResult, PrecompileSet,
 };
@@ -28,6 +30,22 @@ where
 			hash(1025),
 		]
 	}
+	/// The address and description of each of the `used_addresses`.
+	pub fn metadata() -> Vec<(H160, PrecompileMetadata)> {
+		let metadata = |name: &[u8], is_ethereum_standard: bool| PrecompileMetadata {
+			name: name.to_vec(),
+			is_ethereum_standard,
+		};
+		vec![
+			(hash(1), metadata(b"ECRecover", true)),
+			(hash(2), metadata(b"Sha256", true)),
+			(hash(3), metadata(b"Ripemd160", true)),
+			(hash(4), metadata(b"Identity", true)),
+			(hash(5), metadata(b"Modexp", true)),
+			(hash(1024), metadata(b"Sha3FIPS256", false)),
+			(hash(1025), metadata(b"ECRecoverPublicKey", false)),
+		]
+	}
 }
 impl<R> PrecompileSet for FrontierPrecompiles<R>
 where
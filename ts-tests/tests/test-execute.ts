@@ -263,4 +263,22 @@ describeWithFrontier("Frontier RPC (RPC execution)", (context) => {
 		]);
 		expect(result.error.data).to.match(/^Ambiguous value for `data` and `input`/);
 	});
+
+	step("pending call observes a timestamp ahead of the latest block", async function () {
+		// TIMESTAMP PUSH1 0 MSTORE PUSH1 32 PUSH1 0 RETURN: returns block.timestamp as the deployed code.
+		const READ_TIMESTAMP_INIT_CODE = "0x4260005260206000f3";
+
+		await createAndFinalizeBlock(context.web3);
+		const latest = await context.web3.eth.getBlock("latest");
+
+		const result = await customRequest(context.web3, "eth_call", [
+			{
+				from: GENESIS_ACCOUNT,
+				data: READ_TIMESTAMP_INIT_CODE,
+			},
+			"pending",
+		]);
+
+		expect(context.web3.utils.hexToNumber(result.result)).to.be.greaterThan(Number(latest.timestamp));
+	});
 });
@@ -6,6 +6,7 @@ import { AbiItem } from "web3-utils";
 import InvalidOpcode from "../build/contracts/InvalidOpcode.json";
 import Test from "../build/contracts/Test.json";
 import StorageLoop from "../build/contracts/StorageLoop.json";
+import Storage from "../build/contracts/Storage.json";
 import Web3 from "web3";
 import {
 	GENESIS_ACCOUNT,
@@ -132,6 +133,26 @@ describeWithFrontier("Frontier RPC (Gas)", (context) => {
 		expect(result).to.equal(197732);
 	});
 
+	it("eth_estimateGas 0x0 gas is equivalent to not setting one", async function () {
+		let result = await context.web3.eth.estimateGas({
+			from: GENESIS_ACCOUNT,
+			data: Test.bytecode,
+			gas: "0x0",
+		});
+		expect(result).to.equal(197732);
+	});
+
+	it("eth_call 0x0 gas uses the block gas limit", async function () {
+		let result = await customRequest(context.web3, "eth_call", [
+			{
+				from: GENESIS_ACCOUNT,
+				data: Test.bytecode,
+				gas: "0x0",
+			},
+		]);
+		expect(result.result).to.equal(Test.deployedBytecode);
+	});
+
 	it("eth_estimateGas should ignore nonce", async function () {
 		let result = await context.web3.eth.estimateGas({
 			from: GENESIS_ACCOUNT,
@@ -423,3 +444,57 @@ describeWithFrontier("Frontier RPC (Invalid opcode estimate gas)", (context) =>
 		expect(estimate).to.equal(85703);
 	});
 });
+
+describeWithFrontier("Frontier RPC (Gas refunds)", (context) => {
+	const STORAGE_ABI = Storage.abi as AbiItem[];
+	const SLOT = "0x0000000000000000000000000000000000000000000000000000000000000001";
+	let contractAddress: string;
+
+	async function sendTransaction(data: string, to?: string, gas = "0x100000") {
+		const tx = await context.web3.eth.accounts.signTransaction(
+			{
+				from: GENESIS_ACCOUNT,
+				to,
+				data,
+				value: "0x00",
+				gasPrice: "0x3B9ACA00",
+				gas,
+			},
+			GENESIS_ACCOUNT_PRIVATE_KEY
+		);
+		await customRequest(context.web3, "eth_sendRawTransaction", [tx.rawTransaction]);
+		await createAndFinalizeBlock(context.web3);
+		return context.web3.eth.getTransactionReceipt(tx.transactionHash);
+	}
+
+	before("create the contract and set a storage slot", async function () {
+		this.timeout(15000);
+		const receipt = await sendTransaction(Storage.bytecode);
+		contractAddress = receipt.contractAddress;
+
+		const contract = new context.web3.eth.Contract(STORAGE_ABI, contractAddress);
+		const data = contract.methods
+			.setStorage(SLOT, "0x0000000000000000000000000000000000000000000000000000000000000042")
+			.encodeABI();
+		await sendTransaction(data, contractAddress);
+	});
+
+	it("estimate leaves room for refunded gas that the receipt does not report", async function () {
+		this.timeout(15000);
+		const contract = new context.web3.eth.Contract(STORAGE_ABI, contractAddress);
+		// Clearing the slot is refunded at the end of the execution.
+		const data = contract.methods
+			.setStorage(SLOT, "0x0000000000000000000000000000000000000000000000000000000000000000")
+			.encodeABI();
+
+		const estimate = await context.web3.eth.estimateGas({
+			from: GENESIS_ACCOUNT,
+			to: contractAddress,
+			data,
+		});
+		const receipt = await sendTransaction(data, contractAddress, context.web3.utils.toHex(estimate));
+
+		expect(receipt.status).to.be.true;
+		expect(receipt.gasUsed).to.be.lessThan(estimate);
+	});
+});
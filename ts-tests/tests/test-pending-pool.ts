@@ -43,6 +43,32 @@ describeWithFrontier("Frontier RPC (Pending Pool)", (context) => {
 			v: "0x77",
 		});
 	});
+
+	it("should return a transaction from the future pool", async function () {
+		this.timeout(15000);
+		// The nonce gap keeps the transaction in the future pool.
+		const tx = await context.web3.eth.accounts.signTransaction(
+			{
+				from: GENESIS_ACCOUNT,
+				data: TEST_CONTRACT_BYTECODE,
+				value: "0x00",
+				gasPrice: "0x3B9ACA00",
+				gas: "0x100000",
+				nonce: 1000,
+			},
+			GENESIS_ACCOUNT_PRIVATE_KEY
+		);
+
+		const txHash = (await customRequest(context.web3, "eth_sendRawTransaction", [tx.rawTransaction])).result;
+
+		const pendingTransaction = (await customRequest(context.web3, "eth_getTransactionByHash", [txHash])).result;
+		expect(pendingTransaction).to.include({
+			blockHash: null,
+			blockNumber: null,
+			hash: txHash,
+			nonce: "0x3e8",
+		});
+	});
 });
 
 describeWithFrontier("Frontier RPC (Pending Transaction Count)", (context) => {
@@ -0,0 +1,47 @@
+import { expect } from "chai";
+import { step } from "mocha-steps";
+
+import { GENESIS_ACCOUNT, GENESIS_ACCOUNT_PRIVATE_KEY } from "./config";
+import { createAndFinalizeBlock, describeWithFrontier, customRequest } from "./util";
+
+describeWithFrontier("Frontier RPC (Raw transaction sender)", (context) => {
+	const TEST_ACCOUNT = "0x1111111111111111111111111111111111111111";
+
+	step("raw transaction is rejected when signed by another account than `from`", async function () {
+		const tx = await context.web3.eth.accounts.signTransaction(
+			{
+				from: GENESIS_ACCOUNT,
+				to: TEST_ACCOUNT,
+				value: "0x200",
+				gasPrice: "0x3B9ACA00",
+				gas: "0x100000",
+			},
+			GENESIS_ACCOUNT_PRIVATE_KEY
+		);
+
+		const result = await customRequest(context.web3, "eth_sendRawTransaction", [tx.rawTransaction, TEST_ACCOUNT]);
+		expect(result.error.message).to.contain("not by `from`");
+		expect(await context.web3.eth.getTransactionCount(GENESIS_ACCOUNT, "pending")).to.eq(0);
+	});
+
+	step("raw transaction is accepted when signed by `from`", async function () {
+		const tx = await context.web3.eth.accounts.signTransaction(
+			{
+				from: GENESIS_ACCOUNT,
+				to: TEST_ACCOUNT,
+				value: "0x200",
+				gasPrice: "0x3B9ACA00",
+				gas: "0x100000",
+			},
+			GENESIS_ACCOUNT_PRIVATE_KEY
+		);
+
+		const result = await customRequest(context.web3, "eth_sendRawTransaction", [
+			tx.rawTransaction,
+			GENESIS_ACCOUNT,
+		]);
+		expect(result.result).to.be.equal(tx.transactionHash);
+		await createAndFinalizeBlock(context.web3);
+		expect(await context.web3.eth.getTransactionCount(GENESIS_ACCOUNT, "latest")).to.eq(1);
+	});
+});
@@ -531,3 +531,74 @@ describeWithFrontierWs("Frontier RPC (Subscription)", (context) => {
 		done();
 	}).timeout(20000);
 });
+
+describeWithFrontierWs("Frontier RPC (Subscription reorg)", (context) => {
+	// Init code emitting one log: PUSH32 0x11..11 PUSH1 0 MSTORE PUSH1 32 PUSH1 0 LOG0 STOP
+	const TEST_CONTRACT_BYTECODE = "0x7f" + "11".repeat(32) + "6000526020" + "6000a0" + "00";
+
+	// Seal a block on top of `parentHash` (or the best block) and return its substrate hash.
+	async function createBlock(finalize: boolean, parentHash: string | null = null) {
+		const response = await customRequest(context.web3, "engine_createBlock", [true, finalize, parentHash]);
+		if (!response.result) {
+			throw new Error(`Unexpected result: ${JSON.stringify(response)}`);
+		}
+		await new Promise<void>((resolve) => setTimeout(() => resolve(), 500));
+		return response.result.hash;
+	}
+
+	step("should re-emit logs of a retracted block as removed", async function () {
+		const base = await createBlock(true);
+
+		const subscription = context.web3.eth.subscribe("logs", {}, function (error, result) {});
+		await new Promise<void>((resolve) => {
+			subscription.on("connected", function (d: any) {
+				resolve();
+			});
+		});
+
+		const received = [];
+		let removedResolve = null;
+		const removedPromise = new Promise<void>((resolve) => {
+			removedResolve = resolve;
+		});
+		subscription.on("data", function (d: any) {
+			received.push(d);
+		});
+		// web3 reports logs flagged as `removed` through the "changed" event.
+		subscription.on("changed", function (d: any) {
+			received.push(d);
+			removedResolve();
+		});
+
+		const tx = await context.web3.eth.accounts.signTransaction(
+			{
+				from: GENESIS_ACCOUNT,
+				data: TEST_CONTRACT_BYTECODE,
+				value: "0x00",
+				gasPrice: "0x3B9ACA00",
+				gas: "0x100000",
+			},
+			GENESIS_ACCOUNT_PRIVATE_KEY
+		);
+		await customRequest(context.web3, "eth_sendRawTransaction", [tx.rawTransaction]);
+
+		// The log is first emitted from a block that is not finalized...
+		await createBlock(false, base);
+		// ...then a longer fork from the same parent retracts that block.
+		const fork = await createBlock(false, base);
+		await createBlock(false, fork);
+		await removedPromise;
+
+		subscription.unsubscribe();
+		const removed = received.filter((log) => log.removed);
+		expect(received[0].removed).to.be.false;
+		// Only the delivered log is removed, before any log of the new canonical blocks.
+		expect(removed.length).to.equal(1);
+		expect(received[1].removed).to.be.true;
+		expect(removed[0]).to.include({
+			blockHash: received[0].blockHash,
+			transactionHash: tx.transactionHash,
+			logIndex: received[0].logIndex,
+		});
+	}).timeout(40000);
+});
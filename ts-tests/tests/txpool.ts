@@ -56,4 +56,12 @@ describeWithFrontier("Frontier RPC (TxPoolApi)", (context) => {
 			"0x0000000000000000000000000000000000000000: 0 wei + 1048576 gas x 1000000000 wei"
 		);
 	});
+
+	step("txpool_queuedFrom should return the queued transactions of an address", async function () {
+		let queued = await customRequest(context.web3, "txpool_queuedFrom", [GENESIS_ACCOUNT]);
+		expect(queued.result).to.deep.equal({ "0x3": future_tx.transactionHash });
+
+		queued = await customRequest(context.web3, "txpool_queuedFrom", ["0x1111111111111111111111111111111111111111"]);
+		expect(queued.result).to.deep.equal({});
+	});
 });